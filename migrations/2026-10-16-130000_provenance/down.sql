@@ -0,0 +1,2 @@
+DROP TABLE provenance;
+DROP TABLE runs;
@@ -0,0 +1,18 @@
+CREATE TABLE runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    module VARCHAR NOT NULL,
+    version VARCHAR NOT NULL,
+    started DATETIME NOT NULL
+);
+
+CREATE TABLE provenance (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    key INTEGER NOT NULL,
+    run_id INTEGER NOT NULL,
+    action VARCHAR NOT NULL,
+    time DATETIME NOT NULL,
+    FOREIGN KEY(run_id) REFERENCES runs(id) ON DELETE CASCADE
+);
+CREATE INDEX provenance_entity ON provenance(family, key);
+CREATE INDEX provenance_run ON provenance(run_id);
@@ -0,0 +1,24 @@
+PRAGMA foreign_keys=off;
+
+ALTER TABLE provenance RENAME TO _provenance_old;
+
+CREATE TABLE provenance (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    key INTEGER NOT NULL,
+    run_id INTEGER NOT NULL,
+    action VARCHAR NOT NULL,
+    time DATETIME NOT NULL,
+    FOREIGN KEY(run_id) REFERENCES runs(id) ON DELETE CASCADE
+);
+
+INSERT INTO provenance (id, family, key, run_id, action, time)
+  SELECT id, family, key, run_id, action, time
+  FROM _provenance_old;
+
+DROP TABLE _provenance_old;
+
+CREATE INDEX provenance_entity ON provenance(family, key);
+CREATE INDEX provenance_run ON provenance(run_id);
+
+PRAGMA foreign_keys=on;
@@ -0,0 +1 @@
+ALTER TABLE provenance ADD COLUMN previous VARCHAR;
@@ -0,0 +1,11 @@
+CREATE TABLE history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    key INTEGER NOT NULL,
+    field VARCHAR NOT NULL,
+    old VARCHAR NOT NULL,
+    new VARCHAR NOT NULL,
+    time DATETIME NOT NULL,
+    source VARCHAR
+);
+CREATE INDEX history_entity ON history(family, key);
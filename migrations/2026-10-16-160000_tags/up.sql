@@ -0,0 +1,17 @@
+CREATE TABLE tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    key INTEGER NOT NULL,
+    name VARCHAR NOT NULL,
+    CONSTRAINT tag_unique UNIQUE (family, key, name)
+);
+CREATE INDEX tags_name ON tags(name);
+
+CREATE TABLE notes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    key INTEGER NOT NULL,
+    body VARCHAR NOT NULL,
+    time DATETIME NOT NULL
+);
+CREATE INDEX notes_entity ON notes(family, key);
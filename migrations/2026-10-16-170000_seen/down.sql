@@ -0,0 +1,382 @@
+PRAGMA foreign_keys=off;
+
+-- domains
+
+ALTER TABLE domains RENAME TO _domains_old;
+
+CREATE TABLE domains (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    CONSTRAINT domain_unique UNIQUE (value)
+);
+
+INSERT INTO domains (id, value, unscoped)
+  SELECT id, value, unscoped
+  FROM _domains_old;
+
+DROP TABLE _domains_old;
+
+-- subdomains
+
+ALTER TABLE subdomains RENAME TO _subdomains_old;
+
+CREATE TABLE subdomains (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    domain_id INTEGER NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    resolvable BOOLEAN,
+    FOREIGN KEY(domain_id) REFERENCES domains(id) ON DELETE CASCADE,
+    CONSTRAINT subdomain_unique UNIQUE (value)
+);
+
+INSERT INTO subdomains (id, domain_id, value, unscoped, resolvable)
+  SELECT id, domain_id, value, unscoped, resolvable
+  FROM _subdomains_old;
+
+DROP TABLE _subdomains_old;
+
+-- ipaddrs
+
+ALTER TABLE ipaddrs RENAME TO _ipaddrs_old;
+
+CREATE TABLE ipaddrs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    continent VARCHAR,
+    continent_code VARCHAR,
+    country VARCHAR,
+    country_code VARCHAR,
+    city VARCHAR,
+    latitude FLOAT,
+    longitude FLOAT,
+    asn INTEGER,
+    as_org VARCHAR, description VARCHAR, reverse_dns VARCHAR,
+    CONSTRAINT ipaddr_unique UNIQUE (value)
+);
+
+INSERT INTO ipaddrs (id, family, value, unscoped, continent, continent_code, country, country_code, city, latitude, longitude, asn, as_org, description, reverse_dns)
+  SELECT id, family, value, unscoped, continent, continent_code, country, country_code, city, latitude, longitude, asn, as_org, description, reverse_dns
+  FROM _ipaddrs_old;
+
+DROP TABLE _ipaddrs_old;
+
+-- subdomain_ipaddrs
+
+ALTER TABLE subdomain_ipaddrs RENAME TO _subdomain_ipaddrs_old;
+
+CREATE TABLE subdomain_ipaddrs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    subdomain_id INTEGER NOT NULL,
+    ip_addr_id INTEGER NOT NULL,
+    FOREIGN KEY(subdomain_id) REFERENCES subdomains(id) ON DELETE CASCADE,
+    FOREIGN KEY(ip_addr_id) REFERENCES ipaddrs(id) ON DELETE CASCADE,
+    CONSTRAINT subdomain_ipaddr_unique UNIQUE (subdomain_id, ip_addr_id)
+);
+
+INSERT INTO subdomain_ipaddrs (id, subdomain_id, ip_addr_id)
+  SELECT id, subdomain_id, ip_addr_id
+  FROM _subdomain_ipaddrs_old;
+
+DROP TABLE _subdomain_ipaddrs_old;
+
+-- urls
+
+ALTER TABLE urls RENAME TO _urls_old;
+
+CREATE TABLE urls (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    subdomain_id INTEGER NOT NULL,
+    value VARCHAR NOT NULL,
+    path VARCHAR NOT NULL,
+    status INTEGER,
+    body BLOB,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    online BOOLEAN,
+    title VARCHAR,
+    redirect VARCHAR, blob VARCHAR,
+    FOREIGN KEY(subdomain_id) REFERENCES subdomains(id) ON DELETE CASCADE,
+    CONSTRAINT url_unique UNIQUE (value)
+);
+
+INSERT INTO urls (id, subdomain_id, value, path, status, body, unscoped, online, title, redirect, blob)
+  SELECT id, subdomain_id, value, path, status, body, unscoped, online, title, redirect, blob
+  FROM _urls_old;
+
+DROP TABLE _urls_old;
+
+-- emails
+
+ALTER TABLE emails RENAME TO _emails_old;
+
+CREATE TABLE emails (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    valid BOOLEAN, displayname VARCHAR,
+    CONSTRAINT email_unique UNIQUE (value)
+);
+
+INSERT INTO emails (id, value, unscoped, valid, displayname)
+  SELECT id, value, unscoped, valid, displayname
+  FROM _emails_old;
+
+DROP TABLE _emails_old;
+
+-- phonenumbers
+
+ALTER TABLE phonenumbers RENAME TO _phonenumbers_old;
+
+CREATE TABLE phonenumbers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    name VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    valid BOOLEAN,
+    last_online DATETIME,
+    country VARCHAR,
+    carrier VARCHAR,
+    line VARCHAR,
+    is_ported BOOLEAN,
+    last_ported DATETIME,
+    caller_name VARCHAR,
+    caller_type VARCHAR,
+    CONSTRAINT phonenumber_unique UNIQUE (value)
+);
+
+INSERT INTO phonenumbers (id, value, name, unscoped, valid, last_online, country, carrier, line, is_ported, last_ported, caller_name, caller_type)
+  SELECT id, value, name, unscoped, valid, last_online, country, carrier, line, is_ported, last_ported, caller_name, caller_type
+  FROM _phonenumbers_old;
+
+DROP TABLE _phonenumbers_old;
+
+-- devices
+
+ALTER TABLE devices RENAME TO _devices_old;
+
+CREATE TABLE devices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    name VARCHAR,
+    hostname VARCHAR,
+    vendor VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    last_seen DATETIME,
+    CONSTRAINT device_unique UNIQUE (value)
+);
+
+INSERT INTO devices (id, value, name, hostname, vendor, unscoped, last_seen)
+  SELECT id, value, name, hostname, vendor, unscoped, last_seen
+  FROM _devices_old;
+
+DROP TABLE _devices_old;
+
+-- networks
+
+ALTER TABLE networks RENAME TO _networks_old;
+
+CREATE TABLE networks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    latitude FLOAT,
+    longitude FLOAT, description VARCHAR,
+    CONSTRAINT network_unique UNIQUE (value)
+);
+
+INSERT INTO networks (id, value, unscoped, latitude, longitude, description)
+  SELECT id, value, unscoped, latitude, longitude, description
+  FROM _networks_old;
+
+DROP TABLE _networks_old;
+
+-- network_devices
+
+ALTER TABLE network_devices RENAME TO _network_devices_old;
+
+CREATE TABLE network_devices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    network_id INTEGER NOT NULL,
+    device_id INTEGER NOT NULL,
+    ipaddr VARCHAR,
+    last_seen DATETIME,
+    FOREIGN KEY(network_id) REFERENCES networks(id) ON DELETE CASCADE,
+    FOREIGN KEY(device_id) REFERENCES devices(id) ON DELETE CASCADE,
+    CONSTRAINT network_device_unique UNIQUE (network_id, device_id)
+);
+
+INSERT INTO network_devices (id, network_id, device_id, ipaddr, last_seen)
+  SELECT id, network_id, device_id, ipaddr, last_seen
+  FROM _network_devices_old;
+
+DROP TABLE _network_devices_old;
+
+-- accounts
+
+ALTER TABLE accounts RENAME TO _accounts_old;
+
+CREATE TABLE accounts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    service VARCHAR NOT NULL,
+    username VARCHAR NOT NULL,
+    displayname VARCHAR,
+    email VARCHAR,
+    url VARCHAR,
+    last_seen DATETIME,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL, phonenumber VARCHAR, profile_pic VARCHAR, birthday VARCHAR,
+    CONSTRAINT account_unique UNIQUE (value)
+);
+
+INSERT INTO accounts (id, value, service, username, displayname, email, url, last_seen, unscoped, phonenumber, profile_pic, birthday)
+  SELECT id, value, service, username, displayname, email, url, last_seen, unscoped, phonenumber, profile_pic, birthday
+  FROM _accounts_old;
+
+DROP TABLE _accounts_old;
+
+-- breaches
+
+ALTER TABLE breaches RENAME TO _breaches_old;
+
+CREATE TABLE breaches (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    CONSTRAINT breach_unique UNIQUE (value)
+);
+
+INSERT INTO breaches (id, value, unscoped)
+  SELECT id, value, unscoped
+  FROM _breaches_old;
+
+DROP TABLE _breaches_old;
+
+-- breach_emails
+
+ALTER TABLE breach_emails RENAME TO _breach_emails_old;
+
+CREATE TABLE breach_emails (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    breach_id INTEGER NOT NULL,
+    email_id INTEGER NOT NULL,
+    password VARCHAR,
+    FOREIGN KEY(breach_id) REFERENCES breaches(id) ON DELETE CASCADE,
+    FOREIGN KEY(email_id) REFERENCES emails(id) ON DELETE CASCADE,
+    CONSTRAINT breach_emails_unique UNIQUE (breach_id, email_id, password)
+);
+
+INSERT INTO breach_emails (id, breach_id, email_id, password)
+  SELECT id, breach_id, email_id, password
+  FROM _breach_emails_old;
+
+DROP TABLE _breach_emails_old;
+
+-- images
+
+ALTER TABLE images RENAME TO _images_old;
+
+CREATE TABLE images (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+
+    filename VARCHAR,
+    mime VARCHAR,
+    width INT,
+    height INT,
+    created DATETIME,
+
+    latitude FLOAT,
+    longitude FLOAT,
+
+    nudity FLOAT,
+    ahash VARCHAR,
+    dhash VARCHAR,
+    phash VARCHAR,
+
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    CONSTRAINT image_unique UNIQUE (value)
+);
+
+INSERT INTO images (id, value, filename, mime, width, height, created, latitude, longitude, nudity, ahash, dhash, phash, unscoped)
+  SELECT id, value, filename, mime, width, height, created, latitude, longitude, nudity, ahash, dhash, phash, unscoped
+  FROM _images_old;
+
+DROP TABLE _images_old;
+
+-- ports
+
+ALTER TABLE ports RENAME TO _ports_old;
+
+CREATE TABLE ports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    ip_addr_id INTEGER NOT NULL,
+    value VARCHAR NOT NULL,
+    ip_addr VARCHAR NOT NULL,
+    port INTEGER NOT NULL,
+    protocol VARCHAR NOT NULL,
+    status VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+
+    banner VARCHAR,
+    service VARCHAR,
+    version VARCHAR,
+
+    FOREIGN KEY(ip_addr_id) REFERENCES ipaddrs(id) ON DELETE CASCADE,
+    CONSTRAINT port_unique UNIQUE (value)
+);
+
+INSERT INTO ports (id, ip_addr_id, value, ip_addr, port, protocol, status, unscoped, banner, service, version)
+  SELECT id, ip_addr_id, value, ip_addr, port, protocol, status, unscoped, banner, service, version
+  FROM _ports_old;
+
+DROP TABLE _ports_old;
+
+-- netblocks
+
+ALTER TABLE netblocks RENAME TO _netblocks_old;
+
+CREATE TABLE netblocks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    family VARCHAR NOT NULL,
+    value VARCHAR NOT NULL,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    asn INTEGER,
+    as_org VARCHAR,
+    description VARCHAR,
+    CONSTRAINT netblock_unique UNIQUE (value)
+);
+
+INSERT INTO netblocks (id, family, value, unscoped, asn, as_org, description)
+  SELECT id, family, value, unscoped, asn, as_org, description
+  FROM _netblocks_old;
+
+DROP TABLE _netblocks_old;
+
+-- cryptoaddrs
+
+ALTER TABLE cryptoaddrs RENAME TO _cryptoaddrs_old;
+
+CREATE TABLE cryptoaddrs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    currency VARCHAR,
+    denominator INTEGER,
+    balance BIGINT,
+    received BIGINT,
+    first_seen DATETIME,
+    last_withdrawal DATETIME,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    description VARCHAR,
+    CONSTRAINT netblock_unique UNIQUE (value)
+);
+
+INSERT INTO cryptoaddrs (id, value, currency, denominator, balance, received, first_seen, last_withdrawal, unscoped, description)
+  SELECT id, value, currency, denominator, balance, received, first_seen, last_withdrawal, unscoped, description
+  FROM _cryptoaddrs_old;
+
+DROP TABLE _cryptoaddrs_old;
+
+PRAGMA foreign_keys=on;
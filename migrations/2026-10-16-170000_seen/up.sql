@@ -0,0 +1,30 @@
+ALTER TABLE domains ADD COLUMN first_seen DATETIME;
+ALTER TABLE domains ADD COLUMN last_seen DATETIME;
+ALTER TABLE subdomains ADD COLUMN first_seen DATETIME;
+ALTER TABLE subdomains ADD COLUMN last_seen DATETIME;
+ALTER TABLE ipaddrs ADD COLUMN first_seen DATETIME;
+ALTER TABLE ipaddrs ADD COLUMN last_seen DATETIME;
+ALTER TABLE subdomain_ipaddrs ADD COLUMN first_seen DATETIME;
+ALTER TABLE subdomain_ipaddrs ADD COLUMN last_seen DATETIME;
+ALTER TABLE urls ADD COLUMN first_seen DATETIME;
+ALTER TABLE urls ADD COLUMN last_seen DATETIME;
+ALTER TABLE emails ADD COLUMN first_seen DATETIME;
+ALTER TABLE emails ADD COLUMN last_seen DATETIME;
+ALTER TABLE phonenumbers ADD COLUMN first_seen DATETIME;
+ALTER TABLE phonenumbers ADD COLUMN last_seen DATETIME;
+ALTER TABLE devices ADD COLUMN first_seen DATETIME;
+ALTER TABLE networks ADD COLUMN first_seen DATETIME;
+ALTER TABLE networks ADD COLUMN last_seen DATETIME;
+ALTER TABLE network_devices ADD COLUMN first_seen DATETIME;
+ALTER TABLE accounts ADD COLUMN first_seen DATETIME;
+ALTER TABLE breaches ADD COLUMN first_seen DATETIME;
+ALTER TABLE breaches ADD COLUMN last_seen DATETIME;
+ALTER TABLE breach_emails ADD COLUMN first_seen DATETIME;
+ALTER TABLE breach_emails ADD COLUMN last_seen DATETIME;
+ALTER TABLE images ADD COLUMN first_seen DATETIME;
+ALTER TABLE images ADD COLUMN last_seen DATETIME;
+ALTER TABLE ports ADD COLUMN first_seen DATETIME;
+ALTER TABLE ports ADD COLUMN last_seen DATETIME;
+ALTER TABLE netblocks ADD COLUMN first_seen DATETIME;
+ALTER TABLE netblocks ADD COLUMN last_seen DATETIME;
+ALTER TABLE cryptoaddrs ADD COLUMN last_seen DATETIME;
@@ -0,0 +1,74 @@
+DROP TRIGGER search_domains_insert;
+DROP TRIGGER search_domains_delete;
+DROP TRIGGER search_domains_update;
+DROP TABLE search_domains;
+
+DROP TRIGGER search_subdomains_insert;
+DROP TRIGGER search_subdomains_delete;
+DROP TRIGGER search_subdomains_update;
+DROP TABLE search_subdomains;
+
+DROP TRIGGER search_ipaddrs_insert;
+DROP TRIGGER search_ipaddrs_delete;
+DROP TRIGGER search_ipaddrs_update;
+DROP TABLE search_ipaddrs;
+
+DROP TRIGGER search_urls_insert;
+DROP TRIGGER search_urls_delete;
+DROP TRIGGER search_urls_update;
+DROP TABLE search_urls;
+
+DROP TRIGGER search_emails_insert;
+DROP TRIGGER search_emails_delete;
+DROP TRIGGER search_emails_update;
+DROP TABLE search_emails;
+
+DROP TRIGGER search_phonenumbers_insert;
+DROP TRIGGER search_phonenumbers_delete;
+DROP TRIGGER search_phonenumbers_update;
+DROP TABLE search_phonenumbers;
+
+DROP TRIGGER search_devices_insert;
+DROP TRIGGER search_devices_delete;
+DROP TRIGGER search_devices_update;
+DROP TABLE search_devices;
+
+DROP TRIGGER search_networks_insert;
+DROP TRIGGER search_networks_delete;
+DROP TRIGGER search_networks_update;
+DROP TABLE search_networks;
+
+DROP TRIGGER search_accounts_insert;
+DROP TRIGGER search_accounts_delete;
+DROP TRIGGER search_accounts_update;
+DROP TABLE search_accounts;
+
+DROP TRIGGER search_breaches_insert;
+DROP TRIGGER search_breaches_delete;
+DROP TRIGGER search_breaches_update;
+DROP TABLE search_breaches;
+
+DROP TRIGGER search_images_insert;
+DROP TRIGGER search_images_delete;
+DROP TRIGGER search_images_update;
+DROP TABLE search_images;
+
+DROP TRIGGER search_ports_insert;
+DROP TRIGGER search_ports_delete;
+DROP TRIGGER search_ports_update;
+DROP TABLE search_ports;
+
+DROP TRIGGER search_netblocks_insert;
+DROP TRIGGER search_netblocks_delete;
+DROP TRIGGER search_netblocks_update;
+DROP TABLE search_netblocks;
+
+DROP TRIGGER search_cryptoaddrs_insert;
+DROP TRIGGER search_cryptoaddrs_delete;
+DROP TRIGGER search_cryptoaddrs_update;
+DROP TABLE search_cryptoaddrs;
+
+DROP TRIGGER search_activity_insert;
+DROP TRIGGER search_activity_delete;
+DROP TRIGGER search_activity_update;
+DROP TABLE search_activity;
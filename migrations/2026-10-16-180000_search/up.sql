@@ -0,0 +1,284 @@
+-- domains
+
+CREATE VIRTUAL TABLE search_domains USING fts5(value, content='domains', content_rowid='id');
+
+CREATE TRIGGER search_domains_insert AFTER INSERT ON domains BEGIN
+    INSERT INTO search_domains (rowid, value) VALUES (new.id, new.value);
+END;
+
+CREATE TRIGGER search_domains_delete AFTER DELETE ON domains BEGIN
+    INSERT INTO search_domains (search_domains, rowid, value) VALUES ('delete', old.id, old.value);
+END;
+
+CREATE TRIGGER search_domains_update AFTER UPDATE OF value ON domains BEGIN
+    INSERT INTO search_domains (search_domains, rowid, value) VALUES ('delete', old.id, old.value);
+    INSERT INTO search_domains (rowid, value) VALUES (new.id, new.value);
+END;
+
+INSERT INTO search_domains (search_domains) VALUES ('rebuild');
+
+-- subdomains
+
+CREATE VIRTUAL TABLE search_subdomains USING fts5(value, content='subdomains', content_rowid='id');
+
+CREATE TRIGGER search_subdomains_insert AFTER INSERT ON subdomains BEGIN
+    INSERT INTO search_subdomains (rowid, value) VALUES (new.id, new.value);
+END;
+
+CREATE TRIGGER search_subdomains_delete AFTER DELETE ON subdomains BEGIN
+    INSERT INTO search_subdomains (search_subdomains, rowid, value) VALUES ('delete', old.id, old.value);
+END;
+
+CREATE TRIGGER search_subdomains_update AFTER UPDATE OF value ON subdomains BEGIN
+    INSERT INTO search_subdomains (search_subdomains, rowid, value) VALUES ('delete', old.id, old.value);
+    INSERT INTO search_subdomains (rowid, value) VALUES (new.id, new.value);
+END;
+
+INSERT INTO search_subdomains (search_subdomains) VALUES ('rebuild');
+
+-- ipaddrs
+
+CREATE VIRTUAL TABLE search_ipaddrs USING fts5(value, continent, country, city, as_org, description, reverse_dns, content='ipaddrs', content_rowid='id');
+
+CREATE TRIGGER search_ipaddrs_insert AFTER INSERT ON ipaddrs BEGIN
+    INSERT INTO search_ipaddrs (rowid, value, continent, country, city, as_org, description, reverse_dns) VALUES (new.id, new.value, new.continent, new.country, new.city, new.as_org, new.description, new.reverse_dns);
+END;
+
+CREATE TRIGGER search_ipaddrs_delete AFTER DELETE ON ipaddrs BEGIN
+    INSERT INTO search_ipaddrs (search_ipaddrs, rowid, value, continent, country, city, as_org, description, reverse_dns) VALUES ('delete', old.id, old.value, old.continent, old.country, old.city, old.as_org, old.description, old.reverse_dns);
+END;
+
+CREATE TRIGGER search_ipaddrs_update AFTER UPDATE OF value, continent, country, city, as_org, description, reverse_dns ON ipaddrs BEGIN
+    INSERT INTO search_ipaddrs (search_ipaddrs, rowid, value, continent, country, city, as_org, description, reverse_dns) VALUES ('delete', old.id, old.value, old.continent, old.country, old.city, old.as_org, old.description, old.reverse_dns);
+    INSERT INTO search_ipaddrs (rowid, value, continent, country, city, as_org, description, reverse_dns) VALUES (new.id, new.value, new.continent, new.country, new.city, new.as_org, new.description, new.reverse_dns);
+END;
+
+INSERT INTO search_ipaddrs (search_ipaddrs) VALUES ('rebuild');
+
+-- urls
+
+CREATE VIRTUAL TABLE search_urls USING fts5(value, title, redirect, content='urls', content_rowid='id');
+
+CREATE TRIGGER search_urls_insert AFTER INSERT ON urls BEGIN
+    INSERT INTO search_urls (rowid, value, title, redirect) VALUES (new.id, new.value, new.title, new.redirect);
+END;
+
+CREATE TRIGGER search_urls_delete AFTER DELETE ON urls BEGIN
+    INSERT INTO search_urls (search_urls, rowid, value, title, redirect) VALUES ('delete', old.id, old.value, old.title, old.redirect);
+END;
+
+CREATE TRIGGER search_urls_update AFTER UPDATE OF value, title, redirect ON urls BEGIN
+    INSERT INTO search_urls (search_urls, rowid, value, title, redirect) VALUES ('delete', old.id, old.value, old.title, old.redirect);
+    INSERT INTO search_urls (rowid, value, title, redirect) VALUES (new.id, new.value, new.title, new.redirect);
+END;
+
+INSERT INTO search_urls (search_urls) VALUES ('rebuild');
+
+-- emails
+
+CREATE VIRTUAL TABLE search_emails USING fts5(value, displayname, content='emails', content_rowid='id');
+
+CREATE TRIGGER search_emails_insert AFTER INSERT ON emails BEGIN
+    INSERT INTO search_emails (rowid, value, displayname) VALUES (new.id, new.value, new.displayname);
+END;
+
+CREATE TRIGGER search_emails_delete AFTER DELETE ON emails BEGIN
+    INSERT INTO search_emails (search_emails, rowid, value, displayname) VALUES ('delete', old.id, old.value, old.displayname);
+END;
+
+CREATE TRIGGER search_emails_update AFTER UPDATE OF value, displayname ON emails BEGIN
+    INSERT INTO search_emails (search_emails, rowid, value, displayname) VALUES ('delete', old.id, old.value, old.displayname);
+    INSERT INTO search_emails (rowid, value, displayname) VALUES (new.id, new.value, new.displayname);
+END;
+
+INSERT INTO search_emails (search_emails) VALUES ('rebuild');
+
+-- phonenumbers
+
+CREATE VIRTUAL TABLE search_phonenumbers USING fts5(value, name, country, carrier, caller_name, content='phonenumbers', content_rowid='id');
+
+CREATE TRIGGER search_phonenumbers_insert AFTER INSERT ON phonenumbers BEGIN
+    INSERT INTO search_phonenumbers (rowid, value, name, country, carrier, caller_name) VALUES (new.id, new.value, new.name, new.country, new.carrier, new.caller_name);
+END;
+
+CREATE TRIGGER search_phonenumbers_delete AFTER DELETE ON phonenumbers BEGIN
+    INSERT INTO search_phonenumbers (search_phonenumbers, rowid, value, name, country, carrier, caller_name) VALUES ('delete', old.id, old.value, old.name, old.country, old.carrier, old.caller_name);
+END;
+
+CREATE TRIGGER search_phonenumbers_update AFTER UPDATE OF value, name, country, carrier, caller_name ON phonenumbers BEGIN
+    INSERT INTO search_phonenumbers (search_phonenumbers, rowid, value, name, country, carrier, caller_name) VALUES ('delete', old.id, old.value, old.name, old.country, old.carrier, old.caller_name);
+    INSERT INTO search_phonenumbers (rowid, value, name, country, carrier, caller_name) VALUES (new.id, new.value, new.name, new.country, new.carrier, new.caller_name);
+END;
+
+INSERT INTO search_phonenumbers (search_phonenumbers) VALUES ('rebuild');
+
+-- devices
+
+CREATE VIRTUAL TABLE search_devices USING fts5(value, name, hostname, vendor, content='devices', content_rowid='id');
+
+CREATE TRIGGER search_devices_insert AFTER INSERT ON devices BEGIN
+    INSERT INTO search_devices (rowid, value, name, hostname, vendor) VALUES (new.id, new.value, new.name, new.hostname, new.vendor);
+END;
+
+CREATE TRIGGER search_devices_delete AFTER DELETE ON devices BEGIN
+    INSERT INTO search_devices (search_devices, rowid, value, name, hostname, vendor) VALUES ('delete', old.id, old.value, old.name, old.hostname, old.vendor);
+END;
+
+CREATE TRIGGER search_devices_update AFTER UPDATE OF value, name, hostname, vendor ON devices BEGIN
+    INSERT INTO search_devices (search_devices, rowid, value, name, hostname, vendor) VALUES ('delete', old.id, old.value, old.name, old.hostname, old.vendor);
+    INSERT INTO search_devices (rowid, value, name, hostname, vendor) VALUES (new.id, new.value, new.name, new.hostname, new.vendor);
+END;
+
+INSERT INTO search_devices (search_devices) VALUES ('rebuild');
+
+-- networks
+
+CREATE VIRTUAL TABLE search_networks USING fts5(value, description, content='networks', content_rowid='id');
+
+CREATE TRIGGER search_networks_insert AFTER INSERT ON networks BEGIN
+    INSERT INTO search_networks (rowid, value, description) VALUES (new.id, new.value, new.description);
+END;
+
+CREATE TRIGGER search_networks_delete AFTER DELETE ON networks BEGIN
+    INSERT INTO search_networks (search_networks, rowid, value, description) VALUES ('delete', old.id, old.value, old.description);
+END;
+
+CREATE TRIGGER search_networks_update AFTER UPDATE OF value, description ON networks BEGIN
+    INSERT INTO search_networks (search_networks, rowid, value, description) VALUES ('delete', old.id, old.value, old.description);
+    INSERT INTO search_networks (rowid, value, description) VALUES (new.id, new.value, new.description);
+END;
+
+INSERT INTO search_networks (search_networks) VALUES ('rebuild');
+
+-- accounts
+
+CREATE VIRTUAL TABLE search_accounts USING fts5(value, service, username, displayname, email, url, phonenumber, content='accounts', content_rowid='id');
+
+CREATE TRIGGER search_accounts_insert AFTER INSERT ON accounts BEGIN
+    INSERT INTO search_accounts (rowid, value, service, username, displayname, email, url, phonenumber) VALUES (new.id, new.value, new.service, new.username, new.displayname, new.email, new.url, new.phonenumber);
+END;
+
+CREATE TRIGGER search_accounts_delete AFTER DELETE ON accounts BEGIN
+    INSERT INTO search_accounts (search_accounts, rowid, value, service, username, displayname, email, url, phonenumber) VALUES ('delete', old.id, old.value, old.service, old.username, old.displayname, old.email, old.url, old.phonenumber);
+END;
+
+CREATE TRIGGER search_accounts_update AFTER UPDATE OF value, service, username, displayname, email, url, phonenumber ON accounts BEGIN
+    INSERT INTO search_accounts (search_accounts, rowid, value, service, username, displayname, email, url, phonenumber) VALUES ('delete', old.id, old.value, old.service, old.username, old.displayname, old.email, old.url, old.phonenumber);
+    INSERT INTO search_accounts (rowid, value, service, username, displayname, email, url, phonenumber) VALUES (new.id, new.value, new.service, new.username, new.displayname, new.email, new.url, new.phonenumber);
+END;
+
+INSERT INTO search_accounts (search_accounts) VALUES ('rebuild');
+
+-- breaches
+
+CREATE VIRTUAL TABLE search_breaches USING fts5(value, content='breaches', content_rowid='id');
+
+CREATE TRIGGER search_breaches_insert AFTER INSERT ON breaches BEGIN
+    INSERT INTO search_breaches (rowid, value) VALUES (new.id, new.value);
+END;
+
+CREATE TRIGGER search_breaches_delete AFTER DELETE ON breaches BEGIN
+    INSERT INTO search_breaches (search_breaches, rowid, value) VALUES ('delete', old.id, old.value);
+END;
+
+CREATE TRIGGER search_breaches_update AFTER UPDATE OF value ON breaches BEGIN
+    INSERT INTO search_breaches (search_breaches, rowid, value) VALUES ('delete', old.id, old.value);
+    INSERT INTO search_breaches (rowid, value) VALUES (new.id, new.value);
+END;
+
+INSERT INTO search_breaches (search_breaches) VALUES ('rebuild');
+
+-- images
+
+CREATE VIRTUAL TABLE search_images USING fts5(value, filename, content='images', content_rowid='id');
+
+CREATE TRIGGER search_images_insert AFTER INSERT ON images BEGIN
+    INSERT INTO search_images (rowid, value, filename) VALUES (new.id, new.value, new.filename);
+END;
+
+CREATE TRIGGER search_images_delete AFTER DELETE ON images BEGIN
+    INSERT INTO search_images (search_images, rowid, value, filename) VALUES ('delete', old.id, old.value, old.filename);
+END;
+
+CREATE TRIGGER search_images_update AFTER UPDATE OF value, filename ON images BEGIN
+    INSERT INTO search_images (search_images, rowid, value, filename) VALUES ('delete', old.id, old.value, old.filename);
+    INSERT INTO search_images (rowid, value, filename) VALUES (new.id, new.value, new.filename);
+END;
+
+INSERT INTO search_images (search_images) VALUES ('rebuild');
+
+-- ports
+
+CREATE VIRTUAL TABLE search_ports USING fts5(value, banner, service, version, content='ports', content_rowid='id');
+
+CREATE TRIGGER search_ports_insert AFTER INSERT ON ports BEGIN
+    INSERT INTO search_ports (rowid, value, banner, service, version) VALUES (new.id, new.value, new.banner, new.service, new.version);
+END;
+
+CREATE TRIGGER search_ports_delete AFTER DELETE ON ports BEGIN
+    INSERT INTO search_ports (search_ports, rowid, value, banner, service, version) VALUES ('delete', old.id, old.value, old.banner, old.service, old.version);
+END;
+
+CREATE TRIGGER search_ports_update AFTER UPDATE OF value, banner, service, version ON ports BEGIN
+    INSERT INTO search_ports (search_ports, rowid, value, banner, service, version) VALUES ('delete', old.id, old.value, old.banner, old.service, old.version);
+    INSERT INTO search_ports (rowid, value, banner, service, version) VALUES (new.id, new.value, new.banner, new.service, new.version);
+END;
+
+INSERT INTO search_ports (search_ports) VALUES ('rebuild');
+
+-- netblocks
+
+CREATE VIRTUAL TABLE search_netblocks USING fts5(value, as_org, description, content='netblocks', content_rowid='id');
+
+CREATE TRIGGER search_netblocks_insert AFTER INSERT ON netblocks BEGIN
+    INSERT INTO search_netblocks (rowid, value, as_org, description) VALUES (new.id, new.value, new.as_org, new.description);
+END;
+
+CREATE TRIGGER search_netblocks_delete AFTER DELETE ON netblocks BEGIN
+    INSERT INTO search_netblocks (search_netblocks, rowid, value, as_org, description) VALUES ('delete', old.id, old.value, old.as_org, old.description);
+END;
+
+CREATE TRIGGER search_netblocks_update AFTER UPDATE OF value, as_org, description ON netblocks BEGIN
+    INSERT INTO search_netblocks (search_netblocks, rowid, value, as_org, description) VALUES ('delete', old.id, old.value, old.as_org, old.description);
+    INSERT INTO search_netblocks (rowid, value, as_org, description) VALUES (new.id, new.value, new.as_org, new.description);
+END;
+
+INSERT INTO search_netblocks (search_netblocks) VALUES ('rebuild');
+
+-- cryptoaddrs
+
+CREATE VIRTUAL TABLE search_cryptoaddrs USING fts5(value, currency, description, content='cryptoaddrs', content_rowid='id');
+
+CREATE TRIGGER search_cryptoaddrs_insert AFTER INSERT ON cryptoaddrs BEGIN
+    INSERT INTO search_cryptoaddrs (rowid, value, currency, description) VALUES (new.id, new.value, new.currency, new.description);
+END;
+
+CREATE TRIGGER search_cryptoaddrs_delete AFTER DELETE ON cryptoaddrs BEGIN
+    INSERT INTO search_cryptoaddrs (search_cryptoaddrs, rowid, value, currency, description) VALUES ('delete', old.id, old.value, old.currency, old.description);
+END;
+
+CREATE TRIGGER search_cryptoaddrs_update AFTER UPDATE OF value, currency, description ON cryptoaddrs BEGIN
+    INSERT INTO search_cryptoaddrs (search_cryptoaddrs, rowid, value, currency, description) VALUES ('delete', old.id, old.value, old.currency, old.description);
+    INSERT INTO search_cryptoaddrs (rowid, value, currency, description) VALUES (new.id, new.value, new.currency, new.description);
+END;
+
+INSERT INTO search_cryptoaddrs (search_cryptoaddrs) VALUES ('rebuild');
+
+-- activity
+
+CREATE VIRTUAL TABLE search_activity USING fts5(topic, uniq, content, content='activity', content_rowid='id');
+
+CREATE TRIGGER search_activity_insert AFTER INSERT ON activity BEGIN
+    INSERT INTO search_activity (rowid, topic, uniq, content) VALUES (new.id, new.topic, new.uniq, new.content);
+END;
+
+CREATE TRIGGER search_activity_delete AFTER DELETE ON activity BEGIN
+    INSERT INTO search_activity (search_activity, rowid, topic, uniq, content) VALUES ('delete', old.id, old.topic, old.uniq, old.content);
+END;
+
+CREATE TRIGGER search_activity_update AFTER UPDATE OF topic, uniq, content ON activity BEGIN
+    INSERT INTO search_activity (search_activity, rowid, topic, uniq, content) VALUES ('delete', old.id, old.topic, old.uniq, old.content);
+    INSERT INTO search_activity (rowid, topic, uniq, content) VALUES (new.id, new.topic, new.uniq, new.content);
+END;
+
+INSERT INTO search_activity (search_activity) VALUES ('rebuild');
@@ -0,0 +1,8 @@
+DROP TRIGGER search_certificates_insert;
+DROP TRIGGER search_certificates_delete;
+DROP TRIGGER search_certificates_update;
+DROP TABLE search_certificates;
+
+DROP TABLE subdomain_certificates;
+DROP TABLE port_certificates;
+DROP TABLE certificates;
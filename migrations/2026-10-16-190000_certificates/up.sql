@@ -0,0 +1,52 @@
+CREATE TABLE certificates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    subject VARCHAR,
+    issuer VARCHAR,
+    sans VARCHAR,
+    serial VARCHAR,
+    key_type VARCHAR,
+    not_before DATETIME,
+    not_after DATETIME,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    CONSTRAINT certificate_unique UNIQUE (value)
+);
+
+CREATE TABLE port_certificates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    port_id INTEGER NOT NULL,
+    certificate_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(port_id) REFERENCES ports(id) ON DELETE CASCADE,
+    FOREIGN KEY(certificate_id) REFERENCES certificates(id) ON DELETE CASCADE,
+    CONSTRAINT port_certificate_unique UNIQUE (port_id, certificate_id)
+);
+
+CREATE TABLE subdomain_certificates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    subdomain_id INTEGER NOT NULL,
+    certificate_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(subdomain_id) REFERENCES subdomains(id) ON DELETE CASCADE,
+    FOREIGN KEY(certificate_id) REFERENCES certificates(id) ON DELETE CASCADE,
+    CONSTRAINT subdomain_certificate_unique UNIQUE (subdomain_id, certificate_id)
+);
+
+CREATE VIRTUAL TABLE search_certificates USING fts5(value, subject, issuer, sans, content='certificates', content_rowid='id');
+
+CREATE TRIGGER search_certificates_insert AFTER INSERT ON certificates BEGIN
+    INSERT INTO search_certificates (rowid, value, subject, issuer, sans) VALUES (new.id, new.value, new.subject, new.issuer, new.sans);
+END;
+
+CREATE TRIGGER search_certificates_delete AFTER DELETE ON certificates BEGIN
+    INSERT INTO search_certificates (search_certificates, rowid, value, subject, issuer, sans) VALUES ('delete', old.id, old.value, old.subject, old.issuer, old.sans);
+END;
+
+CREATE TRIGGER search_certificates_update AFTER UPDATE OF value, subject, issuer, sans ON certificates BEGIN
+    INSERT INTO search_certificates (search_certificates, rowid, value, subject, issuer, sans) VALUES ('delete', old.id, old.value, old.subject, old.issuer, old.sans);
+    INSERT INTO search_certificates (rowid, value, subject, issuer, sans) VALUES (new.id, new.value, new.subject, new.issuer, new.sans);
+END;
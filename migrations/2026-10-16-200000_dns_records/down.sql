@@ -0,0 +1,6 @@
+DROP TRIGGER search_dns_records_insert;
+DROP TRIGGER search_dns_records_delete;
+DROP TRIGGER search_dns_records_update;
+DROP TABLE search_dns_records;
+
+DROP TABLE dns_records;
@@ -0,0 +1,29 @@
+CREATE TABLE dns_records (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    subdomain_id INTEGER NOT NULL,
+    value VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    record_type VARCHAR NOT NULL,
+    rdata VARCHAR NOT NULL,
+    ttl INTEGER,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(subdomain_id) REFERENCES subdomains(id) ON DELETE CASCADE,
+    CONSTRAINT dns_record_unique UNIQUE (value)
+);
+
+CREATE VIRTUAL TABLE search_dns_records USING fts5(value, content='dns_records', content_rowid='id');
+
+CREATE TRIGGER search_dns_records_insert AFTER INSERT ON dns_records BEGIN
+    INSERT INTO search_dns_records (rowid, value) VALUES (new.id, new.value);
+END;
+
+CREATE TRIGGER search_dns_records_delete AFTER DELETE ON dns_records BEGIN
+    INSERT INTO search_dns_records (search_dns_records, rowid, value) VALUES ('delete', old.id, old.value);
+END;
+
+CREATE TRIGGER search_dns_records_update AFTER UPDATE OF value ON dns_records BEGIN
+    INSERT INTO search_dns_records (search_dns_records, rowid, value) VALUES ('delete', old.id, old.value);
+    INSERT INTO search_dns_records (rowid, value) VALUES (new.id, new.value);
+END;
@@ -0,0 +1,18 @@
+DROP TRIGGER search_persons_insert;
+DROP TRIGGER search_persons_delete;
+DROP TRIGGER search_persons_update;
+DROP TABLE search_persons;
+
+DROP TRIGGER search_organizations_insert;
+DROP TRIGGER search_organizations_delete;
+DROP TRIGGER search_organizations_update;
+DROP TABLE search_organizations;
+
+DROP TABLE organization_domains;
+DROP TABLE person_organizations;
+DROP TABLE person_images;
+DROP TABLE person_phonenumbers;
+DROP TABLE person_accounts;
+DROP TABLE person_emails;
+DROP TABLE organizations;
+DROP TABLE persons;
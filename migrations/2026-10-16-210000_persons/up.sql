@@ -0,0 +1,115 @@
+CREATE TABLE persons (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    description VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    CONSTRAINT person_unique UNIQUE (value)
+);
+
+CREATE TABLE organizations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    description VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    CONSTRAINT organization_unique UNIQUE (value)
+);
+
+CREATE TABLE person_emails (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    person_id INTEGER NOT NULL,
+    email_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE,
+    FOREIGN KEY(email_id) REFERENCES emails(id) ON DELETE CASCADE,
+    CONSTRAINT person_email_unique UNIQUE (person_id, email_id)
+);
+
+CREATE TABLE person_accounts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    person_id INTEGER NOT NULL,
+    account_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE,
+    FOREIGN KEY(account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    CONSTRAINT person_account_unique UNIQUE (person_id, account_id)
+);
+
+CREATE TABLE person_phonenumbers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    person_id INTEGER NOT NULL,
+    phonenumber_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE,
+    FOREIGN KEY(phonenumber_id) REFERENCES phonenumbers(id) ON DELETE CASCADE,
+    CONSTRAINT person_phonenumber_unique UNIQUE (person_id, phonenumber_id)
+);
+
+CREATE TABLE person_images (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    person_id INTEGER NOT NULL,
+    image_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE,
+    FOREIGN KEY(image_id) REFERENCES images(id) ON DELETE CASCADE,
+    CONSTRAINT person_image_unique UNIQUE (person_id, image_id)
+);
+
+CREATE TABLE person_organizations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    person_id INTEGER NOT NULL,
+    organization_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(person_id) REFERENCES persons(id) ON DELETE CASCADE,
+    FOREIGN KEY(organization_id) REFERENCES organizations(id) ON DELETE CASCADE,
+    CONSTRAINT person_organization_unique UNIQUE (person_id, organization_id)
+);
+
+CREATE TABLE organization_domains (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    organization_id INTEGER NOT NULL,
+    domain_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(organization_id) REFERENCES organizations(id) ON DELETE CASCADE,
+    FOREIGN KEY(domain_id) REFERENCES domains(id) ON DELETE CASCADE,
+    CONSTRAINT organization_domain_unique UNIQUE (organization_id, domain_id)
+);
+
+CREATE VIRTUAL TABLE search_persons USING fts5(value, description, content='persons', content_rowid='id');
+
+CREATE TRIGGER search_persons_insert AFTER INSERT ON persons BEGIN
+    INSERT INTO search_persons (rowid, value, description) VALUES (new.id, new.value, new.description);
+END;
+
+CREATE TRIGGER search_persons_delete AFTER DELETE ON persons BEGIN
+    INSERT INTO search_persons (search_persons, rowid, value, description) VALUES ('delete', old.id, old.value, old.description);
+END;
+
+CREATE TRIGGER search_persons_update AFTER UPDATE OF value, description ON persons BEGIN
+    INSERT INTO search_persons (search_persons, rowid, value, description) VALUES ('delete', old.id, old.value, old.description);
+    INSERT INTO search_persons (rowid, value, description) VALUES (new.id, new.value, new.description);
+END;
+
+CREATE VIRTUAL TABLE search_organizations USING fts5(value, description, content='organizations', content_rowid='id');
+
+CREATE TRIGGER search_organizations_insert AFTER INSERT ON organizations BEGIN
+    INSERT INTO search_organizations (rowid, value, description) VALUES (new.id, new.value, new.description);
+END;
+
+CREATE TRIGGER search_organizations_delete AFTER DELETE ON organizations BEGIN
+    INSERT INTO search_organizations (search_organizations, rowid, value, description) VALUES ('delete', old.id, old.value, old.description);
+END;
+
+CREATE TRIGGER search_organizations_update AFTER UPDATE OF value, description ON organizations BEGIN
+    INSERT INTO search_organizations (search_organizations, rowid, value, description) VALUES ('delete', old.id, old.value, old.description);
+    INSERT INTO search_organizations (rowid, value, description) VALUES (new.id, new.value, new.description);
+END;
@@ -0,0 +1,8 @@
+DROP TRIGGER search_asns_insert;
+DROP TRIGGER search_asns_delete;
+DROP TRIGGER search_asns_update;
+DROP TABLE search_asns;
+
+DROP TABLE asn_ipaddrs;
+DROP TABLE asn_netblocks;
+DROP TABLE asns;
@@ -0,0 +1,59 @@
+CREATE TABLE asns (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    as_org VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    CONSTRAINT asn_unique UNIQUE (value)
+);
+
+CREATE TABLE asn_netblocks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    asn_id INTEGER NOT NULL,
+    netblock_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(asn_id) REFERENCES asns(id) ON DELETE CASCADE,
+    FOREIGN KEY(netblock_id) REFERENCES netblocks(id) ON DELETE CASCADE,
+    CONSTRAINT asn_netblock_unique UNIQUE (asn_id, netblock_id)
+);
+
+CREATE TABLE asn_ipaddrs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    asn_id INTEGER NOT NULL,
+    ip_addr_id INTEGER NOT NULL,
+    first_seen DATETIME,
+    last_seen DATETIME,
+    FOREIGN KEY(asn_id) REFERENCES asns(id) ON DELETE CASCADE,
+    FOREIGN KEY(ip_addr_id) REFERENCES ipaddrs(id) ON DELETE CASCADE,
+    CONSTRAINT asn_ipaddr_unique UNIQUE (asn_id, ip_addr_id)
+);
+
+CREATE VIRTUAL TABLE search_asns USING fts5(value, as_org, content='asns', content_rowid='id');
+
+CREATE TRIGGER search_asns_insert AFTER INSERT ON asns BEGIN
+    INSERT INTO search_asns (rowid, value, as_org) VALUES (new.id, new.value, new.as_org);
+END;
+
+CREATE TRIGGER search_asns_delete AFTER DELETE ON asns BEGIN
+    INSERT INTO search_asns (search_asns, rowid, value, as_org) VALUES ('delete', old.id, old.value, old.as_org);
+END;
+
+CREATE TRIGGER search_asns_update AFTER UPDATE OF value, as_org ON asns BEGIN
+    INSERT INTO search_asns (search_asns, rowid, value, as_org) VALUES ('delete', old.id, old.value, old.as_org);
+    INSERT INTO search_asns (rowid, value, as_org) VALUES (new.id, new.value, new.as_org);
+END;
+
+-- create entities for the asn columns that are already in the workspace
+INSERT OR IGNORE INTO asns (value, as_org)
+    SELECT 'AS' || asn, as_org FROM netblocks WHERE asn IS NOT NULL;
+INSERT OR IGNORE INTO asns (value, as_org)
+    SELECT 'AS' || asn, as_org FROM ipaddrs WHERE asn IS NOT NULL;
+
+INSERT OR IGNORE INTO asn_netblocks (asn_id, netblock_id)
+    SELECT asns.id, netblocks.id FROM netblocks
+    JOIN asns ON asns.value = 'AS' || netblocks.asn;
+INSERT OR IGNORE INTO asn_ipaddrs (asn_id, ip_addr_id)
+    SELECT asns.id, ipaddrs.id FROM ipaddrs
+    JOIN asns ON asns.value = 'AS' || ipaddrs.asn;
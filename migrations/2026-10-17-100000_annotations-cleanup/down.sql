@@ -0,0 +1,32 @@
+DROP TRIGGER annotations_domains_delete;
+DROP TRIGGER annotations_subdomains_delete;
+DROP TRIGGER annotations_ipaddrs_delete;
+DROP TRIGGER annotations_subdomain_ipaddrs_delete;
+DROP TRIGGER annotations_urls_delete;
+DROP TRIGGER annotations_emails_delete;
+DROP TRIGGER annotations_phonenumbers_delete;
+DROP TRIGGER annotations_devices_delete;
+DROP TRIGGER annotations_networks_delete;
+DROP TRIGGER annotations_network_devices_delete;
+DROP TRIGGER annotations_accounts_delete;
+DROP TRIGGER annotations_breaches_delete;
+DROP TRIGGER annotations_breach_emails_delete;
+DROP TRIGGER annotations_images_delete;
+DROP TRIGGER annotations_ports_delete;
+DROP TRIGGER annotations_netblocks_delete;
+DROP TRIGGER annotations_cryptoaddrs_delete;
+DROP TRIGGER annotations_certificates_delete;
+DROP TRIGGER annotations_port_certificates_delete;
+DROP TRIGGER annotations_subdomain_certificates_delete;
+DROP TRIGGER annotations_dns_records_delete;
+DROP TRIGGER annotations_persons_delete;
+DROP TRIGGER annotations_organizations_delete;
+DROP TRIGGER annotations_person_emails_delete;
+DROP TRIGGER annotations_person_accounts_delete;
+DROP TRIGGER annotations_person_phonenumbers_delete;
+DROP TRIGGER annotations_person_images_delete;
+DROP TRIGGER annotations_person_organizations_delete;
+DROP TRIGGER annotations_organization_domains_delete;
+DROP TRIGGER annotations_asns_delete;
+DROP TRIGGER annotations_asn_netblocks_delete;
+DROP TRIGGER annotations_asn_ipaddrs_delete;
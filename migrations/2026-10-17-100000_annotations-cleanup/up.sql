@@ -0,0 +1,391 @@
+-- tags, notes and ttls refer to entities by family and key, remove them together with the entity
+-- this also covers rows that are deleted by ON DELETE CASCADE
+
+CREATE TRIGGER annotations_domains_delete AFTER DELETE ON domains BEGIN
+    DELETE FROM tags WHERE family = 'domains' AND key = old.id;
+    DELETE FROM notes WHERE family = 'domains' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'domains' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_subdomains_delete AFTER DELETE ON subdomains BEGIN
+    DELETE FROM tags WHERE family = 'subdomains' AND key = old.id;
+    DELETE FROM notes WHERE family = 'subdomains' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'subdomains' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_ipaddrs_delete AFTER DELETE ON ipaddrs BEGIN
+    DELETE FROM tags WHERE family = 'ipaddrs' AND key = old.id;
+    DELETE FROM notes WHERE family = 'ipaddrs' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'ipaddrs' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_subdomain_ipaddrs_delete AFTER DELETE ON subdomain_ipaddrs BEGIN
+    DELETE FROM tags WHERE family = 'subdomain_ipaddrs' AND key = old.id;
+    DELETE FROM notes WHERE family = 'subdomain_ipaddrs' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'subdomain_ipaddrs' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_urls_delete AFTER DELETE ON urls BEGIN
+    DELETE FROM tags WHERE family = 'urls' AND key = old.id;
+    DELETE FROM notes WHERE family = 'urls' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'urls' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_emails_delete AFTER DELETE ON emails BEGIN
+    DELETE FROM tags WHERE family = 'emails' AND key = old.id;
+    DELETE FROM notes WHERE family = 'emails' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'emails' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_phonenumbers_delete AFTER DELETE ON phonenumbers BEGIN
+    DELETE FROM tags WHERE family = 'phonenumbers' AND key = old.id;
+    DELETE FROM notes WHERE family = 'phonenumbers' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'phonenumbers' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_devices_delete AFTER DELETE ON devices BEGIN
+    DELETE FROM tags WHERE family = 'devices' AND key = old.id;
+    DELETE FROM notes WHERE family = 'devices' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'devices' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_networks_delete AFTER DELETE ON networks BEGIN
+    DELETE FROM tags WHERE family = 'networks' AND key = old.id;
+    DELETE FROM notes WHERE family = 'networks' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'networks' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_network_devices_delete AFTER DELETE ON network_devices BEGIN
+    DELETE FROM tags WHERE family = 'network_devices' AND key = old.id;
+    DELETE FROM notes WHERE family = 'network_devices' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'network_devices' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_accounts_delete AFTER DELETE ON accounts BEGIN
+    DELETE FROM tags WHERE family = 'accounts' AND key = old.id;
+    DELETE FROM notes WHERE family = 'accounts' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'accounts' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_breaches_delete AFTER DELETE ON breaches BEGIN
+    DELETE FROM tags WHERE family = 'breaches' AND key = old.id;
+    DELETE FROM notes WHERE family = 'breaches' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'breaches' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_breach_emails_delete AFTER DELETE ON breach_emails BEGIN
+    DELETE FROM tags WHERE family = 'breach_emails' AND key = old.id;
+    DELETE FROM notes WHERE family = 'breach_emails' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'breach_emails' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_images_delete AFTER DELETE ON images BEGIN
+    DELETE FROM tags WHERE family = 'images' AND key = old.id;
+    DELETE FROM notes WHERE family = 'images' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'images' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_ports_delete AFTER DELETE ON ports BEGIN
+    DELETE FROM tags WHERE family = 'ports' AND key = old.id;
+    DELETE FROM notes WHERE family = 'ports' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'ports' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_netblocks_delete AFTER DELETE ON netblocks BEGIN
+    DELETE FROM tags WHERE family = 'netblocks' AND key = old.id;
+    DELETE FROM notes WHERE family = 'netblocks' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'netblocks' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_cryptoaddrs_delete AFTER DELETE ON cryptoaddrs BEGIN
+    DELETE FROM tags WHERE family = 'cryptoaddrs' AND key = old.id;
+    DELETE FROM notes WHERE family = 'cryptoaddrs' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'cryptoaddrs' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_certificates_delete AFTER DELETE ON certificates BEGIN
+    DELETE FROM tags WHERE family = 'certificates' AND key = old.id;
+    DELETE FROM notes WHERE family = 'certificates' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'certificates' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_port_certificates_delete AFTER DELETE ON port_certificates BEGIN
+    DELETE FROM tags WHERE family = 'port_certificates' AND key = old.id;
+    DELETE FROM notes WHERE family = 'port_certificates' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'port_certificates' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_subdomain_certificates_delete AFTER DELETE ON subdomain_certificates BEGIN
+    DELETE FROM tags WHERE family = 'subdomain_certificates' AND key = old.id;
+    DELETE FROM notes WHERE family = 'subdomain_certificates' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'subdomain_certificates' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_dns_records_delete AFTER DELETE ON dns_records BEGIN
+    DELETE FROM tags WHERE family = 'dns_records' AND key = old.id;
+    DELETE FROM notes WHERE family = 'dns_records' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'dns_records' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_persons_delete AFTER DELETE ON persons BEGIN
+    DELETE FROM tags WHERE family = 'persons' AND key = old.id;
+    DELETE FROM notes WHERE family = 'persons' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'persons' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_organizations_delete AFTER DELETE ON organizations BEGIN
+    DELETE FROM tags WHERE family = 'organizations' AND key = old.id;
+    DELETE FROM notes WHERE family = 'organizations' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'organizations' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_person_emails_delete AFTER DELETE ON person_emails BEGIN
+    DELETE FROM tags WHERE family = 'person_emails' AND key = old.id;
+    DELETE FROM notes WHERE family = 'person_emails' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'person_emails' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_person_accounts_delete AFTER DELETE ON person_accounts BEGIN
+    DELETE FROM tags WHERE family = 'person_accounts' AND key = old.id;
+    DELETE FROM notes WHERE family = 'person_accounts' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'person_accounts' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_person_phonenumbers_delete AFTER DELETE ON person_phonenumbers BEGIN
+    DELETE FROM tags WHERE family = 'person_phonenumbers' AND key = old.id;
+    DELETE FROM notes WHERE family = 'person_phonenumbers' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'person_phonenumbers' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_person_images_delete AFTER DELETE ON person_images BEGIN
+    DELETE FROM tags WHERE family = 'person_images' AND key = old.id;
+    DELETE FROM notes WHERE family = 'person_images' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'person_images' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_person_organizations_delete AFTER DELETE ON person_organizations BEGIN
+    DELETE FROM tags WHERE family = 'person_organizations' AND key = old.id;
+    DELETE FROM notes WHERE family = 'person_organizations' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'person_organizations' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_organization_domains_delete AFTER DELETE ON organization_domains BEGIN
+    DELETE FROM tags WHERE family = 'organization_domains' AND key = old.id;
+    DELETE FROM notes WHERE family = 'organization_domains' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'organization_domains' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_asns_delete AFTER DELETE ON asns BEGIN
+    DELETE FROM tags WHERE family = 'asns' AND key = old.id;
+    DELETE FROM notes WHERE family = 'asns' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'asns' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_asn_netblocks_delete AFTER DELETE ON asn_netblocks BEGIN
+    DELETE FROM tags WHERE family = 'asn_netblocks' AND key = old.id;
+    DELETE FROM notes WHERE family = 'asn_netblocks' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'asn_netblocks' AND key = old.id;
+END;
+
+CREATE TRIGGER annotations_asn_ipaddrs_delete AFTER DELETE ON asn_ipaddrs BEGIN
+    DELETE FROM tags WHERE family = 'asn_ipaddrs' AND key = old.id;
+    DELETE FROM notes WHERE family = 'asn_ipaddrs' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'asn_ipaddrs' AND key = old.id;
+END;
+
+-- clean up everything that has been left behind so far
+DELETE FROM tags WHERE NOT EXISTS (
+    SELECT 1 FROM domains WHERE tags.family = 'domains' AND domains.id = tags.key
+    UNION ALL
+    SELECT 1 FROM subdomains WHERE tags.family = 'subdomains' AND subdomains.id = tags.key
+    UNION ALL
+    SELECT 1 FROM ipaddrs WHERE tags.family = 'ipaddrs' AND ipaddrs.id = tags.key
+    UNION ALL
+    SELECT 1 FROM subdomain_ipaddrs WHERE tags.family = 'subdomain_ipaddrs' AND subdomain_ipaddrs.id = tags.key
+    UNION ALL
+    SELECT 1 FROM urls WHERE tags.family = 'urls' AND urls.id = tags.key
+    UNION ALL
+    SELECT 1 FROM emails WHERE tags.family = 'emails' AND emails.id = tags.key
+    UNION ALL
+    SELECT 1 FROM phonenumbers WHERE tags.family = 'phonenumbers' AND phonenumbers.id = tags.key
+    UNION ALL
+    SELECT 1 FROM devices WHERE tags.family = 'devices' AND devices.id = tags.key
+    UNION ALL
+    SELECT 1 FROM networks WHERE tags.family = 'networks' AND networks.id = tags.key
+    UNION ALL
+    SELECT 1 FROM network_devices WHERE tags.family = 'network_devices' AND network_devices.id = tags.key
+    UNION ALL
+    SELECT 1 FROM accounts WHERE tags.family = 'accounts' AND accounts.id = tags.key
+    UNION ALL
+    SELECT 1 FROM breaches WHERE tags.family = 'breaches' AND breaches.id = tags.key
+    UNION ALL
+    SELECT 1 FROM breach_emails WHERE tags.family = 'breach_emails' AND breach_emails.id = tags.key
+    UNION ALL
+    SELECT 1 FROM images WHERE tags.family = 'images' AND images.id = tags.key
+    UNION ALL
+    SELECT 1 FROM ports WHERE tags.family = 'ports' AND ports.id = tags.key
+    UNION ALL
+    SELECT 1 FROM netblocks WHERE tags.family = 'netblocks' AND netblocks.id = tags.key
+    UNION ALL
+    SELECT 1 FROM cryptoaddrs WHERE tags.family = 'cryptoaddrs' AND cryptoaddrs.id = tags.key
+    UNION ALL
+    SELECT 1 FROM certificates WHERE tags.family = 'certificates' AND certificates.id = tags.key
+    UNION ALL
+    SELECT 1 FROM port_certificates WHERE tags.family = 'port_certificates' AND port_certificates.id = tags.key
+    UNION ALL
+    SELECT 1 FROM subdomain_certificates WHERE tags.family = 'subdomain_certificates' AND subdomain_certificates.id = tags.key
+    UNION ALL
+    SELECT 1 FROM dns_records WHERE tags.family = 'dns_records' AND dns_records.id = tags.key
+    UNION ALL
+    SELECT 1 FROM persons WHERE tags.family = 'persons' AND persons.id = tags.key
+    UNION ALL
+    SELECT 1 FROM organizations WHERE tags.family = 'organizations' AND organizations.id = tags.key
+    UNION ALL
+    SELECT 1 FROM person_emails WHERE tags.family = 'person_emails' AND person_emails.id = tags.key
+    UNION ALL
+    SELECT 1 FROM person_accounts WHERE tags.family = 'person_accounts' AND person_accounts.id = tags.key
+    UNION ALL
+    SELECT 1 FROM person_phonenumbers WHERE tags.family = 'person_phonenumbers' AND person_phonenumbers.id = tags.key
+    UNION ALL
+    SELECT 1 FROM person_images WHERE tags.family = 'person_images' AND person_images.id = tags.key
+    UNION ALL
+    SELECT 1 FROM person_organizations WHERE tags.family = 'person_organizations' AND person_organizations.id = tags.key
+    UNION ALL
+    SELECT 1 FROM organization_domains WHERE tags.family = 'organization_domains' AND organization_domains.id = tags.key
+    UNION ALL
+    SELECT 1 FROM asns WHERE tags.family = 'asns' AND asns.id = tags.key
+    UNION ALL
+    SELECT 1 FROM asn_netblocks WHERE tags.family = 'asn_netblocks' AND asn_netblocks.id = tags.key
+    UNION ALL
+    SELECT 1 FROM asn_ipaddrs WHERE tags.family = 'asn_ipaddrs' AND asn_ipaddrs.id = tags.key
+);
+DELETE FROM notes WHERE NOT EXISTS (
+    SELECT 1 FROM domains WHERE notes.family = 'domains' AND domains.id = notes.key
+    UNION ALL
+    SELECT 1 FROM subdomains WHERE notes.family = 'subdomains' AND subdomains.id = notes.key
+    UNION ALL
+    SELECT 1 FROM ipaddrs WHERE notes.family = 'ipaddrs' AND ipaddrs.id = notes.key
+    UNION ALL
+    SELECT 1 FROM subdomain_ipaddrs WHERE notes.family = 'subdomain_ipaddrs' AND subdomain_ipaddrs.id = notes.key
+    UNION ALL
+    SELECT 1 FROM urls WHERE notes.family = 'urls' AND urls.id = notes.key
+    UNION ALL
+    SELECT 1 FROM emails WHERE notes.family = 'emails' AND emails.id = notes.key
+    UNION ALL
+    SELECT 1 FROM phonenumbers WHERE notes.family = 'phonenumbers' AND phonenumbers.id = notes.key
+    UNION ALL
+    SELECT 1 FROM devices WHERE notes.family = 'devices' AND devices.id = notes.key
+    UNION ALL
+    SELECT 1 FROM networks WHERE notes.family = 'networks' AND networks.id = notes.key
+    UNION ALL
+    SELECT 1 FROM network_devices WHERE notes.family = 'network_devices' AND network_devices.id = notes.key
+    UNION ALL
+    SELECT 1 FROM accounts WHERE notes.family = 'accounts' AND accounts.id = notes.key
+    UNION ALL
+    SELECT 1 FROM breaches WHERE notes.family = 'breaches' AND breaches.id = notes.key
+    UNION ALL
+    SELECT 1 FROM breach_emails WHERE notes.family = 'breach_emails' AND breach_emails.id = notes.key
+    UNION ALL
+    SELECT 1 FROM images WHERE notes.family = 'images' AND images.id = notes.key
+    UNION ALL
+    SELECT 1 FROM ports WHERE notes.family = 'ports' AND ports.id = notes.key
+    UNION ALL
+    SELECT 1 FROM netblocks WHERE notes.family = 'netblocks' AND netblocks.id = notes.key
+    UNION ALL
+    SELECT 1 FROM cryptoaddrs WHERE notes.family = 'cryptoaddrs' AND cryptoaddrs.id = notes.key
+    UNION ALL
+    SELECT 1 FROM certificates WHERE notes.family = 'certificates' AND certificates.id = notes.key
+    UNION ALL
+    SELECT 1 FROM port_certificates WHERE notes.family = 'port_certificates' AND port_certificates.id = notes.key
+    UNION ALL
+    SELECT 1 FROM subdomain_certificates WHERE notes.family = 'subdomain_certificates' AND subdomain_certificates.id = notes.key
+    UNION ALL
+    SELECT 1 FROM dns_records WHERE notes.family = 'dns_records' AND dns_records.id = notes.key
+    UNION ALL
+    SELECT 1 FROM persons WHERE notes.family = 'persons' AND persons.id = notes.key
+    UNION ALL
+    SELECT 1 FROM organizations WHERE notes.family = 'organizations' AND organizations.id = notes.key
+    UNION ALL
+    SELECT 1 FROM person_emails WHERE notes.family = 'person_emails' AND person_emails.id = notes.key
+    UNION ALL
+    SELECT 1 FROM person_accounts WHERE notes.family = 'person_accounts' AND person_accounts.id = notes.key
+    UNION ALL
+    SELECT 1 FROM person_phonenumbers WHERE notes.family = 'person_phonenumbers' AND person_phonenumbers.id = notes.key
+    UNION ALL
+    SELECT 1 FROM person_images WHERE notes.family = 'person_images' AND person_images.id = notes.key
+    UNION ALL
+    SELECT 1 FROM person_organizations WHERE notes.family = 'person_organizations' AND person_organizations.id = notes.key
+    UNION ALL
+    SELECT 1 FROM organization_domains WHERE notes.family = 'organization_domains' AND organization_domains.id = notes.key
+    UNION ALL
+    SELECT 1 FROM asns WHERE notes.family = 'asns' AND asns.id = notes.key
+    UNION ALL
+    SELECT 1 FROM asn_netblocks WHERE notes.family = 'asn_netblocks' AND asn_netblocks.id = notes.key
+    UNION ALL
+    SELECT 1 FROM asn_ipaddrs WHERE notes.family = 'asn_ipaddrs' AND asn_ipaddrs.id = notes.key
+);
+DELETE FROM ttls WHERE NOT EXISTS (
+    SELECT 1 FROM domains WHERE ttls.family = 'domains' AND domains.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM subdomains WHERE ttls.family = 'subdomains' AND subdomains.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM ipaddrs WHERE ttls.family = 'ipaddrs' AND ipaddrs.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM subdomain_ipaddrs WHERE ttls.family = 'subdomain_ipaddrs' AND subdomain_ipaddrs.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM urls WHERE ttls.family = 'urls' AND urls.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM emails WHERE ttls.family = 'emails' AND emails.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM phonenumbers WHERE ttls.family = 'phonenumbers' AND phonenumbers.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM devices WHERE ttls.family = 'devices' AND devices.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM networks WHERE ttls.family = 'networks' AND networks.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM network_devices WHERE ttls.family = 'network_devices' AND network_devices.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM accounts WHERE ttls.family = 'accounts' AND accounts.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM breaches WHERE ttls.family = 'breaches' AND breaches.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM breach_emails WHERE ttls.family = 'breach_emails' AND breach_emails.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM images WHERE ttls.family = 'images' AND images.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM ports WHERE ttls.family = 'ports' AND ports.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM netblocks WHERE ttls.family = 'netblocks' AND netblocks.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM cryptoaddrs WHERE ttls.family = 'cryptoaddrs' AND cryptoaddrs.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM certificates WHERE ttls.family = 'certificates' AND certificates.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM port_certificates WHERE ttls.family = 'port_certificates' AND port_certificates.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM subdomain_certificates WHERE ttls.family = 'subdomain_certificates' AND subdomain_certificates.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM dns_records WHERE ttls.family = 'dns_records' AND dns_records.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM persons WHERE ttls.family = 'persons' AND persons.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM organizations WHERE ttls.family = 'organizations' AND organizations.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM person_emails WHERE ttls.family = 'person_emails' AND person_emails.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM person_accounts WHERE ttls.family = 'person_accounts' AND person_accounts.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM person_phonenumbers WHERE ttls.family = 'person_phonenumbers' AND person_phonenumbers.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM person_images WHERE ttls.family = 'person_images' AND person_images.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM person_organizations WHERE ttls.family = 'person_organizations' AND person_organizations.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM organization_domains WHERE ttls.family = 'organization_domains' AND organization_domains.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM asns WHERE ttls.family = 'asns' AND asns.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM asn_netblocks WHERE ttls.family = 'asn_netblocks' AND asn_netblocks.id = ttls.key
+    UNION ALL
+    SELECT 1 FROM asn_ipaddrs WHERE ttls.family = 'asn_ipaddrs' AND asn_ipaddrs.id = ttls.key
+);
@@ -0,0 +1,222 @@
+PRAGMA foreign_keys=off;
+
+ALTER TABLE domains RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE domains RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE subdomains RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE subdomains RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE emails RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE emails RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE ipaddrs RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE ipaddrs RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE subdomain_ipaddrs RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE subdomain_ipaddrs RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE urls RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE urls RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE phonenumbers RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE phonenumbers RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE networks RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE networks RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE breaches RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE breaches RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE breach_emails RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE breach_emails RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE images RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE images RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE netblocks RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE netblocks RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE ports RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE ports RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE certificates RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE certificates RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE port_certificates RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE port_certificates RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE subdomain_certificates RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE subdomain_certificates RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE dns_records RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE dns_records RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE persons RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE persons RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE organizations RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE organizations RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE person_emails RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE person_emails RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE person_accounts RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE person_accounts RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE person_phonenumbers RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE person_phonenumbers RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE person_images RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE person_images RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE person_organizations RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE person_organizations RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE organization_domains RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE organization_domains RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE asns RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE asns RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE asn_netblocks RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE asn_netblocks RENAME COLUMN last_observed TO last_seen;
+ALTER TABLE asn_ipaddrs RENAME COLUMN first_observed TO first_seen;
+ALTER TABLE asn_ipaddrs RENAME COLUMN last_observed TO last_seen;
+
+-- devices
+
+ALTER TABLE devices RENAME TO _devices_old;
+
+CREATE TABLE devices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    name VARCHAR,
+    hostname VARCHAR,
+    vendor VARCHAR,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    last_seen DATETIME,
+    first_seen DATETIME,
+    CONSTRAINT device_unique UNIQUE (value)
+);
+
+INSERT INTO devices (id, value, name, hostname, vendor, unscoped, last_seen, first_seen)
+  SELECT id, value, name, hostname, vendor, unscoped, last_seen, first_observed
+  FROM _devices_old;
+
+DROP TABLE _devices_old;
+
+CREATE TRIGGER search_devices_insert AFTER INSERT ON devices BEGIN
+    INSERT INTO search_devices (rowid, value, name, hostname, vendor) VALUES (new.id, new.value, new.name, new.hostname, new.vendor);
+END;
+
+CREATE TRIGGER search_devices_delete AFTER DELETE ON devices BEGIN
+    INSERT INTO search_devices (search_devices, rowid, value, name, hostname, vendor) VALUES ('delete', old.id, old.value, old.name, old.hostname, old.vendor);
+END;
+
+CREATE TRIGGER search_devices_update AFTER UPDATE OF value, name, hostname, vendor ON devices BEGIN
+    INSERT INTO search_devices (search_devices, rowid, value, name, hostname, vendor) VALUES ('delete', old.id, old.value, old.name, old.hostname, old.vendor);
+    INSERT INTO search_devices (rowid, value, name, hostname, vendor) VALUES (new.id, new.value, new.name, new.hostname, new.vendor);
+END;
+
+CREATE TRIGGER annotations_devices_delete AFTER DELETE ON devices BEGIN
+    DELETE FROM tags WHERE family = 'devices' AND key = old.id;
+    DELETE FROM notes WHERE family = 'devices' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'devices' AND key = old.id;
+END;
+
+
+-- network_devices
+
+ALTER TABLE network_devices RENAME TO _network_devices_old;
+
+CREATE TABLE network_devices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    network_id INTEGER NOT NULL,
+    device_id INTEGER NOT NULL,
+    ipaddr VARCHAR,
+    last_seen DATETIME,
+    first_seen DATETIME,
+    FOREIGN KEY(network_id) REFERENCES networks(id) ON DELETE CASCADE,
+    FOREIGN KEY(device_id) REFERENCES devices(id) ON DELETE CASCADE,
+    CONSTRAINT network_device_unique UNIQUE (network_id, device_id)
+);
+
+INSERT INTO network_devices (id, network_id, device_id, ipaddr, last_seen, first_seen)
+  SELECT id, network_id, device_id, ipaddr, last_seen, first_observed
+  FROM _network_devices_old;
+
+DROP TABLE _network_devices_old;
+
+CREATE TRIGGER annotations_network_devices_delete AFTER DELETE ON network_devices BEGIN
+    DELETE FROM tags WHERE family = 'network_devices' AND key = old.id;
+    DELETE FROM notes WHERE family = 'network_devices' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'network_devices' AND key = old.id;
+END;
+
+
+-- accounts
+
+ALTER TABLE accounts RENAME TO _accounts_old;
+
+CREATE TABLE accounts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    service VARCHAR NOT NULL,
+    username VARCHAR NOT NULL,
+    displayname VARCHAR,
+    email VARCHAR,
+    url VARCHAR,
+    last_seen DATETIME,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    phonenumber VARCHAR,
+    profile_pic VARCHAR,
+    birthday VARCHAR,
+    first_seen DATETIME,
+    CONSTRAINT account_unique UNIQUE (value)
+);
+
+INSERT INTO accounts (id, value, service, username, displayname, email, url, last_seen, unscoped, phonenumber, profile_pic, birthday, first_seen)
+  SELECT id, value, service, username, displayname, email, url, last_seen, unscoped, phonenumber, profile_pic, birthday, first_observed
+  FROM _accounts_old;
+
+DROP TABLE _accounts_old;
+
+CREATE TRIGGER search_accounts_insert AFTER INSERT ON accounts BEGIN
+    INSERT INTO search_accounts (rowid, value, service, username, displayname, email, url, phonenumber) VALUES (new.id, new.value, new.service, new.username, new.displayname, new.email, new.url, new.phonenumber);
+END;
+
+CREATE TRIGGER search_accounts_delete AFTER DELETE ON accounts BEGIN
+    INSERT INTO search_accounts (search_accounts, rowid, value, service, username, displayname, email, url, phonenumber) VALUES ('delete', old.id, old.value, old.service, old.username, old.displayname, old.email, old.url, old.phonenumber);
+END;
+
+CREATE TRIGGER search_accounts_update AFTER UPDATE OF value, service, username, displayname, email, url, phonenumber ON accounts BEGIN
+    INSERT INTO search_accounts (search_accounts, rowid, value, service, username, displayname, email, url, phonenumber) VALUES ('delete', old.id, old.value, old.service, old.username, old.displayname, old.email, old.url, old.phonenumber);
+    INSERT INTO search_accounts (rowid, value, service, username, displayname, email, url, phonenumber) VALUES (new.id, new.value, new.service, new.username, new.displayname, new.email, new.url, new.phonenumber);
+END;
+
+CREATE TRIGGER annotations_accounts_delete AFTER DELETE ON accounts BEGIN
+    DELETE FROM tags WHERE family = 'accounts' AND key = old.id;
+    DELETE FROM notes WHERE family = 'accounts' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'accounts' AND key = old.id;
+END;
+
+
+-- cryptoaddrs
+
+ALTER TABLE cryptoaddrs RENAME TO _cryptoaddrs_old;
+
+CREATE TABLE cryptoaddrs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    value VARCHAR NOT NULL,
+    currency VARCHAR,
+    denominator INTEGER,
+    balance BIGINT,
+    received BIGINT,
+    first_seen DATETIME,
+    last_withdrawal DATETIME,
+    unscoped BOOLEAN DEFAULT 0 NOT NULL,
+    description VARCHAR,
+    last_seen DATETIME,
+    CONSTRAINT netblock_unique UNIQUE (value)
+);
+
+INSERT INTO cryptoaddrs (id, value, currency, denominator, balance, received, first_seen, last_withdrawal, unscoped, description, last_seen)
+  SELECT id, value, currency, denominator, balance, received, first_seen, last_withdrawal, unscoped, description, last_observed
+  FROM _cryptoaddrs_old;
+
+DROP TABLE _cryptoaddrs_old;
+
+CREATE TRIGGER search_cryptoaddrs_insert AFTER INSERT ON cryptoaddrs BEGIN
+    INSERT INTO search_cryptoaddrs (rowid, value, currency, description) VALUES (new.id, new.value, new.currency, new.description);
+END;
+
+CREATE TRIGGER search_cryptoaddrs_delete AFTER DELETE ON cryptoaddrs BEGIN
+    INSERT INTO search_cryptoaddrs (search_cryptoaddrs, rowid, value, currency, description) VALUES ('delete', old.id, old.value, old.currency, old.description);
+END;
+
+CREATE TRIGGER search_cryptoaddrs_update AFTER UPDATE OF value, currency, description ON cryptoaddrs BEGIN
+    INSERT INTO search_cryptoaddrs (search_cryptoaddrs, rowid, value, currency, description) VALUES ('delete', old.id, old.value, old.currency, old.description);
+    INSERT INTO search_cryptoaddrs (rowid, value, currency, description) VALUES (new.id, new.value, new.currency, new.description);
+END;
+
+CREATE TRIGGER annotations_cryptoaddrs_delete AFTER DELETE ON cryptoaddrs BEGIN
+    DELETE FROM tags WHERE family = 'cryptoaddrs' AND key = old.id;
+    DELETE FROM notes WHERE family = 'cryptoaddrs' AND key = old.id;
+    DELETE FROM ttls WHERE family = 'cryptoaddrs' AND key = old.id;
+END;
+
+PRAGMA foreign_keys=on;
@@ -0,0 +1,68 @@
+ALTER TABLE domains RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE domains RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE subdomains RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE subdomains RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE emails RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE emails RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE ipaddrs RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE ipaddrs RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE subdomain_ipaddrs RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE subdomain_ipaddrs RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE urls RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE urls RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE phonenumbers RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE phonenumbers RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE networks RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE networks RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE breaches RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE breaches RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE breach_emails RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE breach_emails RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE images RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE images RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE netblocks RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE netblocks RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE ports RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE ports RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE certificates RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE certificates RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE port_certificates RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE port_certificates RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE subdomain_certificates RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE subdomain_certificates RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE dns_records RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE dns_records RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE persons RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE persons RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE organizations RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE organizations RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE person_emails RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE person_emails RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE person_accounts RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE person_accounts RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE person_phonenumbers RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE person_phonenumbers RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE person_images RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE person_images RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE person_organizations RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE person_organizations RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE organization_domains RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE organization_domains RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE asns RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE asns RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE asn_netblocks RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE asn_netblocks RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE asn_ipaddrs RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE asn_ipaddrs RENAME COLUMN last_seen TO last_observed;
+
+-- devices, network_devices and accounts have a last_seen reported by modules
+ALTER TABLE devices RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE devices ADD COLUMN last_observed DATETIME;
+ALTER TABLE network_devices RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE network_devices ADD COLUMN last_observed DATETIME;
+ALTER TABLE accounts RENAME COLUMN first_seen TO first_observed;
+ALTER TABLE accounts ADD COLUMN last_observed DATETIME;
+
+-- cryptoaddrs have a first_seen reported by modules
+ALTER TABLE cryptoaddrs RENAME COLUMN last_seen TO last_observed;
+ALTER TABLE cryptoaddrs ADD COLUMN first_observed DATETIME;
@@ -55,6 +55,11 @@ pub enum Source {
     Ports,
     Netblocks,
     CryptoAddrs(Option<String>),
+    Certificates,
+    DnsRecords,
+    Persons,
+    Organizations,
+    Asns,
     KeyRing(String),
     Notifications,
 }
@@ -76,6 +81,11 @@ impl Source {
             Source::Ports => "ports",
             Source::Netblocks => "netblocks",
             Source::CryptoAddrs(_) => "cryptoaddrs",
+            Source::Certificates => "certificates",
+            Source::DnsRecords => "dns-records",
+            Source::Persons => "persons",
+            Source::Organizations => "organizations",
+            Source::Asns => "asns",
             Source::Notifications => "notifications",
             Source::KeyRing(_) => "keyring",
         }
@@ -108,6 +118,11 @@ impl FromStr for Source {
             ("ports", None) => Ok(Source::Ports),
             ("netblocks", None) => Ok(Source::Netblocks),
             ("cryptoaddrs", param) => Ok(Source::CryptoAddrs(param.map(String::from))),
+            ("certificates", None) => Ok(Source::Certificates),
+            ("dns-records", None) => Ok(Source::DnsRecords),
+            ("persons", None) => Ok(Source::Persons),
+            ("organizations", None) => Ok(Source::Organizations),
+            ("asns", None) => Ok(Source::Asns),
             ("notifications", None) => Ok(Source::Notifications),
             ("keyring", Some(param)) => Ok(Source::KeyRing(param.to_string())),
             (x, Some(param)) => bail!("Unknown Source: {:?} ({:?})", x, param),
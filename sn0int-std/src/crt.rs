@@ -1,5 +1,8 @@
 use crate::errors::*;
+use chrono::NaiveDateTime;
+use data_encoding::HEXLOWER;
 use serde::{Deserialize, Serialize};
+use sodiumoxide::crypto::hash::sha256;
 use std::collections::HashSet;
 use std::net::IpAddr;
 use x509_parser::certificate::X509Certificate;
@@ -7,6 +10,36 @@ use x509_parser::extensions::{GeneralName, ParsedExtension};
 use x509_parser::prelude::*;
 use x509_parser::x509::X509Version;
 
+fn pem_to_der(crt: &str) -> Result<Vec<u8>> {
+    match x509_parser::pem::parse_x509_pem(crt.as_bytes()) {
+        Ok((remaining, pem)) => {
+            if !remaining.is_empty() {
+                bail!("input cert has trailing garbage");
+            }
+            if pem.label != "CERTIFICATE" {
+                bail!("input is not a certificate");
+            }
+            Ok(pem.contents)
+        }
+        Err(_) => bail!("Failed to parse pem"),
+    }
+}
+
+fn parse_der(crt: &[u8]) -> Result<X509Certificate<'_>> {
+    match X509Certificate::from_der(crt) {
+        Ok((remaining, der)) => {
+            if !remaining.is_empty() {
+                bail!("input cert has trailing garbage");
+            }
+            if der.tbs_certificate.version != X509Version::V3 {
+                bail!("unexpected certificate version");
+            }
+            Ok(der)
+        }
+        Err(_) => bail!("Failed to parse der"),
+    }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Certificate {
     pub valid_names: Vec<String>,
@@ -16,35 +49,16 @@ pub struct Certificate {
 
 impl Certificate {
     pub fn parse_pem(crt: &str) -> Result<Certificate> {
-        let pem = match x509_parser::pem::parse_x509_pem(crt.as_bytes()) {
-            Ok((remaining, pem)) => {
-                if !remaining.is_empty() {
-                    bail!("input cert has trailing garbage");
-                }
-                if pem.label != "CERTIFICATE" {
-                    bail!("input is not a certificate");
-                }
-                pem
-            }
-            Err(_) => bail!("Failed to parse pem"),
-        };
-        Certificate::from_bytes(&pem.contents)
+        let der = pem_to_der(crt)?;
+        Certificate::from_bytes(&der)
     }
 
     pub fn from_bytes(crt: &[u8]) -> Result<Certificate> {
-        let crt = match X509Certificate::from_der(crt) {
-            Ok((remaining, der)) => {
-                if !remaining.is_empty() {
-                    bail!("input cert has trailing garbage");
-                }
-                if der.tbs_certificate.version != X509Version::V3 {
-                    bail!("unexpected certificate version");
-                }
-                der
-            }
-            Err(_) => bail!("Failed to parse der"),
-        };
+        let crt = parse_der(crt)?;
+        Ok(Certificate::from_x509(&crt))
+    }
 
+    fn from_x509(crt: &X509Certificate) -> Certificate {
         let mut valid_names = HashSet::new();
         let mut valid_emails = HashSet::new();
         let mut valid_ipaddrs = HashSet::new();
@@ -92,22 +106,75 @@ impl Certificate {
         let valid_names = valid_names.into_iter().collect();
         let valid_emails = valid_emails.into_iter().collect();
         let valid_ipaddrs = valid_ipaddrs.into_iter().collect();
-        Ok(Certificate {
+        Certificate {
             valid_names,
             valid_emails,
             valid_ipaddrs,
+        }
+    }
+}
+
+
+/// The details of a certificate that are stored in the database, this also
+/// contains the names the certificate is valid for
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CertificateInfo {
+    /// The hex encoded sha256 of the der encoded certificate
+    pub fingerprint: String,
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub key_type: String,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+    #[serde(flatten)]
+    pub names: Certificate,
+}
+
+impl CertificateInfo {
+    pub fn parse_pem(crt: &str) -> Result<CertificateInfo> {
+        let der = pem_to_der(crt)?;
+        CertificateInfo::from_bytes(&der)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<CertificateInfo> {
+        let crt = parse_der(bytes)?;
+
+        let fingerprint = HEXLOWER.encode(&sha256::hash(bytes).0);
+        let validity = crt.validity();
+
+        Ok(CertificateInfo {
+            fingerprint,
+            subject: crt.subject().to_string(),
+            issuer: crt.issuer().to_string(),
+            serial: crt.tbs_certificate.raw_serial_as_string(),
+            key_type: key_type(&crt),
+            not_before: NaiveDateTime::from_timestamp_opt(validity.not_before.timestamp(), 0),
+            not_after: NaiveDateTime::from_timestamp_opt(validity.not_after.timestamp(), 0),
+            names: Certificate::from_x509(&crt),
         })
     }
 }
 
+/// The name of the public key algorithm, unknown algorithms are returned as oid
+fn key_type(crt: &X509Certificate) -> String {
+    let oid = crt.tbs_certificate.subject_pki.algorithm.algorithm.to_id_string();
+    match oid.as_str() {
+        "1.2.840.113549.1.1.1" => "rsa",
+        "1.2.840.10045.2.1" => "ec",
+        "1.2.840.10040.4.1" => "dsa",
+        "1.3.101.112" => "ed25519",
+        "1.3.101.113" => "ed448",
+        _ => return oid,
+    }
+    .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_parse_pem_github() {
-        let mut x = Certificate::parse_pem(
-            r#"-----BEGIN CERTIFICATE-----
+    const GITHUB: &str = r#"-----BEGIN CERTIFICATE-----
 MIIHQjCCBiqgAwIBAgIQCgYwQn9bvO1pVzllk7ZFHzANBgkqhkiG9w0BAQsFADB1
 MQswCQYDVQQGEwJVUzEVMBMGA1UEChMMRGlnaUNlcnQgSW5jMRkwFwYDVQQLExB3
 d3cuZGlnaWNlcnQuY29tMTQwMgYDVQQDEytEaWdpQ2VydCBTSEEyIEV4dGVuZGVk
@@ -148,9 +215,11 @@ Kqg6LK0Hcq4K0sZnxE8HFxiZ92WpV2AVWjRMEc/2z2shNoDvxvFUYyY1Oe67xINk
 myQKc+ygSBZzyLnXSFVWmHr3u5dcaaQGGAR42v6Ydr4iL38Hd4dOiBma+FXsXBIq
 WUjbST4VXmdaol7uzFMojA4zkxQDZAvF5XgJlAFadfySna/teik=
 -----END CERTIFICATE-----
-"#,
-        )
-        .expect("Failed to parse cert");
+"#;
+
+    #[test]
+    fn test_parse_pem_github() {
+        let mut x = Certificate::parse_pem(GITHUB).expect("Failed to parse cert");
         x.valid_names.sort();
         assert_eq!(
             x,
@@ -162,6 +231,34 @@ WUjbST4VXmdaol7uzFMojA4zkxQDZAvF5XgJlAFadfySna/teik=
         );
     }
 
+    #[test]
+    fn test_certificate_info_github() {
+        let mut x = CertificateInfo::parse_pem(GITHUB).expect("Failed to parse cert");
+        assert_eq!(
+            x.fingerprint,
+            "3111500c4a66012cdae333ec3fca1c9dde45c954440e7ee413716bff3663c074"
+        );
+        assert!(x.subject.contains("CN=github.com"));
+        assert!(x
+            .issuer
+            .contains("CN=DigiCert SHA2 Extended Validation Server CA"));
+        assert_eq!(x.serial, "0a:06:30:42:7f:5b:bc:ed:69:57:39:65:93:b6:45:1f");
+        assert_eq!(x.key_type, "rsa");
+        assert_eq!(
+            x.not_before,
+            Some("2018-05-08T00:00:00".parse().unwrap())
+        );
+        assert_eq!(
+            x.not_after,
+            Some("2020-06-03T12:00:00".parse().unwrap())
+        );
+        x.names.valid_names.sort();
+        assert_eq!(
+            x.names.valid_names,
+            vec!["github.com".to_string(), "www.github.com".to_string()]
+        );
+    }
+
     #[test]
     fn test_parse_pem_1_1_1_1() {
         let mut x = Certificate::parse_pem(
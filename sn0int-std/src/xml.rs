@@ -72,7 +72,7 @@ fn append_text(stack: &mut [XmlElement], text: String) {
     }
 }
 
-fn decode_raw(x: &str) -> Result<XmlDocument> {
+pub fn decode_raw(x: &str) -> Result<XmlDocument> {
     let config = ParserConfig::new()
         .trim_whitespace(true)
         .whitespace_to_characters(true)
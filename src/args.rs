@@ -89,9 +89,24 @@ pub enum SubCommand {
     /// Export a workspace for external processing
     #[command(name="export")]
     Export(cmd::export_cmd::Args),
+    /// Import a workspace from an export
+    #[command(name="import")]
+    Import(cmd::import_cmd::Args),
     /// Show statistics about your current workspace
     #[command(name="stats")]
     Stats(cmd::stats_cmd::Args),
+    /// Manage tags of entities
+    #[command(name="tag")]
+    Tag(cmd::tag_cmd::Args),
+    /// Manage notes of entities
+    #[command(name="note")]
+    Note(cmd::note_cmd::Args),
+    /// Show the recorded changes of an entity
+    #[command(name="history")]
+    History(cmd::history_cmd::Args),
+    /// List or revert module runs
+    #[command(name="runs")]
+    Runs(cmd::runs_cmd::Args),
     /// Run a lua repl
     #[command(name="repl")]
     Repl,
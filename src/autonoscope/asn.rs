@@ -0,0 +1,142 @@
+use crate::errors::*;
+use crate::autonoscope::{Autonoscope, ToRule, AutoRule, RulePrecision};
+use crate::models::*;
+use std::convert::TryFrom;
+
+
+#[derive(Debug, PartialEq)]
+pub struct AsnRule {
+    asn: u32,
+}
+
+impl ToString for AsnRule {
+    fn to_string(&self) -> String {
+        format!("AS{}", self.asn)
+    }
+}
+
+impl TryFrom<&str> for AsnRule {
+    type Error = Error;
+
+    fn try_from(x: &str) -> Result<AsnRule> {
+        let asn = parse_asn(x)?;
+        Ok(AsnRule {
+            asn,
+        })
+    }
+}
+
+impl TryFrom<Autonoscope> for AsnRule {
+    type Error = Error;
+
+    fn try_from(x: Autonoscope) -> Result<AsnRule> {
+        AsnRule::try_from(x.value.as_str())
+    }
+}
+
+impl AutoRule<Asn> for AsnRule {
+    fn matches(&self, asn: &Asn) -> Result<bool> {
+        self.matches(asn.value.as_str())
+    }
+}
+
+impl AutoRule<NewAsn> for AsnRule {
+    fn matches(&self, asn: &NewAsn) -> Result<bool> {
+        self.matches(asn.value.as_str())
+    }
+}
+
+impl AutoRule<IpAddr> for AsnRule {
+    fn matches(&self, ipaddr: &IpAddr) -> Result<bool> {
+        self.matches(&ipaddr.asn)
+    }
+}
+
+impl AutoRule<NewIpAddr> for AsnRule {
+    fn matches(&self, ipaddr: &NewIpAddr) -> Result<bool> {
+        self.matches(&ipaddr.asn)
+    }
+}
+
+impl AutoRule<Netblock> for AsnRule {
+    fn matches(&self, netblock: &Netblock) -> Result<bool> {
+        self.matches(&netblock.asn)
+    }
+}
+
+impl AutoRule<NewNetblock> for AsnRule {
+    fn matches(&self, netblock: &NewNetblock) -> Result<bool> {
+        self.matches(&netblock.asn)
+    }
+}
+
+impl AutoRule<Option<i32>> for AsnRule {
+    fn matches(&self, asn: &Option<i32>) -> Result<bool> {
+        Ok(asn.and_then(|x| u32::try_from(x).ok()) == Some(self.asn))
+    }
+}
+
+impl AutoRule<str> for AsnRule {
+    fn matches(&self, asn: &str) -> Result<bool> {
+        Ok(parse_asn(asn)? == self.asn)
+    }
+}
+
+impl RulePrecision for AsnRule {
+    fn precision(&self) -> usize {
+        // every rule matches exactly one autonomous system
+        0
+    }
+}
+
+impl ToRule for AsnRule {
+    fn to_rule(&self) -> (&'static str, String) {
+        ("asn", self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_asn_rule_match() {
+        let rule = AsnRule::try_from("AS13335").unwrap();
+        assert!(rule.matches("AS13335").unwrap());
+        assert!(rule.matches("13335").unwrap());
+        assert_eq!(rule.to_string(), "AS13335");
+    }
+
+    #[test]
+    fn test_asn_rule_mismatch() {
+        let rule = AsnRule::try_from("13335").unwrap();
+        assert!(!rule.matches("AS15169").unwrap());
+    }
+
+    #[test]
+    fn test_asn_rule_invalid() {
+        assert!(AsnRule::try_from("cloudflare").is_err());
+    }
+
+    #[test]
+    fn test_asn_rule_netblock() {
+        let rule = AsnRule::try_from("AS64496").unwrap();
+        assert!(rule.matches(&NewNetblock {
+            family: String::from("4"),
+            value: String::from("192.0.2.0/24"),
+            asn: Some(64496),
+            as_org: None,
+            description: None,
+            unscoped: false,
+        }).unwrap());
+        assert!(!rule.matches(&NewNetblock {
+            family: String::from("4"),
+            value: String::from("192.0.2.0/24"),
+            asn: None,
+            as_org: None,
+            description: None,
+            unscoped: false,
+        }).unwrap());
+    }
+}
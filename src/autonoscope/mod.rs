@@ -4,6 +4,7 @@ use diesel::prelude::*;
 use crate::schema::*;
 use crate::models::*;
 use lazy_static::lazy_static;
+use serde::{Serialize, Deserialize};
 use std::cmp::Ordering;
 use std::convert::{TryInto, TryFrom};
 use std::str::FromStr;
@@ -14,8 +15,10 @@ mod ip;
 pub use self::ip::IpRule;
 mod url;
 pub use self::url::UrlRule;
+mod asn;
+pub use self::asn::AsnRule;
 
-#[derive(Identifiable, Queryable, PartialEq, Debug)]
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="autonoscope"]
 pub struct Autonoscope {
     pub id: i32,
@@ -24,6 +27,14 @@ pub struct Autonoscope {
     pub scoped: bool,
 }
 
+impl Autonoscope {
+    pub fn list(db: &DatabaseSock) -> Result<Vec<Self>> {
+        use crate::schema::autonoscope::dsl::*;
+        autonoscope.load::<Self>(db)
+            .map_err(Error::from)
+    }
+}
+
 #[derive(Insertable, PartialEq, Debug)]
 #[table_name="autonoscope"]
 pub struct NewAutonoscope {
@@ -37,6 +48,7 @@ pub struct RuleSet {
     domains: Vec<Rule<DomainRule>>,
     ips: Vec<Rule<IpRule>>,
     urls: Vec<Rule<UrlRule>>,
+    asns: Vec<Rule<AsnRule>>,
 }
 
 #[inline(always)]
@@ -59,6 +71,10 @@ impl RuleSet {
         &self.urls
     }
 
+    pub fn asns(&self) -> &[Rule<AsnRule>] {
+        &self.asns
+    }
+
     pub fn load(db: &DatabaseSock) -> Result<Self> {
         use crate::schema::autonoscope::dsl::*;
         let rules = autonoscope.load::<Autonoscope>(db)?;
@@ -71,6 +87,7 @@ impl RuleSet {
                 RuleType::Domain => set.domains.push(Rule::new(rule.try_into()?, is_scoped)),
                 RuleType::Ip => set.ips.push(Rule::new(rule.try_into()?, is_scoped)),
                 RuleType::Url => set.urls.push(Rule::new(rule.try_into()?, is_scoped)),
+                RuleType::Asn => set.asns.push(Rule::new(rule.try_into()?, is_scoped)),
             }
         }
         set.sort_rules();
@@ -82,6 +99,7 @@ impl RuleSet {
         self.domains.sort_by(sort_precision_desc);
         self.ips.sort_by(sort_precision_desc);
         self.urls.sort_by(sort_precision_desc);
+        self.asns.sort_by(sort_precision_desc);
     }
 
     pub fn add_rule(&mut self, db: &DatabaseSock, object: &RuleType, value: &str, scoped: bool) -> Result<()> {
@@ -106,6 +124,12 @@ impl RuleSet {
                 self.db_add(db, &rule)?;
                 self.urls.push(rule);
             },
+            RuleType::Asn => {
+                let rule = AsnRule::try_from(value)?;
+                let rule = Rule::new(rule, scoped);
+                self.db_add(db, &rule)?;
+                self.asns.push(rule);
+            },
         }
         self.sort_rules();
 
@@ -134,6 +158,12 @@ impl RuleSet {
                 self.urls.retain(|x| x.to_string().as_str() != rule);
                 self.db_delete(db, obj, rule)?;
             },
+            RuleType::Asn => {
+                // rules are stored normalized, accept AS13335 as well as 13335
+                let rule = AsnRule::try_from(rule)?.to_string();
+                self.asns.retain(|x| x.to_string() != rule);
+                self.db_delete(db, obj, &rule)?;
+            },
         }
         Ok(())
     }
@@ -152,15 +182,16 @@ impl RuleSet {
         Self::push_rules_display(&mut rules, &self.domains);
         Self::push_rules_display(&mut rules, &self.ips);
         Self::push_rules_display(&mut rules, &self.urls);
+        Self::push_rules_display(&mut rules, &self.asns);
         rules
     }
 
     pub fn is_empty(&self) -> bool {
-        self.domains.is_empty() && self.ips.is_empty() && self.urls.is_empty()
+        self.domains.is_empty() && self.ips.is_empty() && self.urls.is_empty() && self.asns.is_empty()
     }
 
     pub fn len(&self) -> usize {
-        self.domains.len() + self.ips.len() + self.urls.len()
+        self.domains.len() + self.ips.len() + self.urls.len() + self.asns.len()
     }
 
     #[inline]
@@ -175,7 +206,13 @@ impl RuleSet {
         let scoped = match object {
             Insert::Domain(domain) => Self::matches_any(&self.domains, domain)?,
             Insert::Subdomain(subdomain) => Self::matches_any(&self.domains, subdomain)?,
-            Insert::IpAddr(ip_addr) => Self::matches_any(&self.ips, ip_addr)?,
+            Insert::IpAddr(ip_addr) => {
+                if let Some(result) = Self::matches_any(&self.ips, ip_addr)? {
+                    Some(result)
+                } else {
+                    Self::matches_any(&self.asns, ip_addr)?
+                }
+            },
             Insert::Url(url) => {
                 if let Some(result) = Self::matches_any(&self.domains, url)? {
                     Some(result)
@@ -186,7 +223,14 @@ impl RuleSet {
             // Insert::Email(email) => unimplemented!(),
             // Insert::Account(account) => unimplemented!(),
             Insert::Port(port) => Self::matches_any(&self.ips, port)?,
-            Insert::Netblock(netblock) => Self::matches_any(&self.ips, netblock)?,
+            Insert::Netblock(netblock) => {
+                if let Some(result) = Self::matches_any(&self.ips, netblock)? {
+                    Some(result)
+                } else {
+                    Self::matches_any(&self.asns, netblock)?
+                }
+            },
+            Insert::Asn(asn) => Self::matches_any(&self.asns, asn)?,
             _ => None,
         };
         Ok(scoped.unwrap_or(true))
@@ -210,6 +254,7 @@ pub enum RuleType {
     Domain,
     Ip,
     Url,
+    Asn,
 }
 
 impl RuleType {
@@ -218,6 +263,7 @@ impl RuleType {
             RuleType::Domain => "domain",
             RuleType::Ip => "ip",
             RuleType::Url => "url",
+            RuleType::Asn => "asn",
         }
     }
 
@@ -227,6 +273,7 @@ impl RuleType {
                 RuleType::Domain.as_str(),
                 RuleType::Ip.as_str(),
                 RuleType::Url.as_str(),
+                RuleType::Asn.as_str(),
             ];
         }
 
@@ -242,6 +289,7 @@ impl FromStr for RuleType {
             "domain" => Ok(RuleType::Domain),
             "ip" => Ok(RuleType::Ip),
             "url" => Ok(RuleType::Url),
+            "asn" => Ok(RuleType::Asn),
             _ => bail!("unknown rule type"),
         }
     }
@@ -316,6 +364,7 @@ mod tests {
             ],
             ips: vec![],
             urls: vec![],
+            asns: vec![],
         };
         set.sort_rules();
         assert_eq!(set, RuleSet {
@@ -326,6 +375,7 @@ mod tests {
             ],
             ips: vec![],
             urls: vec![],
+            asns: vec![],
         });
     }
 
@@ -339,6 +389,7 @@ mod tests {
                 Rule::new(IpRule::try_from("10.5.6.0/24").unwrap(), true),
             ],
             urls: vec![],
+            asns: vec![],
         };
         set.sort_rules();
         // TODO: add ipv6
@@ -350,6 +401,7 @@ mod tests {
                 Rule::new(IpRule::try_from("0.0.0.0/0").unwrap(), true),
             ],
             urls: vec![],
+            asns: vec![],
         });
     }
 
@@ -363,6 +415,7 @@ mod tests {
                 Rule::new(UrlRule::try_from("https://example.com/").unwrap(), true),
                 Rule::new(UrlRule::try_from("https://example.com/foo/bar/?asdf=1").unwrap(), true),
             ],
+            asns: vec![],
         };
         set.sort_rules();
         assert_eq!(set, RuleSet {
@@ -373,6 +426,7 @@ mod tests {
                 Rule::new(UrlRule::try_from("http://example.com/foo/").unwrap(), true),
                 Rule::new(UrlRule::try_from("https://example.com/").unwrap(), true),
             ],
+            asns: vec![],
         });
     }
 }
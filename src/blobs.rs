@@ -4,6 +4,7 @@ use crate::worker::{EventWithCallback, Event2};
 use crate::workspaces::Workspace;
 
 use bytes::Bytes;
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::result;
@@ -101,6 +102,31 @@ impl BlobStorage {
             .context("Failed to stat blob")?;
         Ok(md.len())
     }
+
+    /// Make this storage contain exactly the blobs of `other`. Blobs are
+    /// immutable, so they are hardlinked if possible instead of copied.
+    pub fn sync_from(&self, other: &BlobStorage) -> Result<()> {
+        fs::create_dir_all(&self.path)
+            .context("Failed to create blobs directory")?;
+
+        let existing = self.list()?.into_iter().collect::<HashSet<_>>();
+        let wanted = other.list()?.into_iter().collect::<HashSet<_>>();
+
+        for id in wanted.difference(&existing) {
+            let src = other.join(id)?;
+            let dst = self.join(id)?;
+            if fs::hard_link(&src, &dst).is_err() {
+                fs::copy(&src, &dst)
+                    .context("Failed to copy blob")?;
+            }
+        }
+
+        for id in existing.difference(&wanted) {
+            self.delete(id)?;
+        }
+
+        Ok(())
+    }
 }
 
 
@@ -147,4 +173,21 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_blobstorage_sync_from() {
+        let dir1 = tempfile::tempdir().expect("tempdir");
+        let dir2 = tempfile::tempdir().expect("tempdir");
+        let a = BlobStorage::new(dir1.path());
+        let b = BlobStorage::new(dir2.path());
+
+        let (_, blob1) = blob();
+        let blob2 = Blob::create(Bytes::from(&b"qwer"[..]));
+        a.save(&blob1).expect("save failed");
+        b.save(&blob2).expect("save failed");
+
+        b.sync_from(&a).expect("sync failed");
+        assert_eq!(b.list().unwrap(), vec![blob1.id.clone()]);
+        assert_eq!(b.load(&blob1.id).unwrap(), blob1);
+    }
 }
@@ -1,8 +1,10 @@
 use crate::errors::*;
+use clap::ValueEnum;
 use crate::blobs::Blob;
 use crate::cmd::Cmd;
-use crate::db::DbChange;
+use crate::db::{Database, DbChange, Table};
 use crate::gfx;
+use crate::import::{self, Inserter};
 use crate::models::*;
 use crate::shell::Shell;
 use clap::Parser;
@@ -13,19 +15,37 @@ use std::fs;
 use std::io::{self, BufRead};
 use std::net;
 use std::net::SocketAddr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 #[derive(Debug, Parser)]
 pub struct Args {
     #[command(subcommand)]
-    subcommand: Target,
+    subcommand: Option<Target>,
     /// Do not actually insert into database
     #[arg(short = 'n', long="dry-run")]
     dry_run: bool,
     /// Stream structs from stdin line by line
     #[arg(long)]
     stdin: bool,
+    /// Import the results of another tool
+    #[arg(long="from", value_enum, requires="path", conflicts_with="stdin")]
+    from: Option<ImportFrom>,
+    /// The file to import with --from
+    #[arg(requires="from")]
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ImportFrom {
+    /// Output of `nmap -oX`
+    NmapXml,
+    /// Output of `masscan -oJ`
+    MasscanJson,
+    /// HTTP Archive exported by a browser or proxy
+    Har,
+    /// Items saved with burp's "Save items"
+    BurpXml,
 }
 
 #[derive(Debug, Parser)]
@@ -72,12 +92,25 @@ pub enum Target {
     /// Insert a crypto currency address into the database
     #[command(name="cryptoaddr")]
     CryptoAddr(AddCryptoAddr),
+    /// Insert a person into the database
+    #[command(name="person")]
+    Person(AddPerson),
+    /// Insert an organization into the database
+    #[command(name="organization")]
+    Organization(AddOrganization),
 }
 
 impl Cmd for Args {
     fn run(self, rl: &mut Shell) -> Result<()> {
+        if let (Some(from), Some(path)) = (&self.from, &self.path) {
+            return import_file(rl, from, path, self.dry_run);
+        }
+
+        let subcommand = self.subcommand
+            .ok_or_else(|| format_err!("Missing struct to add"))?;
+
         if self.stdin {
-            match self.subcommand {
+            match subcommand {
                 Target::Domain(_) => stream_stdin::<AddDomain>(rl),
                 Target::Subdomain(_) => stream_stdin::<AddSubdomain>(rl),
                 Target::IpAddr(_) => stream_stdin::<AddIpAddr>(rl),
@@ -86,12 +119,13 @@ impl Cmd for Args {
                 Target::PhoneNumber(_) => stream_stdin::<AddPhoneNumber>(rl),
                 Target::Port(_) => stream_stdin::<AddPort>(rl),
                 Target::CryptoAddr(_) => stream_stdin::<AddCryptoAddr>(rl),
-                Target::Device(_) | Target::Network(_) | Target::Account(_) | Target::Breach(_) | Target::Image(_) | Target::Netblock(_) => {
+                Target::Device(_) | Target::Network(_) | Target::Account(_) | Target::Breach(_) | Target::Image(_) | Target::Netblock(_) |
+                Target::Person(_) | Target::Organization(_) => {
                     bail!("This struct doesn't support streaming from stdin")
                 },
             }
         } else {
-            match self.subcommand {
+            match subcommand {
                 Target::Domain(args) => args.insert(rl, self.dry_run),
                 Target::Subdomain(args) => args.insert(rl, self.dry_run),
                 Target::IpAddr(args) => args.insert(rl, self.dry_run),
@@ -106,11 +140,53 @@ impl Cmd for Args {
                 Target::Netblock(args) => args.insert(rl, self.dry_run),
                 Target::Port(args) => args.insert(rl, self.dry_run),
                 Target::CryptoAddr(args) => args.insert(rl, self.dry_run),
+                Target::Person(args) => args.insert(rl, self.dry_run),
+                Target::Organization(args) => args.insert(rl, self.dry_run),
             }
         }
     }
 }
 
+fn import_file(rl: &mut Shell, from: &ImportFrom, path: &Path, dry_run: bool) -> Result<()> {
+    let text = fs::read_to_string(path)
+        .context("Failed to read file")?;
+
+    match from {
+        ImportFrom::NmapXml => import_hosts(rl, import::nmap::parse(&text)?, dry_run),
+        ImportFrom::MasscanJson => import_hosts(rl, import::masscan::parse(&text)?, dry_run),
+        ImportFrom::Har => import_responses(rl, import::har::parse(&text)?, dry_run),
+        ImportFrom::BurpXml => import_responses(rl, import::burp::parse(&text)?, dry_run),
+    }
+}
+
+fn import_hosts(rl: &mut Shell, hosts: import::Hosts, dry_run: bool) -> Result<()> {
+    if dry_run {
+        import::print_hosts(&hosts);
+        Ok(())
+    } else {
+        Inserter::new(rl).insert_hosts(hosts)
+    }
+}
+
+fn import_responses(rl: &mut Shell, responses: Vec<import::Response>, dry_run: bool) -> Result<()> {
+    if dry_run {
+        import::print_responses(&responses);
+        Ok(())
+    } else {
+        Inserter::new(rl).insert_responses(responses)
+    }
+}
+
+/// Insert an entity and mark it as observed right now
+fn insert_observed(db: &Database, insert: Insert) -> Result<Option<(DbChange, i32)>> {
+    let table = Table::from(&insert);
+    let result = db.insert_generic(insert)?;
+    if let Some((_, id)) = result {
+        db.seen(table, id)?;
+    }
+    Ok(result)
+}
+
 fn stream_stdin<T: InsertFromString>(rl: &mut Shell) -> Result<()> {
     let stdin = io::stdin();
     for line in stdin.lock().lines() {
@@ -118,7 +194,7 @@ fn stream_stdin<T: InsertFromString>(rl: &mut Shell) -> Result<()> {
         match T::from_string(rl, line) {
             Ok(insert) => {
                 debug!("Received {:?}", insert);
-                let result = rl.db().insert_generic(insert.clone())?;
+                let result = insert_observed(rl.db(), insert.clone())?;
 
                 if let Some((DbChange::Insert, _)) = result {
                     if let Ok(value) = insert.value(rl.db()) {
@@ -144,7 +220,7 @@ trait IntoInsert: Sized {
     fn insert(self, rl: &mut Shell, dry_run: bool) -> Result<()> {
         let insert = self.into_insert(rl)?;
         if !dry_run {
-            rl.db().insert_generic(insert)?;
+            insert_observed(rl.db(), insert)?;
         }
         Ok(())
     }
@@ -594,7 +670,7 @@ impl IntoInsert for AddImage {
                 term::info(&format!("{} {:?}", value, path.path()));
 
                 if !dry_run {
-                    rl.db().insert_generic(Insert::Image(NewImage {
+                    insert_observed(rl.db(), Insert::Image(NewImage {
                         value,
 
                         filename: Some(filename),
@@ -758,3 +834,78 @@ impl InsertFromString for AddCryptoAddr {
         }))
     }
 }
+
+#[derive(Debug, Parser)]
+pub struct AddPerson {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+impl IntoInsert for AddPerson {
+    fn into_insert(self, _rl: &mut Shell) -> Result<Insert> {
+        let (name, description) = match self.name {
+            Some(name) => (name, self.description),
+            _ => {
+                let name = utils::question("Name")?;
+                let description = utils::question_opt("Description")?;
+                (name, description)
+            }
+        };
+
+        let person = InsertPerson {
+            value: name,
+            description,
+        }.try_into_new()?;
+        Ok(Insert::Person(person))
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct AddOrganization {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+impl IntoInsert for AddOrganization {
+    fn into_insert(self, _rl: &mut Shell) -> Result<Insert> {
+        let (name, description) = match self.name {
+            Some(name) => (name, self.description),
+            _ => {
+                let name = utils::question("Name")?;
+                let description = utils::question_opt("Description")?;
+                (name, description)
+            }
+        };
+
+        let organization = InsertOrganization {
+            value: name,
+            description,
+        }.try_into_new()?;
+        Ok(Insert::Organization(organization))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_observed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let domain = || Insert::Domain(NewDomain {
+            value: String::from("example.com"),
+            unscoped: false,
+        });
+        let (_, id) = insert_observed(&db, domain()).unwrap().unwrap();
+        let first = Domain::by_id(&db, id).unwrap();
+        assert!(first.first_observed.is_some());
+        assert_eq!(first.first_observed, first.last_observed);
+
+        insert_observed(&db, domain()).unwrap();
+        let second = Domain::by_id(&db, id).unwrap();
+        assert_eq!(second.first_observed, first.first_observed);
+        assert!(second.last_observed >= first.last_observed);
+    }
+}
@@ -10,25 +10,34 @@ use crate::term;
 pub struct Args {
     #[structopt(subcommand)]
     subcommand: Target,
+    /// Only delete entities that have been added or updated by this module,
+    /// the filter is optional in this case
+    #[arg(long)]
+    by_module: Option<String>,
 }
 
 impl Cmd for Args {
     fn run(self, rl: &mut Shell) -> Result<()> {
         let rows = match &self.subcommand {
-            Target::Domains(filter) => delete::<Domain>(rl, filter),
-            Target::Subdomains(filter) => delete::<Subdomain>(rl, filter),
-            Target::IpAddrs(filter) => delete::<IpAddr>(rl, filter),
-            Target::Urls(filter) => delete::<Url>(rl, filter),
-            Target::Emails(filter) => delete::<Email>(rl, filter),
-            Target::PhoneNumbers(filter) => delete::<PhoneNumber>(rl, filter),
-            Target::Devices(filter) => delete::<Device>(rl, filter),
-            Target::Networks(filter) => delete::<Network>(rl, filter),
-            Target::Accounts(filter) => delete::<Account>(rl, filter),
-            Target::Breaches(filter) => delete::<Breach>(rl, filter),
-            Target::Images(filter) => delete::<Image>(rl, filter),
-            Target::Ports(filter) => delete::<Port>(rl, filter),
-            Target::Netblocks(filter) => delete::<Netblock>(rl, filter),
-            Target::CryptoAddrs(filter) => delete::<CryptoAddr>(rl, filter),
+            Target::Domains(filter) => delete::<Domain>(rl, filter, self.by_module.as_deref()),
+            Target::Subdomains(filter) => delete::<Subdomain>(rl, filter, self.by_module.as_deref()),
+            Target::IpAddrs(filter) => delete::<IpAddr>(rl, filter, self.by_module.as_deref()),
+            Target::Urls(filter) => delete::<Url>(rl, filter, self.by_module.as_deref()),
+            Target::Emails(filter) => delete::<Email>(rl, filter, self.by_module.as_deref()),
+            Target::PhoneNumbers(filter) => delete::<PhoneNumber>(rl, filter, self.by_module.as_deref()),
+            Target::Devices(filter) => delete::<Device>(rl, filter, self.by_module.as_deref()),
+            Target::Networks(filter) => delete::<Network>(rl, filter, self.by_module.as_deref()),
+            Target::Accounts(filter) => delete::<Account>(rl, filter, self.by_module.as_deref()),
+            Target::Breaches(filter) => delete::<Breach>(rl, filter, self.by_module.as_deref()),
+            Target::Images(filter) => delete::<Image>(rl, filter, self.by_module.as_deref()),
+            Target::Ports(filter) => delete::<Port>(rl, filter, self.by_module.as_deref()),
+            Target::Netblocks(filter) => delete::<Netblock>(rl, filter, self.by_module.as_deref()),
+            Target::CryptoAddrs(filter) => delete::<CryptoAddr>(rl, filter, self.by_module.as_deref()),
+            Target::Certificates(filter) => delete::<Certificate>(rl, filter, self.by_module.as_deref()),
+            Target::DnsRecords(filter) => delete::<DnsRecord>(rl, filter, self.by_module.as_deref()),
+            Target::Persons(filter) => delete::<Person>(rl, filter, self.by_module.as_deref()),
+            Target::Organizations(filter) => delete::<Organization>(rl, filter, self.by_module.as_deref()),
+            Target::Asns(filter) => delete::<Asn>(rl, filter, self.by_module.as_deref()),
         }?;
         term::info(&format!("Deleted {} rows", rows));
         Ok(())
@@ -41,6 +50,11 @@ pub fn run(rl: &mut Shell, args: &[String]) -> Result<()> {
 }
 
 #[inline]
-fn delete<T: Model + Detailed>(rl: &mut Shell, filter: &Filter) -> Result<usize> {
-    T::delete(rl.db(), &filter.parse()?)
+fn delete<T: Model + Detailed>(rl: &mut Shell, filter: &Filter, module: Option<&str>) -> Result<usize> {
+    let filter = if let Some(module) = module {
+        filter.parse_optional()?.and_module(module)
+    } else {
+        filter.parse()?
+    };
+    T::delete(rl.db(), &filter)
 }
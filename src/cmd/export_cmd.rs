@@ -1,13 +1,22 @@
 use clap::Parser;
 use clap::ValueEnum;
+use crate::autonoscope::Autonoscope;
 use crate::blobs::Blob;
 use crate::cmd::Cmd;
-use crate::db::ttl;
+use crate::db::Database;
+use crate::db::history::History;
+use crate::db::provenance::{Provenance, Run};
+use crate::db::tags::{Note, Tag};
+use crate::db::ttl::{self, Ttl};
 use crate::errors::*;
+use crate::export::geo::FeatureCollection;
+use crate::export::graph::Graph;
+use crate::export::ndjson;
+use crate::export::stix::Bundle;
 use crate::models::*;
 use crate::shell::Shell;
 use serde::{Serialize, Deserialize};
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 use strum_macros::{EnumString, IntoStaticStr};
 
 #[derive(Debug, Parser)]
@@ -23,10 +32,60 @@ impl Cmd for Args {
         match self.format {
             Format::Json => export::<JsonFormat>(rl),
             Format::JsonBlobs => export::<JsonBlobsFormat>(rl),
+            Format::Ndjson => export_ndjson(rl, false),
+            Format::NdjsonBlobs => export_ndjson(rl, true),
+            Format::Graphml => export_graph(rl, |graph, w| graph.write_graphml(w)),
+            Format::Gexf => export_graph(rl, |graph, w| graph.write_gexf(w)),
+            Format::Dot => export_graph(rl, |graph, w| graph.write_dot(w)),
+            Format::Stix => export_stix(rl),
+            Format::Geojson => export_geo(rl, |geo, w| geo.write_geojson(w)),
+            Format::Kml => export_geo(rl, |geo, w| geo.write_kml(w)),
         }
     }
 }
 
+fn export_graph<F>(rl: &mut Shell, write: F) -> Result<()>
+    where F: FnOnce(&Graph, &mut dyn Write) -> Result<()>
+{
+    let graph = Graph::load(rl.db())?;
+    let stdout = io::stdout();
+    let mut stdout = BufWriter::new(stdout.lock());
+    write(&graph, &mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn export_ndjson(rl: &mut Shell, blobs: bool) -> Result<()> {
+    let storage = if blobs {
+        Some(rl.blobs())
+    } else {
+        None
+    };
+    let stdout = io::stdout();
+    let mut stdout = BufWriter::new(stdout.lock());
+    ndjson::export(&mut stdout, rl.db(), storage)
+}
+
+fn export_geo<F>(rl: &mut Shell, write: F) -> Result<()>
+    where F: FnOnce(&FeatureCollection, &mut dyn Write) -> Result<()>
+{
+    let collection = FeatureCollection::load(rl.db())?;
+    let stdout = io::stdout();
+    let mut stdout = BufWriter::new(stdout.lock());
+    write(&collection, &mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn export_stix(rl: &mut Shell) -> Result<()> {
+    let bundle = Bundle::load(rl.db())?;
+    let stdout = io::stdout();
+    let mut stdout = BufWriter::new(stdout.lock());
+    bundle.write(&mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
 fn export<T: ExportFormat + Serialize>(rl: &mut Shell) -> Result<()> {
     let export = T::load(rl)?;
     let mut stdout = io::stdout();
@@ -41,61 +100,156 @@ fn export<T: ExportFormat + Serialize>(rl: &mut Shell) -> Result<()> {
 pub enum Format {
     Json,
     JsonBlobs,
+    Ndjson,
+    NdjsonBlobs,
+    Graphml,
+    Gexf,
+    Dot,
+    Stix,
+    Geojson,
+    Kml,
 }
 
 trait ExportFormat {
     fn load(rl: &mut Shell) -> Result<Box<Self>>;
 }
 
+/// Bumped on every change that breaks existing consumers, see docs/export.rst
+pub const FORMAT_VERSION: u32 = 1;
+
 #[derive(Serialize, Deserialize)]
-struct JsonFormat {
-    accounts: Vec<Account>,
-    breaches: Vec<Breach>,
-    breach_emails: Vec<BreachEmail>,
-    devices: Vec<Device>,
-    domains: Vec<Domain>,
-    emails: Vec<Email>,
-    images: Vec<Image>,
-    ipaddrs: Vec<IpAddr>,
-    netblocks: Vec<Netblock>,
-    networks: Vec<Netblock>,
-    network_devices: Vec<NetworkDevice>,
-    phonenumbers: Vec<PhoneNumber>,
-    ports: Vec<Port>,
-    subdomains: Vec<Subdomain>,
-    subdomain_ipaddrs: Vec<SubdomainIpAddr>,
-    urls: Vec<Url>,
+pub(crate) struct JsonFormat {
+    // exports created before versioning was introduced are version 0
+    #[serde(default)]
+    pub(crate) format_version: u32,
+    #[serde(default)]
+    pub(crate) sn0int_version: Option<String>,
+    pub(crate) accounts: Vec<Account>,
+    #[serde(default)]
+    pub(crate) activity: Vec<Activity>,
+    #[serde(default)]
+    pub(crate) asns: Vec<Asn>,
+    #[serde(default)]
+    pub(crate) asn_ipaddrs: Vec<AsnIpAddr>,
+    #[serde(default)]
+    pub(crate) asn_netblocks: Vec<AsnNetblock>,
+    #[serde(default)]
+    pub(crate) autonoscope: Vec<Autonoscope>,
+    pub(crate) breaches: Vec<Breach>,
+    pub(crate) breach_emails: Vec<BreachEmail>,
+    #[serde(default)]
+    pub(crate) certificates: Vec<Certificate>,
+    #[serde(default)]
+    pub(crate) cryptoaddrs: Vec<CryptoAddr>,
+    pub(crate) devices: Vec<Device>,
+    #[serde(default)]
+    pub(crate) dns_records: Vec<DnsRecord>,
+    pub(crate) domains: Vec<Domain>,
+    pub(crate) emails: Vec<Email>,
+    #[serde(default)]
+    pub(crate) history: Vec<History>,
+    pub(crate) images: Vec<Image>,
+    pub(crate) ipaddrs: Vec<IpAddr>,
+    pub(crate) netblocks: Vec<Netblock>,
+    pub(crate) networks: Vec<Network>,
+    pub(crate) network_devices: Vec<NetworkDevice>,
+    #[serde(default)]
+    pub(crate) notes: Vec<Note>,
+    #[serde(default)]
+    pub(crate) organizations: Vec<Organization>,
+    #[serde(default)]
+    pub(crate) organization_domains: Vec<OrganizationDomain>,
+    #[serde(default)]
+    pub(crate) persons: Vec<Person>,
+    #[serde(default)]
+    pub(crate) person_accounts: Vec<PersonAccount>,
+    #[serde(default)]
+    pub(crate) person_emails: Vec<PersonEmail>,
+    #[serde(default)]
+    pub(crate) person_images: Vec<PersonImage>,
+    #[serde(default)]
+    pub(crate) person_organizations: Vec<PersonOrganization>,
+    #[serde(default)]
+    pub(crate) person_phonenumbers: Vec<PersonPhoneNumber>,
+    pub(crate) phonenumbers: Vec<PhoneNumber>,
+    #[serde(default)]
+    pub(crate) port_certificates: Vec<PortCertificate>,
+    pub(crate) ports: Vec<Port>,
+    #[serde(default)]
+    pub(crate) provenance: Vec<Provenance>,
+    #[serde(default)]
+    pub(crate) runs: Vec<Run>,
+    pub(crate) subdomains: Vec<Subdomain>,
+    #[serde(default)]
+    pub(crate) subdomain_certificates: Vec<SubdomainCertificate>,
+    pub(crate) subdomain_ipaddrs: Vec<SubdomainIpAddr>,
+    #[serde(default)]
+    pub(crate) tags: Vec<Tag>,
+    #[serde(default)]
+    pub(crate) ttls: Vec<Ttl>,
+    pub(crate) urls: Vec<Url>,
 }
 
-impl ExportFormat for JsonFormat {
-    fn load(rl: &mut Shell) -> Result<Box<JsonFormat>> {
-        let db = rl.db();
-
-        Ok(Box::new(JsonFormat {
+impl JsonFormat {
+    pub(crate) fn from_db(db: &Database) -> Result<JsonFormat> {
+        Ok(JsonFormat {
+            format_version: FORMAT_VERSION,
+            sn0int_version: Some(env!("CARGO_PKG_VERSION").to_string()),
             accounts: Account::list(db)?,
+            activity: Activity::list(db)?,
+            asns: Asn::list(db)?,
+            asn_ipaddrs: AsnIpAddr::list(db)?,
+            asn_netblocks: AsnNetblock::list(db)?,
+            autonoscope: Autonoscope::list(db.db())?,
             breaches: Breach::list(db)?,
             breach_emails: BreachEmail::list(db)?,
+            certificates: Certificate::list(db)?,
+            cryptoaddrs: CryptoAddr::list(db)?,
             devices: Device::list(db)?,
+            dns_records: DnsRecord::list(db)?,
             domains: Domain::list(db)?,
             emails: Email::list(db)?,
+            history: History::list_all(db)?,
             images: Image::list(db)?,
             ipaddrs: IpAddr::list(db)?,
             netblocks: Netblock::list(db)?,
-            networks: Netblock::list(db)?,
+            networks: Network::list(db)?,
             network_devices: NetworkDevice::list(db)?,
+            notes: Note::list(db)?,
+            organizations: Organization::list(db)?,
+            organization_domains: OrganizationDomain::list(db)?,
+            persons: Person::list(db)?,
+            person_accounts: PersonAccount::list(db)?,
+            person_emails: PersonEmail::list(db)?,
+            person_images: PersonImage::list(db)?,
+            person_organizations: PersonOrganization::list(db)?,
+            person_phonenumbers: PersonPhoneNumber::list(db)?,
             phonenumbers: PhoneNumber::list(db)?,
+            port_certificates: PortCertificate::list(db)?,
             ports: Port::list(db)?,
+            provenance: Provenance::list_all(db)?,
+            runs: Run::list(db)?,
             subdomains: Subdomain::list(db)?,
+            subdomain_certificates: SubdomainCertificate::list(db)?,
             subdomain_ipaddrs: SubdomainIpAddr::list(db)?,
+            tags: Tag::list(db)?,
+            ttls: Ttl::list(db)?,
             urls: Url::list(db)?,
-        }))
+        })
+    }
+}
+
+impl ExportFormat for JsonFormat {
+    fn load(rl: &mut Shell) -> Result<Box<JsonFormat>> {
+        let export = JsonFormat::from_db(rl.db())?;
+        Ok(Box::new(export))
     }
 }
 
 #[derive(Serialize, Deserialize)]
-struct JsonBlobsFormat {
-    models: JsonFormat,
-    blobs: Vec<Blob>,
+pub(crate) struct JsonBlobsFormat {
+    pub(crate) models: JsonFormat,
+    pub(crate) blobs: Vec<Blob>,
 }
 
 impl ExportFormat for JsonBlobsFormat {
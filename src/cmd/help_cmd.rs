@@ -15,13 +15,18 @@ pub fn run(_rl: &mut Shell, _args: &[String]) -> Result<()> {
     help("autonoscope", "Manage rules to automatically remove entities from scope");
     help("autoscope",   "Manage rules to automatically add entities to scope");
     help("delete",      "Delete entities from the database");
+    help("history",     "Show the recorded changes of an entity");
     help("keyring",     "Manage saved credentials");
     help("pkg",         "Manage installed modules");
+    help("note",        "Attach notes to entities");
     help("noscope",     "Exclude entities from scope");
     help("run",         "Run the currently selected module");
+    help("runs",        "List or revert module runs");
     help("scope",       "Include entities in the scope again");
+    help("search",      "Search all entities for a text");
     help("select",      "Select entities from the database");
     help("stats",       "Show statistics about your current workspace");
+    help("tag",         "Add, remove or list tags of entities");
     help("target",      "Preview targeted entities or narrow them down");
     help("use",         "Select a module");
     help("workspace",   "Switch to a different workspace");
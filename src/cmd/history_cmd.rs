@@ -0,0 +1,40 @@
+use crate::errors::*;
+use crate::cmd::Cmd;
+use crate::db::Family;
+use crate::db::history::History;
+use crate::shell::Shell;
+use crate::term;
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The type of the entity, eg. subdomain or port
+    family: Family,
+    /// The value of the entity
+    value: String,
+    /// Print the changes as json
+    #[arg(long)]
+    json: bool,
+}
+
+impl Cmd for Args {
+    fn run(self, rl: &mut Shell) -> Result<()> {
+        let db = rl.db();
+        let (table, key) = db.get_entity(&self.family, &self.value)?;
+
+        let history = History::list(db, table, key)?;
+        if history.is_empty() && !self.json {
+            term::info(&format!("No changes recorded for {} {:?}", self.family.as_str(), self.value));
+        }
+
+        for change in history {
+            if self.json {
+                println!("{}", serde_json::to_string(&change)?);
+            } else {
+                println!("{}", change.to_term_str()?);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,746 @@
+use clap::{Parser, ValueEnum};
+use crate::autonoscope::RuleType;
+use crate::blobs::BlobStorage;
+use crate::cmd::Cmd;
+use crate::cmd::export_cmd::{FORMAT_VERSION, JsonFormat, JsonBlobsFormat};
+use crate::db::{Database, DbChange, Filter, Table};
+use crate::db::history::NewHistory;
+use crate::db::provenance::{NewProvenance, NewRun};
+use crate::db::tags::{NewNote, Note, Tag};
+use crate::db::ttl::NewTtl;
+use crate::errors::*;
+use crate::models::*;
+use crate::schema::{activity, history, images, notes, runs, tags, ttls, urls};
+use crate::shell::Shell;
+use crate::term;
+use crate::utils;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Delete everything in the workspace before importing
+    #[arg(long="replace")]
+    replace: bool,
+    /// Don't ask for confirmation
+    #[arg(short = 'y', long)]
+    yes: bool,
+    /// Read the export from a file instead of stdin
+    path: Option<PathBuf>,
+}
+
+impl Cmd for Args {
+    fn run(self, rl: &mut Shell) -> Result<()> {
+        let import: ImportFormat = if let Some(path) = &self.path {
+            let file = File::open(path)
+                .context("Failed to open export")?;
+            serde_json::from_reader(BufReader::new(file))
+        } else {
+            serde_json::from_reader(io::stdin().lock())
+        }.context("Failed to parse export")?;
+
+        let (models, blobs) = match import {
+            ImportFormat::JsonBlobs(export) => (export.models, export.blobs),
+            ImportFormat::Json(export) => (export, Vec::new()),
+        };
+
+        if models.format_version > FORMAT_VERSION {
+            bail!("Export format version {} is not supported, upgrade sn0int to import it (created by sn0int {})",
+                models.format_version,
+                models.sn0int_version.as_deref().unwrap_or("unknown"));
+        }
+
+        if self.replace && !self.yes {
+            let msg = format!("Delete everything in {:?} before importing?", rl.db().name());
+            if !utils::no_else_yes(&msg)? {
+                return Ok(());
+            }
+        }
+
+        // nothing is changed if any part of the import fails
+        let stats = rl.db_mut().transaction(|db| {
+            if self.replace {
+                wipe(db)?;
+            }
+
+            for rule in &models.autonoscope {
+                let object = rule.object.parse::<RuleType>()?;
+                db.autonoscope_add_rule(&object, &rule.value, rule.scoped)?;
+            }
+
+            let mut importer = Importer::new(db, ScopePolicy::KeepDst);
+            importer.import(models)?;
+            Ok(importer.stats)
+        })?;
+
+        let storage = rl.blobs();
+        for blob in &blobs {
+            storage.save(blob)?;
+        }
+        if self.replace {
+            // plain json exports don't carry blobs, only remove the ones that
+            // aren't referenced by the imported entities
+            remove_unreferenced_blobs(rl.db(), storage)?;
+        }
+
+        term::info(&format!("Imported {} entities ({} new), {} events and {} blobs",
+            stats.total, stats.inserted, stats.activity, blobs.len()));
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ImportFormat {
+    JsonBlobs(JsonBlobsFormat),
+    Json(JsonFormat),
+}
+
+/// Delete every blob that isn't used by an url or an image
+fn remove_unreferenced_blobs(db: &Database, storage: &BlobStorage) -> Result<()> {
+    let mut keep = urls::table
+        .select(urls::blob)
+        .filter(urls::blob.is_not_null())
+        .load::<Option<String>>(db.db())?
+        .into_iter()
+        .flatten()
+        .collect::<HashSet<_>>();
+    keep.extend(images::table
+        .select(images::value)
+        .load::<String>(db.db())?);
+
+    for id in storage.list()? {
+        if !keep.contains(&id) {
+            storage.delete(&id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Delete all entities, their history and rules, relation tables are cleared by ON DELETE CASCADE
+fn wipe(db: &mut Database) -> Result<()> {
+    let filter = Filter::any();
+    Domain::delete(db, &filter)?;
+    IpAddr::delete(db, &filter)?;
+    Email::delete(db, &filter)?;
+    PhoneNumber::delete(db, &filter)?;
+    Device::delete(db, &filter)?;
+    Network::delete(db, &filter)?;
+    Account::delete(db, &filter)?;
+    Breach::delete(db, &filter)?;
+    Image::delete(db, &filter)?;
+    Netblock::delete(db, &filter)?;
+    CryptoAddr::delete(db, &filter)?;
+    Certificate::delete(db, &filter)?;
+    Person::delete(db, &filter)?;
+    Organization::delete(db, &filter)?;
+    Asn::delete(db, &filter)?;
+
+    diesel::delete(activity::table)
+        .execute(db.db())?;
+    diesel::delete(ttls::table)
+        .execute(db.db())?;
+    diesel::delete(tags::table)
+        .execute(db.db())?;
+    diesel::delete(notes::table)
+        .execute(db.db())?;
+    // provenance is removed together with its run
+    diesel::delete(runs::table)
+        .execute(db.db())?;
+    diesel::delete(history::table)
+        .execute(db.db())?;
+
+    for (object, value, _) in db.autonoscope_rules() {
+        let object = object.parse::<RuleType>()?;
+        db.autonoscope_delete_rule(&object, &value)?;
+    }
+
+    Ok(())
+}
+
+/// Maps the row ids of the export to the row ids in the current workspace
+#[derive(Default)]
+struct IdMap {
+    ids: HashMap<i32, (i32, bool)>,
+}
+
+impl IdMap {
+    fn insert(&mut self, old: i32, new: i32, inserted: bool) {
+        self.ids.insert(old, (new, inserted));
+    }
+
+    fn get(&self, table: &str, old: i32) -> Result<(i32, bool)> {
+        self.ids.get(&old)
+            .copied()
+            .ok_or_else(|| format_err!("Export references unknown {} id: {}", table, old))
+    }
+}
+
+/// Decides the scope of entities that exist in both workspaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ScopePolicy {
+    /// Keep the scope of the workspace we import into
+    KeepDst,
+    /// Scope the entity if it's scoped in either workspace
+    PreferScoped,
+    /// Unscope the entity if it's unscoped in either workspace
+    PreferUnscoped,
+}
+
+impl ScopePolicy {
+    fn scoped(self, dst: bool, src: bool) -> bool {
+        match self {
+            ScopePolicy::KeepDst => dst,
+            ScopePolicy::PreferScoped => dst || src,
+            ScopePolicy::PreferUnscoped => dst && src,
+        }
+    }
+}
+
+/// The first_observed and last_observed timestamps of an exported entity
+type Seen = (Option<NaiveDateTime>, Option<NaiveDateTime>);
+
+/// What an import changed in the workspace
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ImportStats {
+    pub(crate) total: usize,
+    pub(crate) inserted: usize,
+    pub(crate) updated: usize,
+    pub(crate) rescoped: usize,
+    pub(crate) activity: usize,
+}
+
+pub(crate) struct Importer<'a> {
+    db: &'a Database,
+    policy: ScopePolicy,
+    ids: HashMap<&'static str, IdMap>,
+    pub(crate) stats: ImportStats,
+}
+
+impl<'a> Importer<'a> {
+    pub(crate) fn new(db: &'a Database, policy: ScopePolicy) -> Importer<'a> {
+        Importer {
+            db,
+            policy,
+            ids: HashMap::new(),
+            stats: ImportStats::default(),
+        }
+    }
+
+    /// Resolve a row id of the export to the row id in the current workspace
+    fn id(&self, table: Table, old: i32) -> Result<i32> {
+        let table = table.as_str();
+        let (id, _) = self.ids.get(table)
+            .ok_or_else(|| format_err!("Export references unknown {} id: {}", table, old))?
+            .get(table, old)?;
+        Ok(id)
+    }
+
+    /// Resolve the entity of a ttl, tag, note, provenance or history row. Exports
+    /// of older versions may still contain them for entities that have been
+    /// deleted, those are skipped.
+    fn annotated(&self, table: Table, old: i32) -> Option<(i32, bool)> {
+        self.ids.get(table.as_str())?
+            .ids.get(&old)
+            .copied()
+    }
+
+    fn record(&mut self, table: &'static str, old: i32, new: i32, inserted: bool) {
+        self.stats.total += 1;
+        if inserted {
+            self.stats.inserted += 1;
+        }
+        self.ids.entry(table)
+            .or_default()
+            .insert(old, new, inserted);
+    }
+
+    /// Insert an entity that is identified by its value and restore its scope
+    fn restore<T: Model<ID=str> + Scopable>(&mut self, old: i32, unscoped: bool, seen: Seen, object: Insert) -> Result<()> {
+        let table = Table::from(&object);
+        let value = object.value(self.db)?.into_owned();
+
+        // updates of unscoped entities are discarded, so scope them before the
+        // upsert but unscope them afterwards
+        let mut unscope = None;
+        if let Some(existing) = T::get_opt(self.db, &value)? {
+            let scoped = self.policy.scoped(existing.scoped(), !unscoped);
+            if scoped && !existing.scoped() {
+                existing.set_scoped(self.db, true)?;
+                self.stats.rescoped += 1;
+            } else if !scoped && existing.scoped() {
+                unscope = Some(existing);
+            }
+        }
+
+        let (id, inserted) = match self.db.insert_generic(object)? {
+            Some((DbChange::Insert, id)) => {
+                // entities that are new to this workspace keep the scope they had in the export
+                let obj = T::by_id(self.db, id)?;
+                if obj.scoped() == unscoped {
+                    obj.set_scoped(self.db, !unscoped)?;
+                }
+                (id, true)
+            },
+            Some((change, id)) => {
+                if let DbChange::Update(..) = change {
+                    self.stats.updated += 1;
+                }
+                (id, false)
+            },
+            // entity exists but is out of scope
+            None => (T::get_id(self.db, &value)?, false),
+        };
+
+        if let Some(existing) = unscope {
+            existing.set_scoped(self.db, false)?;
+            self.stats.rescoped += 1;
+        }
+
+        self.db.merge_seen(table, id, seen.0, seen.1)?;
+        self.record(table.as_str(), old, id, inserted);
+        Ok(())
+    }
+
+    /// Insert an entity that is identified by the entities it connects
+    fn link(&mut self, old: i32, seen: Seen, object: Insert) -> Result<()> {
+        let table = Table::from(&object);
+        match self.db.insert_generic(object)? {
+            Some((change, id)) => {
+                let inserted = matches!(change, DbChange::Insert);
+                if let DbChange::Update(..) = change {
+                    self.stats.updated += 1;
+                }
+                self.db.merge_seen(table, id, seen.0, seen.1)?;
+                self.record(table.as_str(), old, id, inserted);
+            },
+            None => bail!("Failed to insert into {}", table.as_str()),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn import(&mut self, export: JsonFormat) -> Result<()> {
+        for x in export.domains {
+            self.restore::<Domain>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Domain(x.into_new()))?;
+        }
+
+        for x in export.subdomains {
+            let domain_id = self.id(Table::Domains, x.domain_id)?;
+            self.restore::<Subdomain>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Subdomain(NewSubdomain {
+                domain_id,
+                ..x.into_new()
+            }))?;
+        }
+
+        for x in export.ipaddrs {
+            self.restore::<IpAddr>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::IpAddr(x.into_new()))?;
+        }
+
+        for x in export.subdomain_ipaddrs {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::SubdomainIpAddr(NewSubdomainIpAddr {
+                subdomain_id: self.id(Table::Subdomains, x.subdomain_id)?,
+                ip_addr_id: self.id(Table::Ipaddrs, x.ip_addr_id)?,
+            }))?;
+        }
+
+        for x in export.urls {
+            let subdomain_id = self.id(Table::Subdomains, x.subdomain_id)?;
+            self.restore::<Url>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Url(NewUrl {
+                subdomain_id,
+                ..x.into_new()
+            }))?;
+        }
+
+        for x in export.emails {
+            self.restore::<Email>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Email(x.into_new()))?;
+        }
+
+        for x in export.phonenumbers {
+            self.restore::<PhoneNumber>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::PhoneNumber(x.into_new()))?;
+        }
+
+        for x in export.devices {
+            self.restore::<Device>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Device(x.into_new()))?;
+        }
+
+        for x in export.networks {
+            self.restore::<Network>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Network(x.into_new()))?;
+        }
+
+        for x in export.network_devices {
+            let network_id = self.id(Table::Networks, x.network_id)?;
+            let device_id = self.id(Table::Devices, x.device_id)?;
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::NetworkDevice(NewNetworkDevice {
+                network_id,
+                device_id,
+                ..x.into_new()
+            }))?;
+        }
+
+        for x in export.accounts {
+            self.restore::<Account>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Account(x.into_new()))?;
+        }
+
+        for x in export.breaches {
+            self.restore::<Breach>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Breach(x.into_new()))?;
+        }
+
+        for x in export.breach_emails {
+            let breach_id = self.id(Table::Breaches, x.breach_id)?;
+            let email_id = self.id(Table::Emails, x.email_id)?;
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::BreachEmail(NewBreachEmail {
+                breach_id,
+                email_id,
+                ..x.into_new()
+            }))?;
+        }
+
+        for x in export.images {
+            self.restore::<Image>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Image(x.into_new()))?;
+        }
+
+        for x in export.ports {
+            let ip_addr_id = self.id(Table::Ipaddrs, x.ip_addr_id)?;
+            self.restore::<Port>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Port(NewPort {
+                ip_addr_id,
+                ..x.into_new()
+            }))?;
+        }
+
+        for x in export.netblocks {
+            self.restore::<Netblock>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Netblock(x.into_new()))?;
+        }
+
+        for x in export.cryptoaddrs {
+            self.restore::<CryptoAddr>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::CryptoAddr(x.into_new()))?;
+        }
+
+        for x in export.certificates {
+            self.restore::<Certificate>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Certificate(x.into_new()))?;
+        }
+
+        for x in export.port_certificates {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::PortCertificate(NewPortCertificate {
+                port_id: self.id(Table::Ports, x.port_id)?,
+                certificate_id: self.id(Table::Certificates, x.certificate_id)?,
+            }))?;
+        }
+
+        for x in export.subdomain_certificates {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::SubdomainCertificate(NewSubdomainCertificate {
+                subdomain_id: self.id(Table::Subdomains, x.subdomain_id)?,
+                certificate_id: self.id(Table::Certificates, x.certificate_id)?,
+            }))?;
+        }
+
+        for x in export.dns_records {
+            let subdomain_id = self.id(Table::Subdomains, x.subdomain_id)?;
+            self.restore::<DnsRecord>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::DnsRecord(NewDnsRecord {
+                subdomain_id,
+                ..x.into_new()
+            }))?;
+        }
+
+        for x in export.persons {
+            self.restore::<Person>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Person(x.into_new()))?;
+        }
+
+        for x in export.organizations {
+            self.restore::<Organization>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Organization(x.into_new()))?;
+        }
+
+        for x in export.person_emails {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::PersonEmail(NewPersonEmail {
+                person_id: self.id(Table::Persons, x.person_id)?,
+                email_id: self.id(Table::Emails, x.email_id)?,
+            }))?;
+        }
+
+        for x in export.person_accounts {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::PersonAccount(NewPersonAccount {
+                person_id: self.id(Table::Persons, x.person_id)?,
+                account_id: self.id(Table::Accounts, x.account_id)?,
+            }))?;
+        }
+
+        for x in export.person_phonenumbers {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::PersonPhoneNumber(NewPersonPhoneNumber {
+                person_id: self.id(Table::Persons, x.person_id)?,
+                phonenumber_id: self.id(Table::Phonenumbers, x.phonenumber_id)?,
+            }))?;
+        }
+
+        for x in export.person_images {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::PersonImage(NewPersonImage {
+                person_id: self.id(Table::Persons, x.person_id)?,
+                image_id: self.id(Table::Images, x.image_id)?,
+            }))?;
+        }
+
+        for x in export.person_organizations {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::PersonOrganization(NewPersonOrganization {
+                person_id: self.id(Table::Persons, x.person_id)?,
+                organization_id: self.id(Table::Organizations, x.organization_id)?,
+            }))?;
+        }
+
+        for x in export.organization_domains {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::OrganizationDomain(NewOrganizationDomain {
+                organization_id: self.id(Table::Organizations, x.organization_id)?,
+                domain_id: self.id(Table::Domains, x.domain_id)?,
+            }))?;
+        }
+
+        for x in export.asns {
+            self.restore::<Asn>(x.id, x.unscoped, (x.first_observed, x.last_observed), Insert::Asn(x.into_new()))?;
+        }
+
+        for x in export.asn_netblocks {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::AsnNetblock(NewAsnNetblock {
+                asn_id: self.id(Table::Asns, x.asn_id)?,
+                netblock_id: self.id(Table::Netblocks, x.netblock_id)?,
+            }))?;
+        }
+
+        for x in export.asn_ipaddrs {
+            self.link(x.id, (x.first_observed, x.last_observed), Insert::AsnIpAddr(NewAsnIpAddr {
+                asn_id: self.id(Table::Asns, x.asn_id)?,
+                ip_addr_id: self.id(Table::Ipaddrs, x.ip_addr_id)?,
+            }))?;
+        }
+
+        for x in export.ttls {
+            let table = x.family.parse::<Table>()?;
+            let (key, inserted) = match self.annotated(table, x.key) {
+                Some(entity) => entity,
+                None => continue,
+            };
+
+            // never let an entity expire that already existed in this workspace
+            if inserted {
+                NewTtl {
+                    family: table.as_str(),
+                    key,
+                    value: x.value,
+                    expire: x.expire,
+                }.insert(self.db)?;
+            }
+        }
+
+        for x in export.tags {
+            let table = x.family.parse::<Table>()?;
+            if let Some((key, _)) = self.annotated(table, x.key) {
+                Tag::add(self.db, table, key, &x.name)?;
+            }
+        }
+
+        for x in export.notes {
+            let table = x.family.parse::<Table>()?;
+            let key = match self.annotated(table, x.key) {
+                Some((key, _)) => key,
+                None => continue,
+            };
+            let note = Note {
+                family: table.as_str().to_string(),
+                key,
+                ..x
+            };
+
+            if !note.exists(self.db)? {
+                NewNote {
+                    family: &note.family,
+                    key: note.key,
+                    body: &note.body,
+                    time: note.time,
+                }.insert(self.db)?;
+            }
+        }
+
+        // runs that have been imported before are reused instead of duplicated
+        let mut runs = HashMap::new();
+        for x in export.runs {
+            let id = NewRun {
+                module: &x.module,
+                version: &x.version,
+                started: x.started,
+            }.get_or_insert(self.db)?;
+            runs.insert(x.id, id);
+        }
+
+        for x in export.provenance {
+            let table = x.family.parse::<Table>()?;
+            let key = match self.annotated(table, x.key) {
+                Some((key, _)) => key,
+                None => continue,
+            };
+            let run_id = *runs.get(&x.run_id)
+                .ok_or_else(|| format_err!("Export references unknown runs id: {}", x.run_id))?;
+
+            let provenance = NewProvenance {
+                family: table.as_str(),
+                key,
+                run_id,
+                action: &x.action,
+                time: x.time,
+                previous: x.previous,
+            };
+            if !provenance.exists(self.db)? {
+                provenance.insert(self.db)?;
+            }
+        }
+
+        for x in export.history {
+            let table = x.family.parse::<Table>()?;
+            let key = match self.annotated(table, x.key) {
+                Some((key, _)) => key,
+                None => continue,
+            };
+
+            let history = NewHistory {
+                family: table.as_str(),
+                key,
+                field: &x.field,
+                old: x.old,
+                new: x.new,
+                time: x.time,
+                source: x.source.as_deref(),
+            };
+            if !history.exists(self.db)? {
+                history.insert(self.db)?;
+            }
+        }
+
+        for x in export.activity {
+            let activity = NewActivity {
+                topic: x.topic,
+                time: x.time,
+                uniq: x.uniq,
+                latitude: x.latitude,
+                longitude: x.longitude,
+                radius: x.radius,
+                content: x.content,
+            };
+
+            if !activity.exists(self.db)? && self.db.insert_activity(activity)? {
+                self.stats.activity += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_policy() {
+        let cases = [
+            (ScopePolicy::KeepDst, [true, true, false, false]),
+            (ScopePolicy::PreferScoped, [true, true, true, false]),
+            (ScopePolicy::PreferUnscoped, [true, false, false, false]),
+        ];
+        for (policy, expected) in cases {
+            let scoped = [
+                policy.scoped(true, true),
+                policy.scoped(true, false),
+                policy.scoped(false, true),
+                policy.scoped(false, false),
+            ];
+            assert_eq!(scoped, expected, "{:?}", policy);
+        }
+    }
+
+    #[test]
+    fn test_remove_unreferenced_blobs() {
+        use crate::blobs::Blob;
+        use bytes::Bytes;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+        let blobs_dir = tempfile::tempdir().expect("tempdir");
+        let storage = BlobStorage::new(blobs_dir.path());
+
+        let used = Blob::create(Bytes::from(&b"used"[..]));
+        let unused = Blob::create(Bytes::from(&b"unused"[..]));
+        storage.save(&used).unwrap();
+        storage.save(&unused).unwrap();
+
+        db.insert_generic(Insert::Image(NewImage {
+            value: used.id.clone(),
+            filename: None,
+            mime: None,
+            width: None,
+            height: None,
+            created: None,
+            latitude: None,
+            longitude: None,
+            nudity: None,
+            ahash: None,
+            dhash: None,
+            phash: None,
+            unscoped: false,
+        })).unwrap();
+
+        remove_unreferenced_blobs(&db, &storage).unwrap();
+        assert_eq!(storage.list().unwrap(), vec![used.id]);
+    }
+
+    #[test]
+    fn test_import_provenance() {
+        use crate::db::history::History;
+        use crate::db::provenance::{Action, Provenance};
+        use chrono::Utc;
+
+        let src_dir = tempfile::tempdir().expect("tempdir");
+        let src = Database::establish_tmp(src_dir.path()).unwrap();
+        let dst_dir = tempfile::tempdir().expect("tempdir");
+        let dst = Database::establish_tmp(dst_dir.path()).unwrap();
+
+        let domain = |value: &str| Insert::Domain(NewDomain {
+            value: value.to_string(),
+            unscoped: false,
+        });
+        // make sure the ids of both workspaces differ
+        dst.insert_generic(domain("example.org")).unwrap();
+        let (_, src_id) = src.insert_generic(domain("example.com")).unwrap().unwrap();
+
+        let run_id = NewRun {
+            module: "kpcyrd/ctlogs",
+            version: "0.1.0",
+            started: Utc::now().naive_utc(),
+        }.get_or_insert(&src).unwrap();
+        Provenance::record(&src, Table::Domains, src_id, run_id, Action::Insert, None).unwrap();
+        NewHistory {
+            family: "domains",
+            key: src_id,
+            field: "unscoped",
+            old: String::from("true"),
+            new: String::from("false"),
+            time: Utc::now().naive_utc(),
+            source: None,
+        }.insert(&src).unwrap();
+
+        // importing twice must not duplicate anything
+        for _ in 0..2 {
+            let export = JsonFormat::from_db(&src).unwrap();
+            Importer::new(&dst, ScopePolicy::KeepDst).import(export).unwrap();
+        }
+
+        let dst_id = Domain::get_id(&dst, "example.com").unwrap();
+        assert_ne!(dst_id, src_id);
+        let provenance = Provenance::list(&dst, Table::Domains, dst_id).unwrap();
+        assert_eq!(provenance.len(), 1);
+        assert_eq!(provenance[0].1.module, "kpcyrd/ctlogs");
+        let history = History::list(&dst, Table::Domains, dst_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].field, "unscoped");
+    }
+}
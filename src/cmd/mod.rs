@@ -25,10 +25,15 @@ pub mod delete_cmd;
 pub mod export_cmd;
 pub mod fsck_cmd;
 pub mod help_cmd;
+pub mod history_cmd;
+pub mod import_cmd;
 pub mod run_cmd;
+pub mod runs_cmd;
+pub mod search_cmd;
 pub mod use_cmd;
 pub mod select_cmd;
 pub mod keyring_cmd;
+pub mod note_cmd;
 pub mod noscope_cmd;
 pub mod notify_cmd;
 pub mod pkg_cmd;
@@ -36,6 +41,7 @@ pub mod rescope_cmd;
 pub mod set_cmd;
 pub mod scope_cmd;
 pub mod stats_cmd;
+pub mod tag_cmd;
 pub mod target_cmd;
 pub mod quickstart_cmd;
 pub mod workspace_cmd;
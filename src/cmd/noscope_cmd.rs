@@ -29,6 +29,11 @@ impl Cmd for Args {
             Target::Ports(filter) => noscope::<Port>(rl, &filter),
             Target::Netblocks(filter) => noscope::<Netblock>(rl, &filter),
             Target::CryptoAddrs(filter) => noscope::<CryptoAddr>(rl, &filter),
+            Target::Certificates(filter) => noscope::<Certificate>(rl, &filter),
+            Target::DnsRecords(filter) => noscope::<DnsRecord>(rl, &filter),
+            Target::Persons(filter) => noscope::<Person>(rl, &filter),
+            Target::Organizations(filter) => noscope::<Organization>(rl, &filter),
+            Target::Asns(filter) => noscope::<Asn>(rl, &filter),
         }?;
         term::info(&format!("Updated {} rows", rows));
         Ok(())
@@ -0,0 +1,92 @@
+use crate::errors::*;
+use crate::cmd::Cmd;
+use crate::db::Family;
+use crate::db::tags::Note;
+use crate::fmt::colors::*;
+use crate::shell::Shell;
+use crate::term;
+use clap::Parser;
+use std::fmt::Write;
+
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct Args {
+    #[command(subcommand)]
+    subcommand: Subcommand,
+}
+
+#[derive(Debug, Parser)]
+pub enum Subcommand {
+    /// Attach a note to an entity
+    #[command(name="add")]
+    Add(AddArgs),
+    /// List the notes of an entity
+    #[command(name="list")]
+    List(ListArgs),
+    /// Delete a note
+    #[command(name="delete")]
+    Delete(DeleteArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct AddArgs {
+    /// The type of the entity, eg. subdomain or port
+    family: Family,
+    /// The value of the entity
+    value: String,
+    /// The text of the note
+    #[arg(required = true)]
+    text: Vec<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListArgs {
+    /// The type of the entity, eg. subdomain or port
+    family: Family,
+    /// The value of the entity
+    value: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct DeleteArgs {
+    /// The id of the note
+    id: i32,
+}
+
+fn add(rl: &mut Shell, args: &AddArgs) -> Result<()> {
+    let db = rl.db();
+    let (table, key) = db.get_entity(&args.family, &args.value)?;
+    Note::add(db, table, key, &args.text.join(" "))?;
+    term::info(&format!("Added note to {} {:?}", args.family.as_str(), args.value));
+    Ok(())
+}
+
+fn list(rl: &mut Shell, args: &ListArgs) -> Result<()> {
+    let db = rl.db();
+    let (table, key) = db.get_entity(&args.family, &args.value)?;
+    for note in Note::for_entity(db, table, key)? {
+        let mut out = String::new();
+        Green::display(&mut out, format!("#{}", note.id))?;
+        write!(out, " {}: {}", note.time, note.body)?;
+        println!("{}", out);
+    }
+    Ok(())
+}
+
+fn delete(rl: &mut Shell, args: &DeleteArgs) -> Result<()> {
+    if !Note::delete(rl.db(), args.id)? {
+        bail!("Note not found: #{}", args.id);
+    }
+    term::info(&format!("Deleted note #{}", args.id));
+    Ok(())
+}
+
+impl Cmd for Args {
+    fn run(self, rl: &mut Shell) -> Result<()> {
+        match &self.subcommand {
+            Subcommand::Add(args) => add(rl, args),
+            Subcommand::List(args) => list(rl, args),
+            Subcommand::Delete(args) => delete(rl, args),
+        }
+    }
+}
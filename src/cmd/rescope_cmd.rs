@@ -35,6 +35,7 @@ enum Entity {
     Url(Url),
     Port(Port),
     Netblock(Netblock),
+    Asn(Asn),
 }
 
 impl Entity {
@@ -46,6 +47,7 @@ impl Entity {
             Entity::Url(entity) => entity.set_scoped(db, value),
             Entity::Port(entity) => entity.set_scoped(db, value),
             Entity::Netblock(entity) => entity.set_scoped(db, value),
+            Entity::Asn(entity) => entity.set_scoped(db, value),
         }
     }
 }
@@ -206,6 +208,11 @@ impl Cmd for Args {
                     return Ok(Some((rule.to_rule(), rule.scoped)));
                 }
             }
+            for rule in rules.asns() {
+                if rule.matches(entity)? {
+                    return Ok(Some((rule.to_rule(), rule.scoped)));
+                }
+            }
             Ok(None)
         }, Entity::IpAddr)?;
 
@@ -236,8 +243,21 @@ impl Cmd for Args {
                     return Ok(Some((rule.to_rule(), rule.scoped)));
                 }
             }
+            for rule in rules.asns() {
+                if rule.matches(entity)? {
+                    return Ok(Some((rule.to_rule(), rule.scoped)));
+                }
+            }
             Ok(None)
         }, Entity::Netblock)?;
+        rescope_to_queue::<Asn, _, _, _>(&mut ctx, rl.db(), self.interactive, |t| t.asns(), |entity| {
+            for rule in rules.asns() {
+                if rule.matches(entity)? {
+                    return Ok(Some((rule.to_rule(), rule.scoped)));
+                }
+            }
+            Ok(None)
+        }, Entity::Asn)?;
 
         if ctx.update_queue.is_empty() {
             term::success("Nothing has changed, not updating database");
@@ -4,6 +4,7 @@ use crate::args;
 use crate::blobs::{Blob, BlobStorage};
 use crate::cmd::Cmd;
 use crate::db::{ttl, Filter};
+use crate::db::provenance::Run;
 use crate::engine::Module;
 use crate::errors::*;
 use crate::ipc::common::StartCommand;
@@ -180,6 +181,11 @@ fn get_args(rl: &mut Shell, module: &Module) -> Result<Vec<(serde_json::Value, O
         Some(Source::Ports) => prepare_args::<Port>(rl, &filter, None),
         Some(Source::Netblocks) => prepare_args::<Netblock>(rl, &filter, None),
         Some(Source::CryptoAddrs(currency)) => prepare_args::<CryptoAddr>(rl, &filter, currency.as_ref()),
+        Some(Source::Certificates) => prepare_args::<Certificate>(rl, &filter, None),
+        Some(Source::DnsRecords) => prepare_args::<DnsRecord>(rl, &filter, None),
+        Some(Source::Persons) => prepare_args::<Person>(rl, &filter, None),
+        Some(Source::Organizations) => prepare_args::<Organization>(rl, &filter, None),
+        Some(Source::Asns) => prepare_args::<Asn>(rl, &filter, None),
         Some(Source::Notifications) => bail!("Notification modules can't be executed like this"),
         Some(Source::KeyRing(namespace)) => {
             let keyring = rl.keyring();
@@ -235,8 +241,15 @@ pub fn execute(rl: &mut Shell, params: Params, options: HashMap<String, String>)
     prepare_keyring(rl.keyring_mut(), &module, &params)?;
     let args = get_args(rl, &module)?;
 
+    // only record runs that could change the database
+    let run = if !args.is_empty() {
+        Some(Run::create(rl.db(), &module)?)
+    } else {
+        None
+    };
+
     rl.signal_register().catch_ctrl();
-    let errors = worker::spawn(rl, &module, &mut Ratelimiter::new(), args, &params, proxy, user_agent, options);
+    let errors = worker::spawn(rl, &module, &mut Ratelimiter::new(), args, &params, proxy, user_agent, options, run.as_ref());
     rl.signal_register().reset_ctrlc();
 
     if errors > 0 {
@@ -0,0 +1,135 @@
+use crate::errors::*;
+use crate::cmd::Cmd;
+use crate::db::provenance::{Action, Conflict, Revert, Run};
+use crate::fmt::colors::*;
+use crate::shell::Shell;
+use crate::term;
+use crate::utils;
+use clap::Parser;
+use std::fmt::Write;
+
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct Args {
+    #[command(subcommand)]
+    subcommand: Subcommand,
+}
+
+#[derive(Debug, Parser)]
+pub enum Subcommand {
+    /// List all module runs of this workspace
+    #[command(name="list")]
+    List,
+    /// Undo everything a module run added or changed
+    #[command(name="revert")]
+    Revert(RevertArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct RevertArgs {
+    /// The id of the run
+    id: i32,
+    /// Only show what would be reverted
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+    /// Don't ask for confirmation
+    #[arg(short = 'y', long)]
+    yes: bool,
+    /// Revert even if this deletes or overwrites data of other runs
+    #[arg(short = 'f', long)]
+    force: bool,
+}
+
+fn list(rl: &mut Shell) -> Result<()> {
+    let db = rl.db();
+    for run in Run::list(db)? {
+        let inserts = run.count(db, Action::Insert)?;
+        let updates = run.count(db, Action::Update)?;
+
+        let mut out = String::new();
+        Green::display(&mut out, format!("#{}", run.id))?;
+        write!(out, " {} {} at {} ({} added, {} updated)",
+            run.module, run.version, run.started, inserts, updates)?;
+        println!("{}", out);
+    }
+    Ok(())
+}
+
+fn revert(rl: &mut Shell, args: &RevertArgs) -> Result<()> {
+    let db = rl.db();
+    let run = Run::by_id(db, args.id)?;
+    let plan = run.revert_plan(db)?;
+
+    let mut deleted = 0;
+    let mut restored = 0;
+    for revert in &plan {
+        let mut out = String::new();
+        match revert {
+            Revert::Delete(family, key) => {
+                let label = db.label_generic(*family, *key)?.unwrap_or_default();
+                Red::display(&mut out, "-")?;
+                write!(out, " {} #{} {}", family.as_str(), key, label)?;
+                deleted += 1;
+            },
+            Revert::Restore(family, key, previous) => {
+                let label = db.label_generic(*family, *key)?.unwrap_or_default();
+                let fields = previous.iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Yellow::display(&mut out, "~")?;
+                write!(out, " {} #{} {} ({})", family.as_str(), key, label, fields)?;
+                restored += 1;
+            },
+        }
+        println!("{}", out);
+    }
+
+    let conflicts = run.conflicts(db, &plan)?;
+    for conflict in &conflicts {
+        let mut out = String::new();
+        match conflict {
+            Conflict::Cascade(family, key) => {
+                let label = db.label_generic(*family, *key)?.unwrap_or_default();
+                Red::display(&mut out, "!")?;
+                write!(out, " {} #{} {} (refers to a deleted entity)", family.as_str(), key, label)?;
+            },
+            Conflict::Newer(family, key, newer) => {
+                let label = db.label_generic(*family, *key)?.unwrap_or_default();
+                Red::display(&mut out, "!")?;
+                write!(out, " {} #{} {} (changed by run #{})", family.as_str(), key, label, newer)?;
+            },
+        }
+        println!("{}", out);
+    }
+
+    if args.dry_run {
+        term::info(&format!("Reverting run #{} would delete {} and restore {} entities", run.id, deleted, restored));
+        if !conflicts.is_empty() {
+            term::warn(&format!("This also affects {} entities of other runs, --force is required", conflicts.len()));
+        }
+        return Ok(());
+    }
+
+    if !conflicts.is_empty() && !args.force {
+        bail!("Reverting run #{} affects {} entities of other runs, use --force to revert anyway", run.id, conflicts.len());
+    }
+
+    let msg = format!("Revert run #{} of {} (delete {}, restore {})?", run.id, run.module, deleted, restored);
+    if !args.yes && !utils::no_else_yes(&msg)? {
+        return Ok(());
+    }
+
+    run.revert(db, &plan)?;
+    term::info(&format!("Reverted run #{} ({} deleted, {} restored)", run.id, deleted, restored));
+    Ok(())
+}
+
+impl Cmd for Args {
+    fn run(self, rl: &mut Shell) -> Result<()> {
+        match &self.subcommand {
+            Subcommand::List => list(rl),
+            Subcommand::Revert(args) => revert(rl, args),
+        }
+    }
+}
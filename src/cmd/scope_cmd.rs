@@ -29,6 +29,11 @@ impl Cmd for Args {
             Target::Ports(filter) => scope::<Port>(rl, &filter),
             Target::Netblocks(filter) => scope::<Netblock>(rl, &filter),
             Target::CryptoAddrs(filter) => scope::<CryptoAddr>(rl, &filter),
+            Target::Certificates(filter) => scope::<Certificate>(rl, &filter),
+            Target::DnsRecords(filter) => scope::<DnsRecord>(rl, &filter),
+            Target::Persons(filter) => scope::<Person>(rl, &filter),
+            Target::Organizations(filter) => scope::<Organization>(rl, &filter),
+            Target::Asns(filter) => scope::<Asn>(rl, &filter),
         }?;
         term::info(&format!("Updated {} rows", rows));
         Ok(())
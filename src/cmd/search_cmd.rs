@@ -0,0 +1,61 @@
+use crate::errors::*;
+use crate::cmd::Cmd;
+use crate::db::search;
+use crate::fmt::colors::*;
+use crate::shell::Shell;
+use crate::term;
+use clap::Parser;
+use std::fmt::Write;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The maximum number of results for each type of entity
+    #[arg(long, default_value = "10")]
+    limit: i64,
+    /// Print the results as json
+    #[arg(long)]
+    json: bool,
+    /// The text to search for, every word needs to match
+    #[arg(required = true)]
+    text: Vec<String>,
+}
+
+impl Cmd for Args {
+    fn run(self, rl: &mut Shell) -> Result<()> {
+        let text = self.text.join(" ");
+
+        if self.json {
+            for group in search::search(rl.db(), &text, self.limit, ("", ""))? {
+                println!("{}", serde_json::to_string(&group)?);
+            }
+            return Ok(());
+        }
+
+        let groups = search::search(rl.db(), &text, self.limit, ("\x1b[1m", "\x1b[0m"))?;
+        if groups.is_empty() {
+            term::info(&format!("No results for {:?}", text));
+        }
+
+        for group in groups {
+            let mut out = String::new();
+            Green::display(&mut out, group.family)?;
+            write!(out, " ({})", group.hits.len())?;
+            println!("{}", out);
+
+            for hit in &group.hits {
+                println!("    #{}, {:?}: {}", hit.id, hit.label, hit.snippet);
+            }
+
+            // activity isn't an entity and can't be selected
+            if group.family != "activity" {
+                let ids = group.hits.iter()
+                    .map(|hit| hit.id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!("    \x1b[90mselect {} where id in ({})\x1b[0m", group.family, ids);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,18 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use crate::cmd::Cmd;
-use crate::db::ttl;
+use crate::db::{filter, ttl, Order, Table};
+use crate::db::provenance::Provenance;
+use crate::db::tags::Annotations;
 use crate::errors::*;
 use crate::filters::{Target, Filter};
+use crate::fmt::colors::*;
 use crate::models::*;
 use crate::shell::Shell;
 use serde::Serialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
 
 #[derive(Debug, Parser)]
 pub struct Args {
@@ -23,6 +30,40 @@ pub struct Args {
     /// Count rows returned
     #[structopt(short = 'c', group="output")]
     count: bool,
+    /// Only print these columns, separated by commas
+    #[arg(long, value_delimiter=',', group="output")]
+    columns: Vec<String>,
+    /// The format that is used for --columns and --group-by
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    /// Count the rows for every value of this column
+    #[arg(long, conflicts_with_all=["values", "paths", "count", "columns"])]
+    group_by: Option<String>,
+    /// List the distinct values of this column for every group
+    #[arg(long, requires="group_by")]
+    distinct: Option<String>,
+    /// Sort rows by this column
+    #[arg(long, conflicts_with="group_by")]
+    order_by: Option<String>,
+    /// Sort in descending order
+    #[arg(long, requires="order_by")]
+    desc: bool,
+    /// Only return this many rows
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Skip this many rows
+    #[arg(long)]
+    offset: Option<usize>,
+    /// Only select entities that have been added or updated by this module
+    #[arg(long)]
+    by_module: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    Table,
+    Csv,
+    Tsv,
 }
 
 #[derive(PartialEq)]
@@ -32,11 +73,161 @@ enum Output {
     Values,
     Paths,
     Count,
+    Columns(Vec<String>),
+}
+
+/// Order nulls first, then by the json type of the value
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Less,
+        (_, Value::Null) => Ordering::Greater,
+        (Value::Number(a), Value::Number(b)) => {
+            a.as_f64().partial_cmp(&b.as_f64())
+                .unwrap_or(Ordering::Equal)
+        },
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (a, b) => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn escape_csv(s: &str) -> Cow<str> {
+    if s.contains(|c: char| matches!(c, ',' | '"' | '\n' | '\r')) {
+        Cow::Owned(format!("\"{}\"", s.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+fn escape_tsv(s: &str) -> Cow<str> {
+    if s.contains(|c: char| matches!(c, '\\' | '\t' | '\n' | '\r')) {
+        Cow::Owned(s.replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r"))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+fn print_rows(columns: &[String], rows: &[Vec<String>], format: Format) {
+    match format {
+        Format::Table => {
+            let mut widths = columns.iter()
+                .map(|c| c.chars().count())
+                .collect::<Vec<_>>();
+            for row in rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+
+            let line = |row: &[String]| {
+                let line = row.iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:1$}", cell, width))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                println!("{}", line.trim_end());
+            };
+
+            line(columns);
+            for row in rows {
+                line(&row[..]);
+            }
+        },
+        Format::Csv | Format::Tsv => {
+            let (escape, sep): (fn(&str) -> Cow<str>, _) = if format == Format::Csv {
+                (escape_csv, ",")
+            } else {
+                (escape_tsv, "\t")
+            };
+
+            for row in Some(columns).into_iter().chain(rows.iter().map(Vec::as_slice)) {
+                let line = row.iter()
+                    .map(|x| escape(x))
+                    .collect::<Vec<_>>()
+                    .join(sep);
+                println!("{}", line);
+            }
+        },
+    }
+}
+
+/// The rows that share the same value in the grouped column
+#[derive(Debug, PartialEq)]
+struct Group {
+    key: Value,
+    count: usize,
+    values: BTreeSet<String>,
+}
+
+/// Group rows by a column, the largest groups are sorted first
+fn aggregate<T: Serialize>(table: Table, rows: Vec<T>, column: &str, distinct: Option<&str>) -> Result<Vec<Group>> {
+    filter::validate_column(table, column)?;
+    if let Some(distinct) = distinct {
+        filter::validate_column(table, distinct)?;
+    }
+
+    let mut index = HashMap::new();
+    let mut groups = Vec::<Group>::new();
+    for row in rows {
+        let row = serde_json::to_value(&row)?;
+        let key = row.get(column).cloned().unwrap_or(Value::Null);
+
+        let idx = *index.entry(key.to_string()).or_insert_with(|| {
+            groups.push(Group {
+                key,
+                count: 0,
+                values: BTreeSet::new(),
+            });
+            groups.len() - 1
+        });
+
+        let group = &mut groups[idx];
+        group.count += 1;
+        if let Some(distinct) = distinct {
+            match row.get(distinct) {
+                Some(Value::Null) | None => (),
+                Some(value) => {
+                    group.values.insert(cell(value));
+                },
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| b.count.cmp(&a.count)
+        .then_with(|| compare(&a.key, &b.key)));
+    Ok(groups)
+}
+
+/// Only keep the groups that fall into --offset and --limit
+fn slice<T>(order: &Order, mut rows: Vec<T>) -> Vec<T> {
+    if let Some(offset) = order.offset {
+        rows.drain(..offset.min(rows.len()));
+    }
+    if let Some(limit) = order.limit {
+        rows.truncate(limit);
+    }
+    rows
 }
 
 struct Printer<'a, 'b> {
     rl: &'a mut Shell<'b>,
     output: Output,
+    format: Format,
+    order: Order,
+    group_by: Option<(String, Option<String>)>,
+    by_module: Option<String>,
 }
 
 impl<'a, 'b> Printer<'a, 'b> {
@@ -49,25 +240,85 @@ impl<'a, 'b> Printer<'a, 'b> {
             Output::Paths
         } else if args.count {
             Output::Count
+        } else if !args.columns.is_empty() {
+            Output::Columns(args.columns.clone())
         } else {
             Output::Normal
         };
 
+        let order = Order {
+            column: args.order_by.clone(),
+            desc: args.desc,
+            limit: args.limit,
+            offset: args.offset,
+        };
+
+        let group_by = args.group_by.clone()
+            .map(|column| (column, args.distinct.clone()));
+
         Printer {
             rl,
             output,
+            format: args.format.unwrap_or(Format::Table),
+            order,
+            group_by,
+            by_module: args.by_module.clone(),
         }
     }
 
-    pub fn select<T: Model + Detailed + Serialize>(&self, filter: &Filter) -> Result<()> {
-        let query = self.rl.db().filter::<T>(&filter.parse_optional()?)?;
+    pub fn select<T: Sortable + Detailed + Serialize>(&self, table: Table, filter: &Filter) -> Result<()> {
+        let mut filter = filter.parse_optional()?;
+        if let Some(module) = &self.by_module {
+            filter = filter.and_module(module);
+        }
+
+        if let Some((column, distinct)) = &self.group_by {
+            let query = self.rl.db().filter::<T>(&filter)?;
+            let groups = aggregate(table, query, column, distinct.as_deref())?;
+            let groups = slice(&self.order, groups);
+            return self.print_groups(column, distinct.as_deref(), groups);
+        }
+
+        let query = self.rl.db().filter_ordered::<T>(&filter, &self.order)?;
 
         if self.output == Output::Count {
             println!("{}", query.len());
+        } else if let Output::Columns(columns) = &self.output {
+            for column in columns {
+                filter::validate_column(table, column)?;
+            }
+
+            let mut rows = Vec::with_capacity(query.len());
+            for obj in query {
+                let obj = serde_json::to_value(&obj)?;
+                let row = columns.iter()
+                    .map(|c| obj.get(c).map(cell).unwrap_or_default())
+                    .collect();
+                rows.push(row);
+            }
+
+            print_rows(columns, &rows, self.format);
         } else {
+            let mut provenance = if self.output == Output::Normal {
+                let ids = query.iter()
+                    .map(|obj| obj.id())
+                    .collect::<Vec<_>>();
+                Provenance::list_many(self.rl.db(), table, &ids)?
+            } else {
+                HashMap::new()
+            };
+
             for obj in query {
                 match self.output {
-                    Output::Normal => println!("{}", obj.detailed(self.rl.db())?),
+                    Output::Normal => {
+                        let annotations = Annotations::load(self.rl.db(), table, obj.id())?;
+                        println!("{}{}", obj.detailed(self.rl.db())?, annotations);
+                        for (change, run) in provenance.remove(&obj.id()).unwrap_or_default() {
+                            let mut out = String::from("\t");
+                            Grey::display(&mut out, change.to_term_str(&run))?;
+                            println!("{}", out);
+                        }
+                    },
                     Output::Json => {
                         let v = serde_json::to_string(&obj)?;
                         println!("{}", v);
@@ -87,13 +338,43 @@ impl<'a, 'b> Printer<'a, 'b> {
 
                         println!("{}", path);
                     },
-                    Output::Count => unreachable!(),
+                    Output::Count | Output::Columns(..) => unreachable!(),
                 }
             }
         }
 
         Ok(())
     }
+
+    fn print_groups(&self, column: &str, distinct: Option<&str>, groups: Vec<Group>) -> Result<()> {
+        if self.output == Output::Json {
+            for group in groups {
+                let mut obj = serde_json::Map::new();
+                obj.insert(column.to_string(), group.key);
+                obj.insert("count".to_string(), group.count.into());
+                if let Some(distinct) = distinct {
+                    obj.insert(distinct.to_string(), group.values.into_iter().collect());
+                }
+                println!("{}", serde_json::to_string(&obj)?);
+            }
+        } else {
+            let mut columns = vec![column.to_string(), "count".to_string()];
+            columns.extend(distinct.map(String::from));
+
+            let rows = groups.into_iter()
+                .map(|group| {
+                    let mut row = vec![cell(&group.key), group.count.to_string()];
+                    if distinct.is_some() {
+                        row.push(group.values.into_iter().collect::<Vec<_>>().join(", "));
+                    }
+                    row
+                })
+                .collect::<Vec<_>>();
+
+            print_rows(&columns, &rows, self.format);
+        }
+        Ok(())
+    }
 }
 
 impl Cmd for Args {
@@ -102,20 +383,86 @@ impl Cmd for Args {
         let printer = Printer::new(rl, &self);
 
         match &self.subcommand {
-            Target::Domains(filter) => printer.select::<Domain>(filter),
-            Target::Subdomains(filter) => printer.select::<Subdomain>(filter),
-            Target::IpAddrs(filter) => printer.select::<IpAddr>(filter),
-            Target::Urls(filter) => printer.select::<Url>(filter),
-            Target::Emails(filter) => printer.select::<Email>(filter),
-            Target::PhoneNumbers(filter) => printer.select::<PhoneNumber>(filter),
-            Target::Devices(filter) => printer.select::<Device>(filter),
-            Target::Networks(filter) => printer.select::<Network>(filter),
-            Target::Accounts(filter) => printer.select::<Account>(filter),
-            Target::Breaches(filter) => printer.select::<Breach>(filter),
-            Target::Images(filter) => printer.select::<Image>(filter),
-            Target::Ports(filter) => printer.select::<Port>(filter),
-            Target::Netblocks(filter) => printer.select::<Netblock>(filter),
-            Target::CryptoAddrs(filter) => printer.select::<CryptoAddr>(filter),
+            Target::Domains(filter) => printer.select::<Domain>(Table::Domains, filter),
+            Target::Subdomains(filter) => printer.select::<Subdomain>(Table::Subdomains, filter),
+            Target::IpAddrs(filter) => printer.select::<IpAddr>(Table::Ipaddrs, filter),
+            Target::Urls(filter) => printer.select::<Url>(Table::Urls, filter),
+            Target::Emails(filter) => printer.select::<Email>(Table::Emails, filter),
+            Target::PhoneNumbers(filter) => printer.select::<PhoneNumber>(Table::Phonenumbers, filter),
+            Target::Devices(filter) => printer.select::<Device>(Table::Devices, filter),
+            Target::Networks(filter) => printer.select::<Network>(Table::Networks, filter),
+            Target::Accounts(filter) => printer.select::<Account>(Table::Accounts, filter),
+            Target::Breaches(filter) => printer.select::<Breach>(Table::Breaches, filter),
+            Target::Images(filter) => printer.select::<Image>(Table::Images, filter),
+            Target::Ports(filter) => printer.select::<Port>(Table::Ports, filter),
+            Target::Netblocks(filter) => printer.select::<Netblock>(Table::Netblocks, filter),
+            Target::CryptoAddrs(filter) => printer.select::<CryptoAddr>(Table::Cryptoaddrs, filter),
+            Target::Certificates(filter) => printer.select::<Certificate>(Table::Certificates, filter),
+            Target::DnsRecords(filter) => printer.select::<DnsRecord>(Table::DnsRecords, filter),
+            Target::Persons(filter) => printer.select::<Person>(Table::Persons, filter),
+            Target::Organizations(filter) => printer.select::<Organization>(Table::Organizations, filter),
+            Target::Asns(filter) => printer.select::<Asn>(Table::Asns, filter),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_order_nulls_first() {
+        let mut values = vec![json!("b"), json!(null), json!("a")];
+        values.sort_by(compare);
+        assert_eq!(values, vec![json!(null), json!("a"), json!("b")]);
+    }
+
+    #[test]
+    fn test_order_numbers() {
+        let mut values = vec![json!(443), json!(80), json!(8080)];
+        values.sort_by(compare);
+        assert_eq!(values, vec![json!(80), json!(443), json!(8080)]);
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let rows = vec![
+            json!({"value": "192.0.2.1", "asn": 13335}),
+            json!({"value": "192.0.2.2", "asn": null}),
+            json!({"value": "192.0.2.3", "asn": 13335}),
+            json!({"value": "192.0.2.3", "asn": 13335}),
+        ];
+        let groups = aggregate(Table::Ipaddrs, rows, "asn", Some("value")).unwrap();
+        assert_eq!(groups, vec![
+            Group {
+                key: json!(13335),
+                count: 3,
+                values: vec!["192.0.2.1".to_string(), "192.0.2.3".to_string()].into_iter().collect(),
+            },
+            Group {
+                key: json!(null),
+                count: 1,
+                values: vec!["192.0.2.2".to_string()].into_iter().collect(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_aggregate_unknown_column() {
+        let rows = vec![json!({"value": "192.0.2.1"})];
+        assert!(aggregate(Table::Ipaddrs, rows, "vlaue", None).is_err());
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv("example.com"), "example.com");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_escape_tsv() {
+        assert_eq!(escape_tsv("a\tb\nc\\d"), "a\\tb\\nc\\\\d");
+    }
+}
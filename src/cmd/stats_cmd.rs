@@ -71,6 +71,11 @@ struct Stats {
     ports: usize,
     netblocks: usize,
     cryptoaddrs: usize,
+    certificates: usize,
+    dns_records: usize,
+    persons: usize,
+    organizations: usize,
+    asns: usize,
     activity: usize,
     blobs: Option<BlobStats>,
 }
@@ -100,6 +105,11 @@ impl Stats {
             ports: count_models::<Port>(db)?,
             netblocks: count_models::<Netblock>(db)?,
             cryptoaddrs: count_models::<CryptoAddr>(db)?,
+            certificates: count_models::<Certificate>(db)?,
+            dns_records: count_models::<DnsRecord>(db)?,
+            persons: count_models::<Person>(db)?,
+            organizations: count_models::<Organization>(db)?,
+            asns: count_models::<Asn>(db)?,
             activity: Activity::count(db)?,
             blobs: None,
         })
@@ -181,6 +191,11 @@ impl Cmd for Args {
                 self.show_count("ports", stats.ports);
                 self.show_count("netblocks", stats.netblocks);
                 self.show_count("cryptoaddrs", stats.cryptoaddrs);
+                self.show_count("certificates", stats.certificates);
+                self.show_count("dns_records", stats.dns_records);
+                self.show_count("persons", stats.persons);
+                self.show_count("organizations", stats.organizations);
+                self.show_count("asns", stats.asns);
                 self.show_count("activity", stats.activity);
 
                 if let Some(blobs) = stats.blobs {
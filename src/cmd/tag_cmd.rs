@@ -0,0 +1,103 @@
+use crate::errors::*;
+use crate::cmd::Cmd;
+use crate::db::Family;
+use crate::db::tags::Tag;
+use crate::fmt::colors::*;
+use crate::shell::Shell;
+use crate::term;
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+#[group(skip)]
+pub struct Args {
+    #[command(subcommand)]
+    subcommand: Subcommand,
+}
+
+#[derive(Debug, Parser)]
+pub enum Subcommand {
+    /// Add tags to an entity
+    #[command(name="add")]
+    Add(TagArgs),
+    /// Remove tags from an entity
+    #[command(name="remove")]
+    Remove(TagArgs),
+    /// List the tags of an entity, or all tags that are in use
+    #[command(name="list")]
+    List(ListArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct TagArgs {
+    /// The type of the entity, eg. subdomain or port
+    family: Family,
+    /// The value of the entity
+    value: String,
+    /// The tags, eg. phishing-infra
+    #[arg(required = true)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListArgs {
+    /// The type of the entity, eg. subdomain or port
+    #[arg(requires = "value")]
+    family: Option<Family>,
+    /// The value of the entity
+    value: Option<String>,
+}
+
+fn add(rl: &mut Shell, args: &TagArgs) -> Result<()> {
+    let db = rl.db();
+    let (table, key) = db.get_entity(&args.family, &args.value)?;
+
+    let mut added = 0;
+    for tag in &args.tags {
+        if Tag::add(db, table, key, tag)? {
+            added += 1;
+        }
+    }
+    term::info(&format!("Added {} tags to {} {:?}", added, args.family.as_str(), args.value));
+    Ok(())
+}
+
+fn remove(rl: &mut Shell, args: &TagArgs) -> Result<()> {
+    let db = rl.db();
+    let (table, key) = db.get_entity(&args.family, &args.value)?;
+
+    let mut removed = 0;
+    for tag in &args.tags {
+        if Tag::remove(db, table, key, tag)? {
+            removed += 1;
+        }
+    }
+    term::info(&format!("Removed {} tags from {} {:?}", removed, args.family.as_str(), args.value));
+    Ok(())
+}
+
+fn list(rl: &mut Shell, args: &ListArgs) -> Result<()> {
+    let db = rl.db();
+    if let (Some(family), Some(value)) = (&args.family, &args.value) {
+        let (table, key) = db.get_entity(family, value)?;
+        for tag in Tag::names(db, table, key)? {
+            println!("{}", tag);
+        }
+    } else {
+        for (tag, count) in Tag::count(db)? {
+            let mut out = String::new();
+            Green::display(&mut out, &tag)?;
+            println!("{} ({})", out, count);
+        }
+    }
+    Ok(())
+}
+
+impl Cmd for Args {
+    fn run(self, rl: &mut Shell) -> Result<()> {
+        match &self.subcommand {
+            Subcommand::Add(args) => add(rl, args),
+            Subcommand::Remove(args) => remove(rl, args),
+            Subcommand::List(args) => list(rl, args),
+        }
+    }
+}
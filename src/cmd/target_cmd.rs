@@ -1,6 +1,7 @@
 use crate::errors::*;
 
-use crate::db;
+use crate::db::{self, Table};
+use crate::db::tags::Annotations;
 use crate::shell::Shell;
 use sn0int_common::metadata::Source;
 use clap::Parser;
@@ -28,20 +29,25 @@ pub fn run(rl: &mut Shell, args: &[String]) -> Result<()> {
 
     if args.filter.is_empty() {
         match source {
-            Source::Domains => select::<Domain>(rl, None)?,
-            Source::Subdomains => select::<Subdomain>(rl, None)?,
-            Source::IpAddrs => select::<IpAddr>(rl, None)?,
-            Source::Urls => select::<Url>(rl, None)?,
-            Source::Emails => select::<Email>(rl, None)?,
-            Source::PhoneNumbers => select::<PhoneNumber>(rl, None)?,
-            Source::Networks => select::<Network>(rl, None)?,
-            Source::Devices => select::<Device>(rl, None)?,
-            Source::Accounts(service) => select::<Account>(rl, service.as_ref())?,
-            Source::Breaches => select::<Breach>(rl, None)?,
-            Source::Images => select::<Image>(rl, None)?,
-            Source::Ports => select::<Port>(rl, None)?,
-            Source::Netblocks => select::<Netblock>(rl, None)?,
-            Source::CryptoAddrs(currency) => select::<CryptoAddr>(rl, currency.as_ref())?,
+            Source::Domains => select::<Domain>(rl, Table::Domains, None)?,
+            Source::Subdomains => select::<Subdomain>(rl, Table::Subdomains, None)?,
+            Source::IpAddrs => select::<IpAddr>(rl, Table::Ipaddrs, None)?,
+            Source::Urls => select::<Url>(rl, Table::Urls, None)?,
+            Source::Emails => select::<Email>(rl, Table::Emails, None)?,
+            Source::PhoneNumbers => select::<PhoneNumber>(rl, Table::Phonenumbers, None)?,
+            Source::Networks => select::<Network>(rl, Table::Networks, None)?,
+            Source::Devices => select::<Device>(rl, Table::Devices, None)?,
+            Source::Accounts(service) => select::<Account>(rl, Table::Accounts, service.as_ref())?,
+            Source::Breaches => select::<Breach>(rl, Table::Breaches, None)?,
+            Source::Images => select::<Image>(rl, Table::Images, None)?,
+            Source::Ports => select::<Port>(rl, Table::Ports, None)?,
+            Source::Netblocks => select::<Netblock>(rl, Table::Netblocks, None)?,
+            Source::CryptoAddrs(currency) => select::<CryptoAddr>(rl, Table::Cryptoaddrs, currency.as_ref())?,
+            Source::Certificates => select::<Certificate>(rl, Table::Certificates, None)?,
+            Source::DnsRecords => select::<DnsRecord>(rl, Table::DnsRecords, None)?,
+            Source::Persons => select::<Person>(rl, Table::Persons, None)?,
+            Source::Organizations => select::<Organization>(rl, Table::Organizations, None)?,
+            Source::Asns => select::<Asn>(rl, Table::Asns, None)?,
             Source::Notifications => bail!("Notifications can't be set as target"),
             Source::KeyRing(namespace) => {
                 for key in rl.keyring().list_for(&namespace) {
@@ -78,17 +84,23 @@ fn count_selected(rl: &mut Shell, source: &Source) -> Result<usize> {
         Source::Ports => db.filter::<Port>(&filter)?.len(),
         Source::Netblocks => db.filter::<Netblock>(&filter)?.len(),
         Source::CryptoAddrs(currency) => db.filter_with_param::<CryptoAddr>(&filter, currency.as_ref())?.len(),
+        Source::Certificates => db.filter::<Certificate>(&filter)?.len(),
+        Source::DnsRecords => db.filter::<DnsRecord>(&filter)?.len(),
+        Source::Persons => db.filter::<Person>(&filter)?.len(),
+        Source::Organizations => db.filter::<Organization>(&filter)?.len(),
+        Source::Asns => db.filter::<Asn>(&filter)?.len(),
         Source::Notifications => bail!("Notifications can't be set as target"),
         Source::KeyRing(namespace) => rl.keyring().list_for(namespace).len(),
     };
     Ok(num)
 }
 
-fn select<T: Model + Detailed>(rl: &mut Shell, param: Option<&String>) -> Result<()> {
+fn select<T: Model + Detailed>(rl: &mut Shell, table: Table, param: Option<&String>) -> Result<()> {
     let filter = rl.scoped_targets();
 
     for obj in rl.db().filter_with_param::<T>(&filter, param)? {
-        println!("{}", obj.detailed(rl.db())?);
+        let annotations = Annotations::load(rl.db(), table, obj.id())?;
+        println!("{}{}", obj.detailed(rl.db())?, annotations);
     }
 
     Ok(())
@@ -1,16 +1,26 @@
 use clap::Parser;
+use crate::autonoscope::RuleType;
 use crate::blobs::BlobStorage;
 use crate::cmd::{Cmd, LiteCmd};
+use crate::cmd::export_cmd::JsonFormat;
+use crate::cmd::import_cmd::{Importer, ScopePolicy};
 use crate::config::Config;
 use crate::db::Database;
+use crate::db::snapshot::SnapshotCopy;
+use crate::diff::{ChangeKind, Diff};
 use crate::errors::*;
 use crate::shell::Shell;
 use crate::term;
 use crate::utils;
 use crate::workspaces::{self, Workspace};
+use std::collections::HashSet;
+use std::ops::Deref;
 
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Args {
+    #[command(subcommand)]
+    subcommand: Option<Subcommand>,
     /// Delete a workspaceb
     #[arg(long = "delete", group = "action")]
     delete: bool,
@@ -23,6 +33,70 @@ pub struct Args {
     workspaces: Vec<Workspace>,
 }
 
+impl Args {
+    /// Subcommands operate on the selected workspace and need a database
+    #[inline]
+    pub fn requires_workspace(&self) -> bool {
+        self.subcommand.is_some()
+    }
+}
+
+#[derive(Debug, Parser)]
+pub enum Subcommand {
+    /// Take a snapshot of the current workspace
+    #[command(name="snapshot")]
+    Snapshot(Snapshot),
+    /// List the snapshots of the current workspace
+    #[command(name="snapshots")]
+    Snapshots,
+    /// Roll the current workspace back to a snapshot
+    #[command(name="restore")]
+    Restore(Restore),
+    /// Compare two workspaces or snapshots
+    #[command(name="diff")]
+    Diff(DiffArgs),
+    /// Merge the entities of a workspace into another one
+    #[command(name="merge")]
+    Merge(Merge),
+}
+
+#[derive(Debug, Parser)]
+pub struct Snapshot {
+    name: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct Restore {
+    snapshot: String,
+    /// Skip confirmation
+    #[arg(short = 'f', long = "force")]
+    force: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct DiffArgs {
+    /// The workspace to compare against, use `workspace@snapshot` or
+    /// `@snapshot` for snapshots
+    a: String,
+    /// The workspace that is compared, same syntax as the first argument
+    b: String,
+    /// Print the changes as json
+    #[arg(long = "json")]
+    json: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct Merge {
+    /// The workspace that is merged
+    src: Workspace,
+    /// The workspace to merge into, defaults to the current workspace
+    #[arg(long = "into")]
+    into: Option<Workspace>,
+    /// Decide the scope of entities that exist in both workspaces
+    #[arg(long = "scope", value_enum, default_value = "keep-dst")]
+    scope: ScopePolicy,
+}
+
 fn delete(workspace: Workspace, force: bool) -> Result<()> {
     if !force && !utils::no_else_yes(&format!("Do you really want to delete {:?}", workspace.as_str()))? {
         return Ok(());
@@ -56,6 +130,175 @@ fn change(rl: &mut Shell, workspace: Workspace) -> Result<()> {
     Ok(())
 }
 
+fn snapshot(rl: &mut Shell, args: Snapshot) -> Result<()> {
+    let snapshot = rl.db().snapshot(&args.name)?;
+    term::info(&format!("Created snapshot {:?} ({})", snapshot.name(), snapshot.usage_human()?));
+    Ok(())
+}
+
+fn snapshots(rl: &mut Shell) -> Result<()> {
+    for snapshot in rl.db().workspace().snapshots()? {
+        let created = snapshot.created()?
+            .format("%Y-%m-%d %H:%M:%S");
+        println!("{:30} {} {:>10}", snapshot.name(), created, snapshot.usage_human()?);
+    }
+    Ok(())
+}
+
+fn restore(rl: &mut Shell, args: Restore) -> Result<()> {
+    let snapshot = rl.db().workspace().snapshot(&args.snapshot)?;
+
+    if !args.force && !utils::no_else_yes(&format!("Do you really want to roll back to {:?}, all changes since are lost", snapshot.name()))? {
+        return Ok(());
+    }
+
+    term::info(&format!("Restoring snapshot: {:?}", snapshot.name()));
+    rl.db_mut().restore(&snapshot)?;
+
+    Ok(())
+}
+
+enum DiffTarget {
+    Workspace(Database),
+    Snapshot(SnapshotCopy),
+}
+
+impl Deref for DiffTarget {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        match self {
+            DiffTarget::Workspace(db) => db,
+            DiffTarget::Snapshot(db) => db,
+        }
+    }
+}
+
+/// Open a workspace, `workspace@snapshot` or `@snapshot` of the current workspace
+fn open_target(rl: &Shell, target: &str) -> Result<DiffTarget> {
+    let (workspace, snapshot) = match target.split_once('@') {
+        Some(("", snapshot)) => (rl.db().workspace().clone(), Some(snapshot)),
+        Some((workspace, snapshot)) => (workspace.parse()?, Some(snapshot)),
+        None => (target.parse()?, None),
+    };
+
+    if !workspaces::list()?.contains(&workspace) {
+        bail!("Workspace doesn't exist: {:?}", workspace.as_str());
+    }
+
+    if let Some(snapshot) = snapshot {
+        // diffs are read-only, the snapshot itself isn't migrated
+        let snapshot = workspace.snapshot(snapshot)?;
+        Database::open_snapshot_copy(&snapshot).map(DiffTarget::Snapshot)
+    } else {
+        Database::establish_quiet(workspace).map(DiffTarget::Workspace)
+    }
+}
+
+fn diff(rl: &mut Shell, args: DiffArgs) -> Result<()> {
+    let a = open_target(rl, &args.a)
+        .context(format!("Failed to open {:?}", args.a))?;
+    let b = open_target(rl, &args.b)
+        .context(format!("Failed to open {:?}", args.b))?;
+    let diff = Diff::compare(&a, &b)?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&diff.changes)?;
+        println!("{}", json);
+    } else {
+        for change in &diff.changes {
+            println!("{}", change.to_term_str()?);
+        }
+        term::info(&format!("{} added, {} removed, {} changed",
+            diff.count(ChangeKind::Added),
+            diff.count(ChangeKind::Removed),
+            diff.count(ChangeKind::Changed)));
+    }
+
+    Ok(())
+}
+
+fn merge(rl: &mut Shell, args: Merge) -> Result<()> {
+    let current = rl.db().workspace().clone();
+    let dst = args.into.unwrap_or_else(|| current.clone());
+    if args.src == dst {
+        bail!("Can't merge a workspace into itself");
+    }
+    if !workspaces::list()?.contains(&args.src) {
+        bail!("Workspace doesn't exist: {:?}", args.src.as_str());
+    }
+
+    let src = Database::establish_quiet(args.src.clone())
+        .context("Failed to open source workspace")?;
+    let export = JsonFormat::from_db(&src)?;
+
+    let mut other;
+    let db = if dst == current {
+        rl.db_mut()
+    } else {
+        dst.migrate()?;
+        other = Database::establish_quiet(dst.clone())?;
+        &mut other
+    };
+
+    // nothing is merged if any part of the import fails
+    let (stats, rules) = db.transaction(|db| {
+        // rules that already exist in the destination take precedence
+        let existing = db.autonoscope_rules()
+            .into_iter()
+            .map(|(object, value, _)| (object.to_string(), value))
+            .collect::<HashSet<_>>();
+        let mut rules = 0;
+        for rule in &export.autonoscope {
+            if existing.contains(&(rule.object.clone(), rule.value.clone())) {
+                continue;
+            }
+            let object = rule.object.parse::<RuleType>()?;
+            db.autonoscope_add_rule(&object, &rule.value, rule.scoped)?;
+            rules += 1;
+        }
+
+        let mut importer = Importer::new(db, args.scope);
+        importer.import(export)?;
+        Ok((importer.stats, rules))
+    })?;
+
+    let src_blobs = BlobStorage::workspace(&args.src)?;
+    let dst_blobs = BlobStorage::workspace(&dst)?;
+    let existing = dst_blobs.list()?
+        .into_iter()
+        .collect::<HashSet<_>>();
+    let mut blobs = 0;
+    for id in src_blobs.list()? {
+        if !existing.contains(&id) {
+            dst_blobs.save(&src_blobs.load(&id)?)?;
+            blobs += 1;
+        }
+    }
+
+    term::info(&format!("Merged {} entities into {:?} ({} new, {} updated, {} rescoped), {} events, {} autonoscope rules and {} blobs",
+        stats.total,
+        dst.as_str(),
+        stats.inserted,
+        stats.updated,
+        stats.rescoped,
+        stats.activity,
+        rules,
+        blobs));
+
+    Ok(())
+}
+
+fn run_subcommand(rl: &mut Shell, subcommand: Subcommand) -> Result<()> {
+    match subcommand {
+        Subcommand::Snapshot(args) => snapshot(rl, args),
+        Subcommand::Snapshots => snapshots(rl),
+        Subcommand::Restore(args) => restore(rl, args),
+        Subcommand::Diff(args) => diff(rl, args),
+        Subcommand::Merge(args) => merge(rl, args),
+    }
+}
+
 fn list() -> Result<()> {
     for ws in workspaces::list()? {
         println!("{}", ws.as_str());
@@ -64,6 +307,11 @@ fn list() -> Result<()> {
 }
 
 fn run(mut args: Args, rl: Option<&mut Shell>) -> Result<()> {
+    if let Some(subcommand) = args.subcommand {
+        let rl = rl.ok_or_else(|| format_err!("This subcommand requires a workspace"))?;
+        return run_subcommand(rl, subcommand);
+    }
+
     if args.delete {
         if args.workspaces.is_empty() {
             bail!("--delete requires workspace");
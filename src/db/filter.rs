@@ -0,0 +1,1449 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use crate::errors::*;
+use crate::schema::*;
+use diesel::dsl::sql;
+use diesel::expression::{AppearsOnTable, Expression, NonAggregate, SelectableExpression};
+use diesel::prelude::*;
+use diesel::query_builder::{AstPass, BoxedSelectStatement, QueryFragment, QueryId};
+use diesel::sql_types::{self, NotNull, Nullable};
+use diesel::sqlite::Sqlite;
+use ipnetwork::IpNetwork;
+use std::fmt;
+use std::net;
+use super::Table;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+}
+
+impl Op {
+    fn parse(s: &str) -> Option<Op> {
+        match s {
+            "=" | "==" => Some(Op::Eq),
+            "!=" | "<>" => Some(Op::Ne),
+            "<" => Some(Op::Lt),
+            ">" => Some(Op::Gt),
+            "<=" => Some(Op::Le),
+            ">=" => Some(Op::Ge),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Op::Eq => "=",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Gt => ">",
+            Op::Le => "<=",
+            Op::Ge => ">=",
+        }
+    }
+
+    #[inline]
+    fn is_equality(&self) -> bool {
+        matches!(self, Op::Eq | Op::Ne)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    LParen,
+    RParen,
+    Comma,
+    Op(Op),
+    Word(String),
+}
+
+impl fmt::Display for Tok {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tok::LParen => write!(w, "'('"),
+            Tok::RParen => write!(w, "')'"),
+            Tok::Comma => write!(w, "','"),
+            Tok::Op(op) => write!(w, "'{}'", op.as_str()),
+            Tok::Word(word) => write!(w, "{:?}", word),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    tok: Tok,
+    pos: usize,
+}
+
+impl Token {
+    #[inline]
+    fn new(tok: Tok, pos: usize) -> Token {
+        Token { tok, pos }
+    }
+
+    #[inline]
+    fn word(word: &str, pos: usize) -> Token {
+        Token::new(Tok::Word(word.to_string()), pos)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match &self.tok {
+            Tok::Word(word) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+}
+
+/// How the next argument is going to be split into tokens
+enum Mode {
+    Normal,
+    /// The argument is taken as is, this is used after operators
+    Value,
+    /// A list of values, split by commas and terminated by a closing parenthesis
+    List,
+}
+
+/// Split the arguments into tokens, the arguments have already been split by
+/// the shell, so values don't need to be quoted unless they contain whitespace.
+fn lex(args: &[(usize, &str)]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut mode = Mode::Normal;
+    for &(pos, arg) in args {
+        mode = match mode {
+            Mode::Normal => lex_normal(&mut tokens, arg, pos),
+            Mode::Value => {
+                lex_value(&mut tokens, arg, pos);
+                Mode::Normal
+            },
+            Mode::List => lex_list(&mut tokens, arg, pos),
+        };
+    }
+    tokens
+}
+
+fn lex_normal(tokens: &mut Vec<Token>, arg: &str, pos: usize) -> Mode {
+    let rest = arg.trim_start_matches('(');
+    for i in 0..arg.len() - rest.len() {
+        tokens.push(Token::new(Tok::LParen, pos + i));
+    }
+    let pos = pos + arg.len() - rest.len();
+
+    if rest.is_empty() {
+        return Mode::Normal;
+    }
+
+    if let Some(op) = Op::parse(rest) {
+        tokens.push(Token::new(Tok::Op(op), pos));
+        return Mode::Value;
+    }
+
+    // key=value in a single argument, everything after the operator is the value
+    if let Some(idx) = rest.find(|c| matches!(c, '=' | '!' | '<' | '>')) {
+        let (key, tail) = rest.split_at(idx);
+        let op = tail.get(..2)
+            .and_then(|x| Op::parse(x).map(|op| (op, 2)))
+            .or_else(|| Op::parse(&tail[..1]).map(|op| (op, 1)));
+        if let (false, Some((op, len))) = (key.is_empty(), op) {
+            tokens.push(Token::word(key, pos));
+            tokens.push(Token::new(Tok::Op(op), pos + idx));
+            lex_value(tokens, &tail[len..], pos + idx + len);
+            return Mode::Normal;
+        }
+    }
+
+    let word = rest.trim_end_matches(')');
+    if !word.is_empty() {
+        tokens.push(Token::word(word, pos));
+    }
+    for i in word.len()..rest.len() {
+        tokens.push(Token::new(Tok::RParen, pos + i));
+    }
+
+    if word.eq_ignore_ascii_case("like") {
+        Mode::Value
+    } else if word.eq_ignore_ascii_case("in") {
+        Mode::List
+    } else {
+        Mode::Normal
+    }
+}
+
+/// Take the argument as a value, but split off closing parentheses at the end
+/// that don't belong to an opening parenthesis within the value
+fn lex_value(tokens: &mut Vec<Token>, arg: &str, pos: usize) {
+    let open = arg.matches('(').count();
+    let mut close = arg.matches(')').count();
+    let mut value = arg;
+    while close > open && value.ends_with(')') {
+        value = &value[..value.len() - 1];
+        close -= 1;
+    }
+
+    tokens.push(Token::word(value, pos));
+    for i in value.len()..arg.len() {
+        tokens.push(Token::new(Tok::RParen, pos + i));
+    }
+}
+
+fn lex_list(tokens: &mut Vec<Token>, arg: &str, pos: usize) -> Mode {
+    let mut start = 0;
+    for (i, c) in arg.char_indices() {
+        let tok = match c {
+            '(' => Tok::LParen,
+            ',' => Tok::Comma,
+            ')' => Tok::RParen,
+            _ => continue,
+        };
+        if start < i {
+            tokens.push(Token::word(&arg[start..i], pos + start));
+        }
+        tokens.push(Token::new(tok.clone(), pos + i));
+        start = i + 1;
+
+        // the list is complete, continue with the remaining characters
+        if tok == Tok::RParen {
+            return if start < arg.len() {
+                lex_normal(tokens, &arg[start..], pos + start)
+            } else {
+                Mode::Normal
+            };
+        }
+    }
+
+    if start < arg.len() {
+        tokens.push(Token::word(&arg[start..], pos + start));
+    }
+    Mode::List
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ident {
+    name: String,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Literal {
+    value: String,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Bool(bool),
+    Column(Ident),
+    Compare(Ident, Op, Literal),
+    Like(Ident, Literal, bool),
+    In(Ident, Vec<Literal>, bool),
+    IsNull(Ident, bool),
+    /// Entities that have been inserted or updated by a module
+    Module(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// Point at the position of an error in the query
+fn error(query: &str, pos: usize, msg: &str) -> Error {
+    let offset = query.get(..pos)
+        .map(|x| x.chars().count())
+        .unwrap_or(pos);
+    format_err!("{} at position {}\n    {}\n    {}^", msg, offset + 1, query, " ".repeat(offset))
+}
+
+struct Parser<'a> {
+    query: &'a str,
+    tokens: Vec<Token>,
+    idx: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, pos: usize, msg: &str) -> Error {
+        error(self.query, pos, msg)
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.idx)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.idx).cloned();
+        self.idx += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().map(|t| t.is_keyword(keyword)).unwrap_or(false) {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: Tok) -> Result<()> {
+        match self.bump() {
+            Some(token) if token.tok == expected => Ok(()),
+            Some(token) => Err(self.error(token.pos, &format!("Expected {}, found {}", expected, token.tok))),
+            None => Err(self.error(self.query.len(), &format!("Expected {}", expected))),
+        }
+    }
+
+    fn parse(mut self) -> Result<Expr> {
+        if self.tokens.is_empty() {
+            return Err(self.error(self.query.len(), "Filter condition is required"));
+        }
+
+        let expr = self.or()?;
+        if let Some(token) = self.peek() {
+            return Err(self.error(token.pos, &format!("Unexpected {}", token.tok)));
+        }
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.eat_keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.eat_keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat_keyword("not") {
+            Ok(Expr::Not(Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.bump() {
+            Some(Token { tok: Tok::LParen, .. }) => {
+                let expr = self.or()?;
+                self.expect(Tok::RParen)?;
+                Ok(expr)
+            },
+            Some(Token { tok: Tok::Word(word), pos }) => {
+                if word.eq_ignore_ascii_case("true") {
+                    Ok(Expr::Bool(true))
+                } else if word.eq_ignore_ascii_case("false") {
+                    Ok(Expr::Bool(false))
+                } else {
+                    let column = self.ident(word, pos)?;
+                    self.predicate(column)
+                }
+            },
+            Some(token) => Err(self.error(token.pos, &format!("Expected column, found {}", token.tok))),
+            None => Err(self.error(self.query.len(), "Unexpected end of filter")),
+        }
+    }
+
+    fn ident(&self, name: String, pos: usize) -> Result<Ident> {
+        // relations are separated by dots, eg. `subdomain.domain`
+        let valid = name.split('.').all(|x| {
+            x.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') &&
+            x.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if !valid {
+            return Err(self.error(pos, &format!("Expected column, found {:?}", name)));
+        }
+        Ok(Ident { name, pos })
+    }
+
+    fn predicate(&mut self, column: Ident) -> Result<Expr> {
+        let negated = match (self.tokens.get(self.idx), self.tokens.get(self.idx + 1)) {
+            (Some(a), Some(b)) if a.is_keyword("not") && (b.is_keyword("like") || b.is_keyword("in")) => {
+                self.idx += 1;
+                true
+            },
+            _ => false,
+        };
+
+        if let Some(Token { tok: Tok::Op(op), .. }) = self.peek() {
+            let op = *op;
+            self.idx += 1;
+            let value = self.literal()?;
+            Ok(Expr::Compare(column, op, value))
+        } else if self.eat_keyword("like") {
+            let pattern = self.literal()?;
+            Ok(Expr::Like(column, pattern, negated))
+        } else if self.eat_keyword("in") {
+            let values = self.list()?;
+            Ok(Expr::In(column, values, negated))
+        } else if self.eat_keyword("is") {
+            let negated = self.eat_keyword("not");
+            if !self.eat_keyword("null") {
+                let pos = self.peek().map(|t| t.pos).unwrap_or(self.query.len());
+                return Err(self.error(pos, "Expected null"));
+            }
+            Ok(Expr::IsNull(column, negated))
+        } else {
+            Ok(Expr::Column(column))
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal> {
+        match self.bump() {
+            Some(Token { tok: Tok::Word(value), pos }) => Ok(Literal { value, pos }),
+            Some(token) => Err(self.error(token.pos, &format!("Expected value, found {}", token.tok))),
+            None => Err(self.error(self.query.len(), "Expected value")),
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<Literal>> {
+        self.expect(Tok::LParen)?;
+        let mut values = Vec::new();
+        loop {
+            values.push(self.literal()?);
+            match self.bump() {
+                Some(Token { tok: Tok::Comma, .. }) => (),
+                Some(Token { tok: Tok::RParen, .. }) => return Ok(values),
+                Some(token) => return Err(self.error(token.pos, &format!("Expected ',' or ')', found {}", token.tok))),
+                None => return Err(self.error(self.query.len(), "Expected ')'")),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ColumnType {
+    Integer,
+    Float,
+    Text,
+    Bool,
+    Timestamp,
+    Binary,
+    IpAddr,
+    Cidr,
+}
+
+trait FilterType {
+    const TYPE: ColumnType;
+    const NULLABLE: bool = false;
+}
+
+impl FilterType for sql_types::Integer { const TYPE: ColumnType = ColumnType::Integer; }
+impl FilterType for sql_types::BigInt { const TYPE: ColumnType = ColumnType::Integer; }
+impl FilterType for sql_types::Float { const TYPE: ColumnType = ColumnType::Float; }
+impl FilterType for sql_types::Text { const TYPE: ColumnType = ColumnType::Text; }
+impl FilterType for sql_types::Bool { const TYPE: ColumnType = ColumnType::Bool; }
+impl FilterType for sql_types::Timestamp { const TYPE: ColumnType = ColumnType::Timestamp; }
+impl FilterType for sql_types::Binary { const TYPE: ColumnType = ColumnType::Binary; }
+
+impl<T: FilterType + NotNull> FilterType for Nullable<T> {
+    const TYPE: ColumnType = T::TYPE;
+    const NULLABLE: bool = true;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Column {
+    name: &'static str,
+    ty: ColumnType,
+    nullable: bool,
+}
+
+impl Column {
+    fn new<C>() -> Column
+    where
+        C: diesel::Column,
+        C::SqlType: FilterType,
+    {
+        Column {
+            name: C::NAME,
+            ty: <C::SqlType as FilterType>::TYPE,
+            nullable: <C::SqlType as FilterType>::NULLABLE,
+        }
+    }
+}
+
+macro_rules! columns {
+    ($table:ident: $($column:ident),+) => {
+        vec![$(Column::new::<$table::$column>()),+]
+    };
+}
+
+/// The columns that can be used in a filter, the types are taken from the schema
+fn columns(table: Table) -> Vec<Column> {
+    let mut columns = match table {
+        Table::Domains => columns!(domains: id, value, unscoped, first_observed, last_observed),
+        Table::Subdomains => columns!(subdomains: id, domain_id, value, unscoped, resolvable,
+                                      first_observed, last_observed),
+        Table::Ipaddrs => columns!(ipaddrs: id, family, value, unscoped, continent, continent_code,
+                                   country, country_code, city, latitude, longitude, asn, as_org,
+                                   description, reverse_dns, first_observed, last_observed),
+        Table::SubdomainIpaddrs => columns!(subdomain_ipaddrs: id, subdomain_id, ip_addr_id,
+                                            first_observed, last_observed),
+        Table::Urls => columns!(urls: id, subdomain_id, value, path, status, body, unscoped, online,
+                                title, redirect, blob, first_observed, last_observed),
+        Table::Emails => columns!(emails: id, value, unscoped, valid, displayname, first_observed,
+                                  last_observed),
+        Table::Phonenumbers => columns!(phonenumbers: id, value, name, unscoped, valid, last_online,
+                                        country, carrier, line, is_ported, last_ported, caller_name,
+                                        caller_type, first_observed, last_observed),
+        Table::Devices => columns!(devices: id, value, name, hostname, vendor, unscoped, last_seen,
+                                   first_observed, last_observed),
+        Table::Networks => columns!(networks: id, value, unscoped, latitude, longitude, description,
+                                    first_observed, last_observed),
+        Table::NetworkDevices => columns!(network_devices: id, network_id, device_id, ipaddr,
+                                          last_seen, first_observed, last_observed),
+        Table::Accounts => columns!(accounts: id, value, service, username, displayname, email, url,
+                                    last_seen, unscoped, phonenumber, profile_pic, birthday,
+                                    first_observed, last_observed),
+        Table::Breaches => columns!(breaches: id, value, unscoped, first_observed, last_observed),
+        Table::BreachEmails => columns!(breach_emails: id, breach_id, email_id, password,
+                                        first_observed, last_observed),
+        Table::Images => columns!(images: id, value, filename, mime, width, height, created,
+                                  latitude, longitude, nudity, ahash, dhash, phash, unscoped,
+                                  first_observed, last_observed),
+        Table::Ports => columns!(ports: id, ip_addr_id, value, ip_addr, port, protocol, status,
+                                 unscoped, banner, service, version, first_observed, last_observed),
+        Table::Netblocks => columns!(netblocks: id, family, value, unscoped, asn, as_org,
+                                     description, first_observed, last_observed),
+        Table::Cryptoaddrs => columns!(cryptoaddrs: id, value, currency, denominator, balance,
+                                       received, first_seen, last_withdrawal, unscoped, description,
+                                       last_observed, first_observed),
+        Table::Certificates => columns!(certificates: id, value, subject, issuer, sans, serial,
+                                        key_type, not_before, not_after, unscoped, first_observed,
+                                        last_observed),
+        Table::PortCertificates => columns!(port_certificates: id, port_id, certificate_id,
+                                            first_observed, last_observed),
+        Table::SubdomainCertificates => columns!(subdomain_certificates: id, subdomain_id,
+                                                 certificate_id, first_observed, last_observed),
+        Table::DnsRecords => columns!(dns_records: id, subdomain_id, value, name, record_type,
+                                      rdata, ttl, unscoped, first_observed, last_observed),
+        Table::Persons => columns!(persons: id, value, description, unscoped, first_observed,
+                                   last_observed),
+        Table::Organizations => columns!(organizations: id, value, description, unscoped,
+                                         first_observed, last_observed),
+        Table::PersonEmails => columns!(person_emails: id, person_id, email_id, first_observed,
+                                        last_observed),
+        Table::PersonAccounts => columns!(person_accounts: id, person_id, account_id,
+                                          first_observed, last_observed),
+        Table::PersonPhonenumbers => columns!(person_phonenumbers: id, person_id, phonenumber_id,
+                                              first_observed, last_observed),
+        Table::PersonImages => columns!(person_images: id, person_id, image_id, first_observed,
+                                        last_observed),
+        Table::PersonOrganizations => columns!(person_organizations: id, person_id, organization_id,
+                                               first_observed, last_observed),
+        Table::OrganizationDomains => columns!(organization_domains: id, organization_id, domain_id,
+                                               first_observed, last_observed),
+        Table::Asns => columns!(asns: id, value, as_org, unscoped, first_observed, last_observed),
+        Table::AsnNetblocks => columns!(asn_netblocks: id, asn_id, netblock_id, first_observed,
+                                        last_observed),
+        Table::AsnIpaddrs => columns!(asn_ipaddrs: id, asn_id, ip_addr_id, first_observed,
+                                      last_observed),
+    };
+
+    // ip addresses and networks are stored as text, but literals are validated
+    for column in &mut columns {
+        match (table, column.name) {
+            (Table::Ipaddrs, "value") |
+            (Table::Ports, "ip_addr") |
+            (Table::NetworkDevices, "ipaddr") => column.ty = ColumnType::IpAddr,
+            (Table::Netblocks, "value") => column.ty = ColumnType::Cidr,
+            _ => (),
+        }
+    }
+
+    columns
+}
+
+/// Mirrors the joinable! declarations in schema.rs, the foreign key of the
+/// first table references the second table
+const JOINS: &[(Table, &str, Table)] = &[
+    (Table::AsnIpaddrs, "asn_id", Table::Asns),
+    (Table::AsnIpaddrs, "ip_addr_id", Table::Ipaddrs),
+    (Table::AsnNetblocks, "asn_id", Table::Asns),
+    (Table::AsnNetblocks, "netblock_id", Table::Netblocks),
+    (Table::BreachEmails, "breach_id", Table::Breaches),
+    (Table::BreachEmails, "email_id", Table::Emails),
+    (Table::DnsRecords, "subdomain_id", Table::Subdomains),
+    (Table::NetworkDevices, "device_id", Table::Devices),
+    (Table::NetworkDevices, "network_id", Table::Networks),
+    (Table::OrganizationDomains, "domain_id", Table::Domains),
+    (Table::OrganizationDomains, "organization_id", Table::Organizations),
+    (Table::PersonAccounts, "account_id", Table::Accounts),
+    (Table::PersonAccounts, "person_id", Table::Persons),
+    (Table::PersonEmails, "email_id", Table::Emails),
+    (Table::PersonEmails, "person_id", Table::Persons),
+    (Table::PersonImages, "image_id", Table::Images),
+    (Table::PersonImages, "person_id", Table::Persons),
+    (Table::PersonOrganizations, "organization_id", Table::Organizations),
+    (Table::PersonOrganizations, "person_id", Table::Persons),
+    (Table::PersonPhonenumbers, "person_id", Table::Persons),
+    (Table::PersonPhonenumbers, "phonenumber_id", Table::Phonenumbers),
+    (Table::PortCertificates, "certificate_id", Table::Certificates),
+    (Table::PortCertificates, "port_id", Table::Ports),
+    (Table::Ports, "ip_addr_id", Table::Ipaddrs),
+    (Table::SubdomainCertificates, "certificate_id", Table::Certificates),
+    (Table::SubdomainCertificates, "subdomain_id", Table::Subdomains),
+    (Table::SubdomainIpaddrs, "ip_addr_id", Table::Ipaddrs),
+    (Table::SubdomainIpaddrs, "subdomain_id", Table::Subdomains),
+    (Table::Subdomains, "domain_id", Table::Domains),
+    (Table::Urls, "subdomain_id", Table::Subdomains),
+];
+
+/// Tables with a foreign key into `table`, their rows are deleted together with the parent
+pub(crate) fn dependents(table: Table) -> impl Iterator<Item=(Table, &'static str)> {
+    JOINS.iter()
+        .filter(move |(_, _, parent)| *parent == table)
+        .map(|&(child, column, _)| (child, column))
+}
+
+/// Tables that only connect two other tables, they are skipped when following a relation
+#[inline]
+fn is_link(table: Table) -> bool {
+    matches!(table, Table::SubdomainIpaddrs | Table::NetworkDevices | Table::BreachEmails |
+                    Table::PortCertificates | Table::SubdomainCertificates | Table::PersonEmails |
+                    Table::PersonAccounts | Table::PersonPhonenumbers | Table::PersonImages |
+                    Table::PersonOrganizations | Table::OrganizationDomains | Table::AsnNetblocks |
+                    Table::AsnIpaddrs)
+}
+
+fn relation_name(table: Table) -> &'static str {
+    match table {
+        Table::Domains => "domain",
+        Table::Subdomains => "subdomain",
+        Table::Ipaddrs => "ipaddr",
+        Table::SubdomainIpaddrs => "subdomain_ipaddr",
+        Table::Urls => "url",
+        Table::Emails => "email",
+        Table::Phonenumbers => "phonenumber",
+        Table::Devices => "device",
+        Table::Networks => "network",
+        Table::NetworkDevices => "network_device",
+        Table::Accounts => "account",
+        Table::Breaches => "breach",
+        Table::BreachEmails => "breach_email",
+        Table::Images => "image",
+        Table::Ports => "port",
+        Table::Netblocks => "netblock",
+        Table::Cryptoaddrs => "cryptoaddr",
+        Table::Certificates => "certificate",
+        Table::PortCertificates => "port_certificate",
+        Table::SubdomainCertificates => "subdomain_certificate",
+        Table::DnsRecords => "dns_record",
+        Table::Persons => "person",
+        Table::Organizations => "organization",
+        Table::PersonEmails => "person_email",
+        Table::PersonAccounts => "person_account",
+        Table::PersonPhonenumbers => "person_phonenumber",
+        Table::PersonImages => "person_image",
+        Table::PersonOrganizations => "person_organization",
+        Table::OrganizationDomains => "organization_domain",
+        Table::Asns => "asn",
+        Table::AsnNetblocks => "asn_netblock",
+        Table::AsnIpaddrs => "asn_ipaddr",
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Join {
+    /// A foreign key of this table references the other table
+    Parent(&'static str),
+    /// A foreign key of the other table references this table
+    Child(&'static str),
+    /// A link table references both tables
+    Link(Table, &'static str, &'static str),
+    /// The tags of this table
+    Tag,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Relation {
+    name: &'static str,
+    table: Table,
+    join: Join,
+}
+
+impl Relation {
+    /// Match rows of this table that have a related row that matches the condition
+    fn wrap(&self, node: Node) -> Node {
+        let table = self.table.as_str();
+        match self.join {
+            Join::Parent(column) => Node::Subquery(column, "id", table, Box::new(node)),
+            Join::Child(column) => Node::Subquery("id", column, table, Box::new(node)),
+            Join::Link(link, this, other) => Node::Subquery("id", this, link.as_str(),
+                Box::new(Node::Subquery(other, "id", table, Box::new(node)))),
+            Join::Tag => {
+                let family = Node::Compare("family", Op::Eq, Value::Text(table.to_string()));
+                Node::Subquery("id", "key", "tags", Box::new(Node::And(Box::new(family), Box::new(node))))
+            },
+        }
+    }
+}
+
+fn relations(table: Table) -> Vec<Relation> {
+    let mut relations = Vec::new();
+    for &(child, column, parent) in JOINS {
+        if child == table {
+            relations.push(Relation {
+                name: relation_name(parent),
+                table: parent,
+                join: Join::Parent(column),
+            });
+        } else if parent == table && is_link(child) {
+            for &(link, other_column, other) in JOINS {
+                if link == child && other_column != column {
+                    relations.push(Relation {
+                        name: relation_name(other),
+                        table: other,
+                        join: Join::Link(link, column, other_column),
+                    });
+                }
+            }
+        } else if parent == table {
+            relations.push(Relation {
+                name: relation_name(child),
+                table: child,
+                join: Join::Child(column),
+            });
+        }
+    }
+    relations
+}
+
+/// Tags behave like a relation, they are compared by their name
+const TAG: Column = Column {
+    name: "name",
+    ty: ColumnType::Text,
+    nullable: false,
+};
+
+/// The names of all columns of a table
+pub fn column_names(table: Table) -> Vec<&'static str> {
+    columns(table).into_iter()
+        .map(|c| c.name)
+        .collect()
+}
+
+pub fn validate_column(table: Table, column: &str) -> Result<&'static str> {
+    let names = column_names(table);
+    match names.iter().find(|name| **name == column) {
+        Some(name) => Ok(*name),
+        None => bail!("Unknown column {:?} in {} (expected one of: {})", column, table.as_str(), names.join(", ")),
+    }
+}
+
+pub(crate) fn column_type(table: Table, name: &str) -> Option<ColumnType> {
+    columns(table).into_iter()
+        .find(|c| c.name == name)
+        .map(|c| c.ty)
+}
+
+fn parse_timestamp(s: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"].iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    Bool(bool),
+    Timestamp(NaiveDateTime),
+}
+
+impl Value {
+    fn walk(&self, out: &mut AstPass<Sqlite>) -> QueryResult<()> {
+        match self {
+            Value::Integer(x) => out.push_bind_param::<sql_types::BigInt, _>(x),
+            Value::Float(x) => out.push_bind_param::<sql_types::Double, _>(x),
+            Value::Text(x) => out.push_bind_param::<sql_types::Text, _>(x),
+            Value::Bool(x) => out.push_bind_param::<sql_types::Bool, _>(x),
+            Value::Timestamp(x) => out.push_bind_param::<sql_types::Timestamp, _>(x),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Bool(bool),
+    Column(&'static str),
+    Compare(&'static str, Op, Value),
+    Like(&'static str, String, bool),
+    In(&'static str, Vec<Value>, bool),
+    IsNull(&'static str, bool),
+    /// `column IN (SELECT column FROM table WHERE node)`
+    Subquery(&'static str, &'static str, &'static str, Box<Node>),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn walk(&self, out: &mut AstPass<Sqlite>) -> QueryResult<()> {
+        match self {
+            Node::Bool(true) => out.push_sql("1"),
+            Node::Bool(false) => out.push_sql("0"),
+            Node::Column(column) => out.push_identifier(column)?,
+            Node::Compare(column, op, value) => {
+                out.push_identifier(column)?;
+                out.push_sql(&format!(" {} ", op.as_str()));
+                value.walk(out)?;
+            },
+            Node::Like(column, pattern, negated) => {
+                out.push_identifier(column)?;
+                out.push_sql(if *negated { " NOT LIKE " } else { " LIKE " });
+                out.push_bind_param::<sql_types::Text, _>(pattern)?;
+            },
+            Node::In(column, values, negated) => {
+                out.push_identifier(column)?;
+                out.push_sql(if *negated { " NOT IN (" } else { " IN (" });
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        out.push_sql(", ");
+                    }
+                    value.walk(out)?;
+                }
+                out.push_sql(")");
+            },
+            Node::IsNull(column, negated) => {
+                out.push_identifier(column)?;
+                out.push_sql(if *negated { " IS NOT NULL" } else { " IS NULL" });
+            },
+            Node::Subquery(column, select, table, node) => {
+                out.push_identifier(column)?;
+                out.push_sql(" IN (SELECT ");
+                out.push_identifier(select)?;
+                out.push_sql(" FROM ");
+                out.push_identifier(table)?;
+                out.push_sql(" WHERE ");
+                node.walk(out)?;
+                out.push_sql(")");
+            },
+            Node::Not(node) => {
+                out.push_sql("NOT (");
+                node.walk(out)?;
+                out.push_sql(")");
+            },
+            Node::And(a, b) => {
+                out.push_sql("(");
+                a.walk(out)?;
+                out.push_sql(") AND (");
+                b.walk(out)?;
+                out.push_sql(")");
+            },
+            Node::Or(a, b) => {
+                out.push_sql("(");
+                a.walk(out)?;
+                out.push_sql(") OR (");
+                b.walk(out)?;
+                out.push_sql(")");
+            },
+        }
+        Ok(())
+    }
+}
+
+/// A filter that has been checked against the columns of a table. All values
+/// are passed to sqlite as bind parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlFilter {
+    node: Node,
+}
+
+impl Expression for SqlFilter {
+    type SqlType = sql_types::Bool;
+}
+
+impl<QS> AppearsOnTable<QS> for SqlFilter {}
+
+impl<QS> SelectableExpression<QS> for SqlFilter {}
+
+impl NonAggregate for SqlFilter {}
+
+impl QueryId for SqlFilter {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl QueryFragment<Sqlite> for SqlFilter {
+    fn walk_ast(&self, mut out: AstPass<Sqlite>) -> QueryResult<()> {
+        self.node.walk(&mut out)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    query: String,
+    expr: Expr,
+}
+
+impl Filter {
+    #[inline]
+    pub fn any() -> Filter {
+        Filter {
+            query: String::from("where true"),
+            expr: Expr::Bool(true),
+        }
+    }
+
+    pub fn parse(args: &[String]) -> Result<Filter> {
+        debug!("Parsing query: {:?}", args);
+
+        if args.is_empty() {
+            bail!("Filter condition is required");
+        }
+
+        if args[0].to_lowercase() != "where" {
+            bail!("Filter must begin with WHERE");
+        }
+
+        let query = args.join(" ");
+        let mut pos = 0;
+        let args = args.iter()
+            .map(|arg| {
+                let x = (pos, arg.as_str());
+                pos += arg.len() + 1;
+                x
+            })
+            .skip(1)
+            .collect::<Vec<_>>();
+
+        let tokens = lex(&args);
+        let expr = Parser {
+            query: &query,
+            tokens,
+            idx: 0,
+        }.parse()?;
+        debug!("Parsed query: {:?}", expr);
+
+        Ok(Filter {
+            query,
+            expr,
+        })
+    }
+
+    pub fn parse_optional(args: &[String]) -> Result<Filter> {
+        debug!("Parsing optional query: {:?}", args);
+
+        if args.is_empty() {
+            debug!("Using filter with no condition");
+            return Ok(Filter::any());
+        }
+
+        Self::parse(args)
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn and_scoped(&self) -> Filter {
+        let unscoped = Expr::Compare(Ident {
+            name: String::from("unscoped"),
+            pos: 0,
+        }, Op::Eq, Literal {
+            value: String::from("0"),
+            pos: 0,
+        });
+
+        Filter {
+            query: self.query.clone(),
+            expr: Expr::And(Box::new(self.expr.clone()), Box::new(unscoped)),
+        }
+    }
+
+    pub fn and_module(&self, module: &str) -> Filter {
+        Filter {
+            query: self.query.clone(),
+            expr: Expr::And(Box::new(self.expr.clone()), Box::new(Expr::Module(module.to_string()))),
+        }
+    }
+
+    /// Check the filter against the columns of a table and turn it into an
+    /// expression that can be used in diesel queries
+    pub fn sql(&self, table: Table) -> Result<SqlFilter> {
+        let node = self.compile(table, &self.expr)?;
+        Ok(SqlFilter { node })
+    }
+
+    fn error(&self, pos: usize, msg: &str) -> Error {
+        error(&self.query, pos, msg)
+    }
+
+    /// Resolve a column, columns of related entities are prefixed with the
+    /// name of the relation. If the path ends with a relation there's no column.
+    fn path(&self, table: Table, ident: &Ident) -> Result<(Vec<Relation>, Option<Column>)> {
+        let mut table = table;
+        let mut joins = Vec::new();
+        let mut pos = ident.pos;
+
+        let mut segments = ident.name.split('.').peekable();
+        while let Some(name) = segments.next() {
+            let last = segments.peek().is_none();
+
+            let columns = columns(table);
+            if last {
+                if let Some(column) = columns.iter().find(|c| c.name == name) {
+                    return Ok((joins, Some(*column)));
+                }
+            }
+
+            let relations = relations(table);
+            if let Some(relation) = relations.iter().find(|r| r.name == name) {
+                joins.push(*relation);
+                table = relation.table;
+                pos += name.len() + 1;
+                continue;
+            }
+
+            if name == "tag" {
+                if !last {
+                    return Err(self.error(pos + name.len() + 1, "Tags don't have columns"));
+                }
+                joins.push(Relation {
+                    name: "tag",
+                    table,
+                    join: Join::Tag,
+                });
+                return Ok((joins, None));
+            }
+
+            let names = columns.iter()
+                .map(|c| c.name)
+                .chain(relations.iter().map(|r| r.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let msg = if last {
+                format!("Unknown column {:?} in {} (expected one of: {})", name, table.as_str(), names)
+            } else {
+                format!("Unknown relation {:?} in {} (expected one of: {})", name, table.as_str(),
+                    relations.iter().map(|r| r.name).collect::<Vec<_>>().join(", "))
+            };
+            return Err(self.error(pos, &msg));
+        }
+
+        Ok((joins, None))
+    }
+
+    /// Resolve a column that is compared with a value, a relation at the end
+    /// of the path refers to the value of the related entity
+    fn column(&self, table: Table, ident: &Ident) -> Result<(Vec<Relation>, Column)> {
+        let (joins, column) = self.path(table, ident)?;
+        let column = match (column, joins.last()) {
+            (Some(column), _) => column,
+            (None, Some(Relation { join: Join::Tag, .. })) => TAG,
+            (None, Some(relation)) => columns(relation.table).into_iter()
+                .find(|c| c.name == "value")
+                .ok_or_else(|| self.error(ident.pos, &format!("Relation {:?} has no value", relation.name)))?,
+            (None, None) => unreachable!(),
+        };
+        Ok((joins, column))
+    }
+
+    fn join(joins: Vec<Relation>, node: Node) -> Node {
+        joins.iter()
+            .rev()
+            .fold(node, |node, relation| relation.wrap(node))
+    }
+
+    fn value(&self, column: &Column, literal: &Literal) -> Result<Value> {
+        let s = literal.value.as_str();
+        let value = match column.ty {
+            ColumnType::Integer => s.parse().ok().map(Value::Integer),
+            ColumnType::Float => s.parse().ok().map(Value::Float),
+            ColumnType::Text => Some(Value::Text(s.to_string())),
+            ColumnType::Bool => match s.to_lowercase().as_str() {
+                "true" | "1" => Some(Value::Bool(true)),
+                "false" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            ColumnType::Timestamp => parse_timestamp(s).map(Value::Timestamp),
+            ColumnType::Binary => None,
+            ColumnType::IpAddr => s.parse::<net::IpAddr>().ok()
+                .map(|x| Value::Text(x.to_string())),
+            ColumnType::Cidr => s.parse::<IpNetwork>().ok()
+                .map(|x| Value::Text(x.to_string())),
+        };
+
+        value.ok_or_else(|| {
+            let expected = match column.ty {
+                ColumnType::Integer => "an integer",
+                ColumnType::Float => "a number",
+                ColumnType::Text => "text",
+                ColumnType::Bool => "true or false",
+                ColumnType::Timestamp => "a timestamp like 2020-01-31 or 2020-01-31T12:00:00",
+                ColumnType::Binary => return self.error(literal.pos, &format!("Column {:?} can only be checked with `is null`", column.name)),
+                ColumnType::IpAddr => "an ip address",
+                ColumnType::Cidr => "a network like 192.0.2.0/24",
+            };
+            self.error(literal.pos, &format!("Expected {} for {:?}, found {:?}", expected, column.name, s))
+        })
+    }
+
+    fn compile(&self, table: Table, expr: &Expr) -> Result<Node> {
+        let node = match expr {
+            Expr::Bool(x) => Node::Bool(*x),
+            Expr::Column(ident) => {
+                let (joins, column) = self.path(table, ident)?;
+                let node = match column {
+                    Some(column) if column.ty == ColumnType::Bool => Node::Column(column.name),
+                    Some(column) => return Err(self.error(ident.pos, &format!("Column {:?} isn't a boolean, compare it with a value instead", column.name))),
+                    // the entity has at least one related entity
+                    None => Node::Bool(true),
+                };
+                Filter::join(joins, node)
+            },
+            Expr::Compare(ident, op, literal) => {
+                let (joins, column) = self.column(table, ident)?;
+                let ordered = matches!(column.ty, ColumnType::Integer | ColumnType::Float | ColumnType::Text | ColumnType::Timestamp);
+                if !op.is_equality() && !ordered {
+                    return Err(self.error(ident.pos, &format!("Column {:?} can only be compared with = and !=", column.name)));
+                }
+                Filter::join(joins, Node::Compare(column.name, *op, self.value(&column, literal)?))
+            },
+            Expr::Like(ident, pattern, negated) => {
+                let (joins, column) = self.column(table, ident)?;
+                if !matches!(column.ty, ColumnType::Text | ColumnType::IpAddr | ColumnType::Cidr) {
+                    return Err(self.error(ident.pos, &format!("Column {:?} isn't text and can't be used with like", column.name)));
+                }
+                Filter::join(joins, Node::Like(column.name, pattern.value.clone(), *negated))
+            },
+            Expr::In(ident, literals, negated) => {
+                let (joins, column) = self.column(table, ident)?;
+                let values = literals.iter()
+                    .map(|literal| self.value(&column, literal))
+                    .collect::<Result<Vec<_>>>()?;
+                Filter::join(joins, Node::In(column.name, values, *negated))
+            },
+            Expr::IsNull(ident, negated) => {
+                let (joins, column) = self.path(table, ident)?;
+                match column {
+                    Some(column) if column.nullable => Filter::join(joins, Node::IsNull(column.name, *negated)),
+                    Some(column) => return Err(self.error(ident.pos, &format!("Column {:?} can't be null", column.name))),
+                    // the entity has no related entity
+                    None if *negated => Filter::join(joins, Node::Bool(true)),
+                    None => Node::Not(Box::new(Filter::join(joins, Node::Bool(true)))),
+                }
+            },
+            Expr::Module(module) => {
+                let family = Node::Compare("family", Op::Eq, Value::Text(table.as_str().to_string()));
+                let run = Node::Subquery("run_id", "id", "runs",
+                    Box::new(Node::Compare("module", Op::Eq, Value::Text(module.clone()))));
+                Node::Subquery("id", "key", "provenance",
+                    Box::new(Node::And(Box::new(family), Box::new(run))))
+            },
+            Expr::Not(expr) => Node::Not(Box::new(self.compile(table, expr)?)),
+            Expr::And(a, b) => Node::And(Box::new(self.compile(table, a)?),
+                                         Box::new(self.compile(table, b)?)),
+            Expr::Or(a, b) => Node::Or(Box::new(self.compile(table, a)?),
+                                       Box::new(self.compile(table, b)?)),
+        };
+        Ok(node)
+    }
+}
+
+/// Sorting and pagination of a query, this is done by sqlite instead of
+/// loading every row first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Order {
+    pub column: Option<String>,
+    pub desc: bool,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl Order {
+    pub fn apply<'a, ST, QS>(&self, table: Table, mut query: BoxedSelectStatement<'a, ST, QS, Sqlite>) -> Result<BoxedSelectStatement<'a, ST, QS, Sqlite>> {
+        if let Some(column) = &self.column {
+            // only names from the schema are put into the query
+            let column = validate_column(table, column)?;
+            let direction = if self.desc { "DESC" } else { "ASC" };
+            // nulls are sorted first, rows that are equal stay in database order
+            query = query.order(sql::<sql_types::Integer>(&format!("{:?} {}, id ASC", column, direction)));
+        }
+
+        if self.limit.is_some() || self.offset.is_some() {
+            // sqlite only accepts an offset after a limit, -1 means no limit
+            let limit = self.limit.map(|x| x as i64).unwrap_or(-1);
+            query = query.limit(limit)
+                .offset(self.offset.unwrap_or(0) as i64);
+        }
+
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Filter> {
+        let args = args.iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>();
+        Filter::parse(&args)
+    }
+
+    /// Render the where clause of a query with this filter
+    fn compile(table: Table, args: &[&str]) -> Result<String> {
+        let filter = parse(args)?.sql(table)?;
+        let query = domains::table.select(domains::id).filter(filter);
+        let sql = diesel::debug_query::<Sqlite, _>(&query).to_string();
+        Ok(sql.split_once(" WHERE ").unwrap().1.to_string())
+    }
+
+    #[test]
+    fn test_filter_simple() {
+        let sql = compile(Table::Domains, &["where", "value=1"]).unwrap();
+        assert_eq!(sql, "`value` = ? -- binds: [\"1\"]");
+    }
+
+    #[test]
+    fn test_filter_str() {
+        let sql = compile(Table::Domains, &["where", "value", "=", "asdf"]).unwrap();
+        assert_eq!(sql, "`value` = ? -- binds: [\"asdf\"]");
+    }
+
+    #[test]
+    fn test_filter_and() {
+        let sql = compile(Table::Domains, &["where", "value", "=", "foobar", "and", "id", "=", "1"]).unwrap();
+        assert_eq!(sql, "(`value` = ?) AND (`id` = ?) -- binds: [\"foobar\", 1]");
+    }
+
+    #[test]
+    fn test_filter_precedence() {
+        let sql = compile(Table::Subdomains, &["where", "not", "resolvable", "or", "id>=3", "and", "value", "like", "%.com"]).unwrap();
+        assert_eq!(sql, "(NOT (`resolvable`)) OR ((`id` >= ?) AND (`value` LIKE ?)) -- binds: [3, \"%.com\"]");
+    }
+
+    #[test]
+    fn test_filter_parens() {
+        let sql = compile(Table::Domains, &["where", "(", "value", "like", "e%", "and", "value", "like", "%m", ")", "or", "false"]).unwrap();
+        assert_eq!(sql, "((`value` LIKE ?) AND (`value` LIKE ?)) OR (0) -- binds: [\"e%\", \"%m\"]");
+    }
+
+    #[test]
+    fn test_filter_parens_unspaced() {
+        let sql = compile(Table::Ports, &["where", "(port", "=", "80", "or", "port", "=", "443)", "and", "(value=a)"]).unwrap();
+        assert_eq!(sql, "((`port` = ?) OR (`port` = ?)) AND (`value` = ?) -- binds: [80, 443, \"a\"]");
+
+        // parentheses that are part of the value are kept
+        let sql = compile(Table::Urls, &["where", "(title", "like", "%(beta))"]).unwrap();
+        assert_eq!(sql, "`title` LIKE ? -- binds: [\"%(beta)\"]");
+    }
+
+    #[test]
+    fn test_filter_injection() {
+        let sql = compile(Table::Domains, &["where", "value=a' or 1=1 --"]).unwrap();
+        assert_eq!(sql, "`value` = ? -- binds: [\"a' or 1=1 --\"]");
+    }
+
+    #[test]
+    fn test_filter_in() {
+        let sql = compile(Table::Urls, &["where", "status", "not", "in", "(200,", "301)", "and", "title", "is", "not", "null"]).unwrap();
+        assert_eq!(sql, "(`status` NOT IN (?, ?)) AND (`title` IS NOT NULL) -- binds: [200, 301]");
+    }
+
+    #[test]
+    fn test_filter_types() {
+        let sql = compile(Table::Netblocks, &["where", "value=10.0.0.0/8", "and", "unscoped=false"]).unwrap();
+        assert_eq!(sql, "(`value` = ?) AND (`unscoped` = ?) -- binds: [\"10.0.0.0/8\", false]");
+
+        let err = compile(Table::Ipaddrs, &["where", "value=10.0.0.0/8"]).unwrap_err();
+        assert!(err.to_string().starts_with("Expected an ip address for \"value\", found \"10.0.0.0/8\" at position 13"));
+
+        let err = compile(Table::Domains, &["where", "id", "=", "abc"]).unwrap_err();
+        assert!(err.to_string().starts_with("Expected an integer for \"id\", found \"abc\" at position 12"));
+    }
+
+    #[test]
+    fn test_filter_timestamp() {
+        let filter = parse(&["where", "last_seen", ">=", "2020-01-31"]).unwrap()
+            .sql(Table::Devices).unwrap();
+        assert_eq!(filter.node, Node::Compare("last_seen", Op::Ge,
+            Value::Timestamp(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap().and_hms_opt(0, 0, 0).unwrap())));
+    }
+
+    #[test]
+    fn test_filter_unknown_column() {
+        let err = compile(Table::Domains, &["where", "vlaue=1"]).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown column \"vlaue\" in domains (expected one of: id, value, unscoped, first_observed, last_observed, organization, subdomain) at position 7
+    where vlaue=1
+          ^");
+    }
+
+    #[test]
+    fn test_filter_syntax_errors() {
+        let err = parse(&["where", "value", "=", "1", "and"]).unwrap_err();
+        assert!(err.to_string().starts_with("Unexpected end of filter at position 20"));
+
+        let err = parse(&["where", "(", "value=1"]).unwrap_err();
+        assert!(err.to_string().starts_with("Expected ')' at position 16"));
+
+        let err = parse(&["where", "value=1", "foo"]).unwrap_err();
+        assert!(err.to_string().starts_with("Unexpected \"foo\" at position 15"));
+
+        let err = parse(&["where", "1=1;", "drop", "table", "domains"]).unwrap_err();
+        assert!(err.to_string().starts_with("Expected column, found \"1\" at position 7"));
+    }
+
+    #[test]
+    fn test_filter_scoped() {
+        let sql = compile(Table::Domains, &["where", "true"]).unwrap();
+        assert_eq!(sql, "1 -- binds: []");
+
+        let filter = Filter::any().and_scoped().sql(Table::Domains).unwrap();
+        assert_eq!(filter.node, Node::And(Box::new(Node::Bool(true)),
+            Box::new(Node::Compare("unscoped", Op::Eq, Value::Bool(false)))));
+    }
+
+    #[test]
+    fn test_filter_module() {
+        let filter = parse(&["where", "value", "like", "%.com"]).unwrap()
+            .and_module("kpcyrd/ctlogs")
+            .sql(Table::Domains)
+            .unwrap();
+        let query = domains::table.select(domains::id).filter(filter);
+        let sql = diesel::debug_query::<Sqlite, _>(&query).to_string();
+        assert!(sql.ends_with(" WHERE (`value` LIKE ?) AND (`id` IN (SELECT `key` FROM `provenance` WHERE (`family` = ?) AND (`run_id` IN (SELECT `id` FROM `runs` WHERE `module` = ?)))) -- binds: [\"%.com\", \"domains\", \"kpcyrd/ctlogs\"]"));
+    }
+
+    #[test]
+    fn test_filter_tag() {
+        let sql = compile(Table::Subdomains, &["where", "tag", "=", "phishing-infra"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `key` FROM `tags` WHERE (`family` = ?) AND (`name` = ?)) -- binds: [\"subdomains\", \"phishing-infra\"]");
+
+        let sql = compile(Table::Urls, &["where", "subdomain.tag", "is", "null"]).unwrap();
+        assert_eq!(sql, "NOT (`subdomain_id` IN (SELECT `id` FROM `subdomains` WHERE `id` IN (SELECT `key` FROM `tags` WHERE (`family` = ?) AND (1)))) -- binds: [\"subdomains\"]");
+
+        let err = compile(Table::Domains, &["where", "tag.name=x"]).unwrap_err();
+        assert!(err.to_string().starts_with("Tags don't have columns at position 11"));
+    }
+
+    #[test]
+    fn test_joins_match_schema() {
+        for &(table, column, _) in JOINS {
+            assert!(columns(table).iter().any(|c| c.name == column), "{}.{}", table.as_str(), column);
+        }
+    }
+
+    #[test]
+    fn test_filter_relation_link() {
+        let sql = compile(Table::Subdomains, &["where", "ipaddr.asn=13335"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `subdomain_id` FROM `subdomain_ipaddrs` WHERE `ip_addr_id` IN (SELECT `id` FROM `ipaddrs` WHERE `asn` = ?)) -- binds: [13335]");
+    }
+
+    #[test]
+    fn test_filter_relation_certificate() {
+        let sql = compile(Table::Ports, &["where", "certificate.key_type=rsa"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `port_id` FROM `port_certificates` WHERE `certificate_id` IN (SELECT `id` FROM `certificates` WHERE `key_type` = ?)) -- binds: [\"rsa\"]");
+    }
+
+    #[test]
+    fn test_filter_relation_dns_record() {
+        let sql = compile(Table::Subdomains, &["where", "dns_record.record_type=MX"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `subdomain_id` FROM `dns_records` WHERE `record_type` = ?) -- binds: [\"MX\"]");
+
+        let sql = compile(Table::DnsRecords, &["where", "subdomain.domain=example.com"]).unwrap();
+        assert_eq!(sql, "`subdomain_id` IN (SELECT `id` FROM `subdomains` WHERE `domain_id` IN (SELECT `id` FROM `domains` WHERE `value` = ?)) -- binds: [\"example.com\"]");
+    }
+
+    #[test]
+    fn test_filter_relation_person() {
+        let sql = compile(Table::Persons, &["where", "email.value", "like", "%@example.com"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `person_id` FROM `person_emails` WHERE `email_id` IN (SELECT `id` FROM `emails` WHERE `value` LIKE ?)) -- binds: [\"%@example.com\"]");
+
+        let sql = compile(Table::Emails, &["where", "person.organization", "=", "ACME"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `email_id` FROM `person_emails` WHERE `person_id` IN (SELECT `id` FROM `persons` WHERE `id` IN (SELECT `person_id` FROM `person_organizations` WHERE `organization_id` IN (SELECT `id` FROM `organizations` WHERE `value` = ?)))) -- binds: [\"ACME\"]");
+    }
+
+    #[test]
+    fn test_filter_relation_asn() {
+        let sql = compile(Table::Asns, &["where", "netblock.value", "=", "192.0.2.0/24"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `asn_id` FROM `asn_netblocks` WHERE `netblock_id` IN (SELECT `id` FROM `netblocks` WHERE `value` = ?)) -- binds: [\"192.0.2.0/24\"]");
+
+        // the asn column of ipaddrs takes precedence over the relation
+        let sql = compile(Table::Ipaddrs, &["where", "asn=13335"]).unwrap();
+        assert_eq!(sql, "`asn` = ? -- binds: [13335]");
+
+        let sql = compile(Table::Ipaddrs, &["where", "asn.as_org", "like", "%cloudflare%"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `ip_addr_id` FROM `asn_ipaddrs` WHERE `asn_id` IN (SELECT `id` FROM `asns` WHERE `as_org` LIKE ?)) -- binds: [\"%cloudflare%\"]");
+    }
+
+    #[test]
+    fn test_filter_relation_path() {
+        let sql = compile(Table::Urls, &["where", "subdomain.domain", "=", "example.com"]).unwrap();
+        assert_eq!(sql, "`subdomain_id` IN (SELECT `id` FROM `subdomains` WHERE `domain_id` IN (SELECT `id` FROM `domains` WHERE `value` = ?)) -- binds: [\"example.com\"]");
+    }
+
+    #[test]
+    fn test_filter_relation_exists() {
+        let sql = compile(Table::Domains, &["where", "subdomain", "is", "null"]).unwrap();
+        assert_eq!(sql, "NOT (`id` IN (SELECT `domain_id` FROM `subdomains` WHERE 1)) -- binds: []");
+
+        let sql = compile(Table::Ipaddrs, &["where", "port.status=open"]).unwrap();
+        assert_eq!(sql, "`id` IN (SELECT `ip_addr_id` FROM `ports` WHERE `status` = ?) -- binds: [\"open\"]");
+    }
+
+    #[test]
+    fn test_filter_unknown_relation() {
+        let err = compile(Table::Urls, &["where", "subdomain.foo.value=1"]).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown relation \"foo\" in subdomains (expected one of: dns_record, certificate, ipaddr, domain, url) at position 17"));
+    }
+
+    fn new_ipaddr(value: &str, asn: Option<i32>) -> crate::models::Insert {
+        crate::models::Insert::IpAddr(crate::models::NewIpAddr {
+            family: String::from("4"),
+            value: value.to_string(),
+            continent: None,
+            continent_code: None,
+            country: None,
+            country_code: None,
+            city: None,
+            latitude: None,
+            longitude: None,
+            asn,
+            as_org: None,
+            description: None,
+            reverse_dns: None,
+            unscoped: false,
+        })
+    }
+
+    #[test]
+    fn test_order() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = crate::db::Database::establish_tmp(dir.path()).unwrap();
+        for (value, asn) in &[("192.0.2.1", Some(13335)), ("192.0.2.2", None),
+                              ("192.0.2.3", Some(3320)), ("192.0.2.4", Some(13335))] {
+            db.insert_generic(new_ipaddr(value, *asn)).unwrap();
+        }
+
+        let select = |order: Order| db.filter_ordered::<crate::models::IpAddr>(&Filter::any(), &order)
+            .map(|rows| rows.into_iter().map(|x| x.value).collect::<Vec<_>>());
+
+        let asn = || Some(String::from("asn"));
+        assert_eq!(select(Order { column: asn(), ..Default::default() }).unwrap(),
+                   ["192.0.2.2", "192.0.2.3", "192.0.2.1", "192.0.2.4"]);
+        assert_eq!(select(Order { column: asn(), desc: true, ..Default::default() }).unwrap(),
+                   ["192.0.2.1", "192.0.2.4", "192.0.2.3", "192.0.2.2"]);
+        assert_eq!(select(Order { column: asn(), desc: true, limit: Some(2), offset: Some(1) }).unwrap(),
+                   ["192.0.2.4", "192.0.2.3"]);
+        assert_eq!(select(Order { offset: Some(3), ..Default::default() }).unwrap(),
+                   ["192.0.2.4"]);
+        assert!(select(Order { column: Some(String::from("asn; --")), ..Default::default() }).is_err());
+    }
+}
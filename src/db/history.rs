@@ -0,0 +1,179 @@
+use crate::db::{Database, Previous, Table};
+use crate::errors::*;
+use crate::fmt::colors::*;
+use crate::models::Update;
+use crate::schema::*;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use std::fmt::Write;
+
+
+/// A single field of an entity that has been changed, values are stored as json
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="history"]
+pub struct History {
+    pub id: i32,
+    pub family: String,
+    pub key: i32,
+    pub field: String,
+    pub old: String,
+    pub new: String,
+    pub time: NaiveDateTime,
+    pub source: Option<String>,
+}
+
+impl History {
+    /// Record every field that is changed by an update
+    pub fn record(db: &Database, family: Table, key: i32, update: &Update, previous: &Previous, source: Option<&str>) -> Result<()> {
+        let changes = changes(update, previous)?;
+        insert_changes(db, family, key, changes, source)
+    }
+
+    /// Record every field that is set back to an older value, `current` is the
+    /// entity before it has been restored
+    pub fn record_restore(db: &Database, family: Table, key: i32, current: &Value, restored: &Previous, source: &str) -> Result<()> {
+        let mut changes = Vec::new();
+        for (field, new) in restored {
+            let old = current.get(field).unwrap_or(&Value::Null);
+            if old != new {
+                changes.push((field.to_string(), serde_json::to_string(old)?, serde_json::to_string(new)?));
+            }
+        }
+        insert_changes(db, family, key, changes, Some(source))
+    }
+
+    /// The changes of an entity, oldest first
+    pub fn list(db: &Database, my_family: Table, my_key: i32) -> Result<Vec<History>> {
+        use crate::schema::history::dsl::*;
+
+        history
+            .filter(family.eq(my_family.as_str()))
+            .filter(key.eq(my_key))
+            .order_by((time.asc(), id.asc()))
+            .load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    /// Every recorded change, used for exports
+    pub fn list_all(db: &Database) -> Result<Vec<History>> {
+        use crate::schema::history::dsl::*;
+
+        history
+            .order_by(id.asc())
+            .load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    pub fn to_term_str(&self) -> Result<String> {
+        let mut out = String::new();
+        write!(out, "{} {}: ", self.time, self.field)?;
+        Red::display(&mut out, &self.old)?;
+        write!(out, " -> ")?;
+        Green::display(&mut out, &self.new)?;
+        if let Some(source) = &self.source {
+            write!(out, " ({})", source)?;
+        }
+        Ok(out)
+    }
+}
+
+/// The changed fields of an update with their old and new value as json
+fn changes(update: &Update, previous: &Previous) -> Result<Vec<(String, String, String)>> {
+    let mut changes = Vec::new();
+    for (field, new) in update.fields()? {
+        let old = previous.get(&field).unwrap_or(&Value::Null);
+        let old = serde_json::to_string(old)?;
+        let new = serde_json::to_string(&new)?;
+        changes.push((field, old, new));
+    }
+    Ok(changes)
+}
+
+fn insert_changes(db: &Database, family: Table, key: i32, changes: Vec<(String, String, String)>, source: Option<&str>) -> Result<()> {
+    let time = Utc::now().naive_utc();
+    for (field, old, new) in changes {
+        NewHistory {
+            family: family.as_str(),
+            key,
+            field: &field,
+            old,
+            new,
+            time,
+            source,
+        }.insert(db)?;
+    }
+    Ok(())
+}
+
+#[derive(Insertable)]
+#[table_name="history"]
+pub struct NewHistory<'a> {
+    pub family: &'a str,
+    pub key: i32,
+    pub field: &'a str,
+    pub old: String,
+    pub new: String,
+    pub time: NaiveDateTime,
+    pub source: Option<&'a str>,
+}
+
+impl<'a> NewHistory<'a> {
+    pub fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(history::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    pub fn exists(&self, db: &Database) -> Result<bool> {
+        use crate::schema::history::dsl::*;
+
+        let n = history
+            .filter(family.eq(self.family))
+            .filter(key.eq(self.key))
+            .filter(field.eq(self.field))
+            .filter(old.eq(&self.old))
+            .filter(new.eq(&self.new))
+            .filter(time.eq(self.time))
+            .count()
+            .get_result::<i64>(db.db())?;
+        Ok(n > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SubdomainUpdate;
+    use serde_json::json;
+
+    #[test]
+    fn test_changes() {
+        let update = Update::Subdomain(SubdomainUpdate {
+            id: 2,
+            resolvable: Some(false),
+        });
+        let previous = match json!({"resolvable": true}) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        let changes = changes(&update, &previous).unwrap();
+        assert_eq!(changes, vec![
+            ("resolvable".to_string(), "true".to_string(), "false".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_changes_unknown_previous() {
+        let update = Update::Subdomain(SubdomainUpdate {
+            id: 2,
+            resolvable: Some(true),
+        });
+        let changes = changes(&update, &Previous::new()).unwrap();
+        assert_eq!(changes, vec![
+            ("resolvable".to_string(), "null".to_string(), "true".to_string()),
+        ]);
+    }
+}
@@ -1,11 +1,10 @@
 use crate::errors::*;
 use serde::{Serialize, Deserialize};
 
-use diesel::expression::SqlLiteral;
-use diesel::expression::sql_literal::sql;
-use diesel::sql_types::Bool;
+use diesel::connection::TransactionManager;
 use diesel::prelude::*;
-use std::fmt::Write;
+use std::fmt;
+use std::path::PathBuf;
 use strum_macros::{EnumString, IntoStaticStr};
 use crate::autonoscope::{RuleSet, RuleType};
 use crate::models::*;
@@ -14,13 +13,24 @@ use crate::migrations;
 use crate::worker;
 use crate::workspaces::Workspace;
 
+pub mod filter;
+pub use self::filter::{Filter, Order};
+pub mod history;
+pub mod provenance;
+pub mod search;
+pub mod seen;
+pub mod snapshot;
+pub mod tags;
 pub mod ttl;
 
 
+/// The values of fields before they have been updated
+pub type Previous = serde_json::Map<String, serde_json::Value>;
+
 #[derive(Debug)]
 pub enum DbChange {
     Insert,
-    Update(Update),
+    Update(Update, Previous),
     None,
 }
 
@@ -30,7 +40,7 @@ impl DbChange {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[derive(EnumString, IntoStaticStr)]
 #[strum(serialize_all = "kebab_case")]
 pub enum Family {
@@ -51,6 +61,21 @@ pub enum Family {
     Port,
     Netblock,
     Cryptoaddr,
+    Certificate,
+    PortCertificate,
+    SubdomainCertificate,
+    DnsRecord,
+    Person,
+    Organization,
+    PersonEmail,
+    PersonAccount,
+    PersonPhonenumber,
+    PersonImage,
+    PersonOrganization,
+    OrganizationDomain,
+    Asn,
+    AsnNetblock,
+    AsnIpaddr,
 }
 
 impl Family {
@@ -60,6 +85,7 @@ impl Family {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[derive(EnumString, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum Table {
@@ -80,6 +106,21 @@ pub enum Table {
     Ports,
     Netblocks,
     Cryptoaddrs,
+    Certificates,
+    PortCertificates,
+    SubdomainCertificates,
+    DnsRecords,
+    Persons,
+    Organizations,
+    PersonEmails,
+    PersonAccounts,
+    PersonPhonenumbers,
+    PersonImages,
+    PersonOrganizations,
+    OrganizationDomains,
+    Asns,
+    AsnNetblocks,
+    AsnIpaddrs,
 }
 
 impl Table {
@@ -89,6 +130,45 @@ impl Table {
     }
 }
 
+impl From<&Family> for Table {
+    fn from(family: &Family) -> Table {
+        match family {
+            Family::Domain => Table::Domains,
+            Family::Subdomain => Table::Subdomains,
+            Family::Ipaddr => Table::Ipaddrs,
+            Family::SubdomainIpaddr => Table::SubdomainIpaddrs,
+            Family::Url => Table::Urls,
+            Family::Email => Table::Emails,
+            Family::Phonenumber => Table::Phonenumbers,
+            Family::Device => Table::Devices,
+            Family::Network => Table::Networks,
+            Family::NetworkDevice => Table::NetworkDevices,
+            Family::Account => Table::Accounts,
+            Family::Breach => Table::Breaches,
+            Family::BreachEmail => Table::BreachEmails,
+            Family::Image => Table::Images,
+            Family::Port => Table::Ports,
+            Family::Netblock => Table::Netblocks,
+            Family::Cryptoaddr => Table::Cryptoaddrs,
+            Family::Certificate => Table::Certificates,
+            Family::PortCertificate => Table::PortCertificates,
+            Family::SubdomainCertificate => Table::SubdomainCertificates,
+            Family::DnsRecord => Table::DnsRecords,
+            Family::Person => Table::Persons,
+            Family::Organization => Table::Organizations,
+            Family::PersonEmail => Table::PersonEmails,
+            Family::PersonAccount => Table::PersonAccounts,
+            Family::PersonPhonenumber => Table::PersonPhonenumbers,
+            Family::PersonImage => Table::PersonImages,
+            Family::PersonOrganization => Table::PersonOrganizations,
+            Family::OrganizationDomain => Table::OrganizationDomains,
+            Family::Asn => Table::Asns,
+            Family::AsnNetblock => Table::AsnNetblocks,
+            Family::AsnIpaddr => Table::AsnIpaddrs,
+        }
+    }
+}
+
 pub struct Database {
     workspace: Workspace,
     db: SqliteConnection,
@@ -108,6 +188,10 @@ impl Database {
 
     pub fn establish_quiet(workspace: Workspace) -> Result<Database> {
         let path = workspace.db_path()?;
+        Database::establish_path(workspace, path)
+    }
+
+    fn establish_path(workspace: Workspace, path: PathBuf) -> Result<Database> {
         let path = path.into_os_string().into_string()
             .map_err(|_| format_err!("Failed to convert db path to utf-8"))?;
 
@@ -135,6 +219,13 @@ impl Database {
         })
     }
 
+    /// Open a throwaway database in a directory, used by tests
+    #[cfg(test)]
+    pub(crate) fn establish_tmp(dir: &std::path::Path) -> Result<Database> {
+        let workspace = "test".parse::<Workspace>()?;
+        Database::establish_path(workspace, dir.join("db.sqlite"))
+    }
+
     #[inline(always)]
     pub fn name(&self) -> &str {
         &self.workspace
@@ -150,6 +241,25 @@ impl Database {
         &self.workspace
     }
 
+    /// Run a closure inside a transaction, nothing it did is kept if it fails
+    pub fn transaction<T, F>(&mut self, f: F) -> Result<T>
+        where F: FnOnce(&mut Database) -> Result<T>
+    {
+        self.db.transaction_manager().begin_transaction(&self.db)?;
+        match f(self) {
+            Ok(value) => {
+                self.db.transaction_manager().commit_transaction(&self.db)?;
+                Ok(value)
+            },
+            Err(err) => {
+                self.db.transaction_manager().rollback_transaction(&self.db)?;
+                // the rules might have been changed in the meantime
+                self.autonoscope = RuleSet::load(&self.db)?;
+                Err(err)
+            },
+        }
+    }
+
     #[inline(always)]
     pub fn autonoscope_add_rule(&mut self, object: &RuleType, value: &str, scoped: bool) -> Result<()> {
         self.autonoscope.add_rule(&self.db, object, value, scoped)
@@ -191,11 +301,26 @@ impl Database {
             Insert::Port(object) => self.insert_struct(object, scoped),
             Insert::Netblock(object) => self.insert_struct(object, scoped),
             Insert::CryptoAddr(object) => self.insert_struct(object, scoped),
+            Insert::Certificate(object) => self.insert_struct(object, scoped),
+            Insert::PortCertificate(object) => self.insert_port_certificate_struct(&object),
+            Insert::SubdomainCertificate(object) => self.insert_subdomain_certificate_struct(&object),
+            Insert::DnsRecord(object) => self.insert_dns_record_struct(object, scoped),
+            Insert::Person(object) => self.insert_struct(object, scoped),
+            Insert::Organization(object) => self.insert_struct(object, scoped),
+            Insert::PersonEmail(object) => self.insert_person_email_struct(&object),
+            Insert::PersonAccount(object) => self.insert_person_account_struct(&object),
+            Insert::PersonPhoneNumber(object) => self.insert_person_phonenumber_struct(&object),
+            Insert::PersonImage(object) => self.insert_person_image_struct(&object),
+            Insert::PersonOrganization(object) => self.insert_person_organization_struct(&object),
+            Insert::OrganizationDomain(object) => self.insert_organization_domain_struct(&object),
+            Insert::Asn(object) => self.insert_struct(object, scoped),
+            Insert::AsnNetblock(object) => self.insert_asn_netblock_struct(&object),
+            Insert::AsnIpAddr(object) => self.insert_asn_ipaddr_struct(&object),
         }
     }
 
     /// Returns true if we didn't have this value yet
-    pub fn insert_struct<T: InsertableStruct<M>, M: Model + Scopable>(&self, mut obj: T, scoped: bool) -> Result<Option<(DbChange, i32)>> {
+    pub fn insert_struct<T: InsertableStruct<M>, M: Model + Scopable + Serialize>(&self, mut obj: T, scoped: bool) -> Result<Option<(DbChange, i32)>> {
         if let Some(existing) = M::get_opt(self, obj.value())? {
             // entity is out of scope
             if !existing.scoped() {
@@ -205,7 +330,9 @@ impl Database {
             let update = obj.upsert(&existing);
             if update.is_dirty() {
                 update.apply(self)?;
-                Ok(Some((DbChange::Update(update.generic()), existing.id())))
+                let update = update.generic();
+                let previous = update.previous(&existing)?;
+                Ok(Some((DbChange::Update(update, previous), existing.id())))
             } else {
                 Ok(Some((DbChange::None, existing.id())))
             }
@@ -240,6 +367,15 @@ impl Database {
         }
     }
 
+    /// A dns record is always stored with the subdomain it belongs to
+    pub fn insert_dns_record_struct(&self, dns_record: NewDnsRecord, scoped: bool) -> Result<Option<(DbChange, i32)>> {
+        let subdomain = Subdomain::by_id(self, dns_record.subdomain_id)?;
+        if dns_record.name != subdomain.value {
+            bail!("Name of dns record {:?} doesn't match subdomain {:?}", dns_record.name, subdomain.value);
+        }
+        self.insert_struct(dns_record, scoped)
+    }
+
     pub fn insert_network_device_struct(&self, network_device: &NewNetworkDevice) -> Result<Option<(DbChange, i32)>> {
         if let Some(network_device_id) = NetworkDevice::get_id_opt(self, &(network_device.network_id, network_device.device_id))? {
             Ok(Some((DbChange::None, network_device_id)))
@@ -252,6 +388,126 @@ impl Database {
         }
     }
 
+    pub fn insert_port_certificate_struct(&self, port_certificate: &NewPortCertificate) -> Result<Option<(DbChange, i32)>> {
+        if let Some(port_certificate_id) = PortCertificate::get_id_opt(self, &(port_certificate.port_id, port_certificate.certificate_id))? {
+            Ok(Some((DbChange::None, port_certificate_id)))
+        } else {
+            diesel::insert_into(port_certificates::table)
+                .values(port_certificate)
+                .execute(&self.db)?;
+            let id = PortCertificate::get_id(self, &(port_certificate.port_id, port_certificate.certificate_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_subdomain_certificate_struct(&self, subdomain_certificate: &NewSubdomainCertificate) -> Result<Option<(DbChange, i32)>> {
+        if let Some(subdomain_certificate_id) = SubdomainCertificate::get_id_opt(self, &(subdomain_certificate.subdomain_id, subdomain_certificate.certificate_id))? {
+            Ok(Some((DbChange::None, subdomain_certificate_id)))
+        } else {
+            diesel::insert_into(subdomain_certificates::table)
+                .values(subdomain_certificate)
+                .execute(&self.db)?;
+            let id = SubdomainCertificate::get_id(self, &(subdomain_certificate.subdomain_id, subdomain_certificate.certificate_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_person_email_struct(&self, person_email: &NewPersonEmail) -> Result<Option<(DbChange, i32)>> {
+        if let Some(person_email_id) = PersonEmail::get_id_opt(self, &(person_email.person_id, person_email.email_id))? {
+            Ok(Some((DbChange::None, person_email_id)))
+        } else {
+            diesel::insert_into(person_emails::table)
+                .values(person_email)
+                .execute(&self.db)?;
+            let id = PersonEmail::get_id(self, &(person_email.person_id, person_email.email_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_person_account_struct(&self, person_account: &NewPersonAccount) -> Result<Option<(DbChange, i32)>> {
+        if let Some(person_account_id) = PersonAccount::get_id_opt(self, &(person_account.person_id, person_account.account_id))? {
+            Ok(Some((DbChange::None, person_account_id)))
+        } else {
+            diesel::insert_into(person_accounts::table)
+                .values(person_account)
+                .execute(&self.db)?;
+            let id = PersonAccount::get_id(self, &(person_account.person_id, person_account.account_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_person_phonenumber_struct(&self, person_phonenumber: &NewPersonPhoneNumber) -> Result<Option<(DbChange, i32)>> {
+        if let Some(person_phonenumber_id) = PersonPhoneNumber::get_id_opt(self, &(person_phonenumber.person_id, person_phonenumber.phonenumber_id))? {
+            Ok(Some((DbChange::None, person_phonenumber_id)))
+        } else {
+            diesel::insert_into(person_phonenumbers::table)
+                .values(person_phonenumber)
+                .execute(&self.db)?;
+            let id = PersonPhoneNumber::get_id(self, &(person_phonenumber.person_id, person_phonenumber.phonenumber_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_person_image_struct(&self, person_image: &NewPersonImage) -> Result<Option<(DbChange, i32)>> {
+        if let Some(person_image_id) = PersonImage::get_id_opt(self, &(person_image.person_id, person_image.image_id))? {
+            Ok(Some((DbChange::None, person_image_id)))
+        } else {
+            diesel::insert_into(person_images::table)
+                .values(person_image)
+                .execute(&self.db)?;
+            let id = PersonImage::get_id(self, &(person_image.person_id, person_image.image_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_person_organization_struct(&self, person_organization: &NewPersonOrganization) -> Result<Option<(DbChange, i32)>> {
+        if let Some(person_organization_id) = PersonOrganization::get_id_opt(self, &(person_organization.person_id, person_organization.organization_id))? {
+            Ok(Some((DbChange::None, person_organization_id)))
+        } else {
+            diesel::insert_into(person_organizations::table)
+                .values(person_organization)
+                .execute(&self.db)?;
+            let id = PersonOrganization::get_id(self, &(person_organization.person_id, person_organization.organization_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_organization_domain_struct(&self, organization_domain: &NewOrganizationDomain) -> Result<Option<(DbChange, i32)>> {
+        if let Some(organization_domain_id) = OrganizationDomain::get_id_opt(self, &(organization_domain.organization_id, organization_domain.domain_id))? {
+            Ok(Some((DbChange::None, organization_domain_id)))
+        } else {
+            diesel::insert_into(organization_domains::table)
+                .values(organization_domain)
+                .execute(&self.db)?;
+            let id = OrganizationDomain::get_id(self, &(organization_domain.organization_id, organization_domain.domain_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_asn_netblock_struct(&self, asn_netblock: &NewAsnNetblock) -> Result<Option<(DbChange, i32)>> {
+        if let Some(asn_netblock_id) = AsnNetblock::get_id_opt(self, &(asn_netblock.asn_id, asn_netblock.netblock_id))? {
+            Ok(Some((DbChange::None, asn_netblock_id)))
+        } else {
+            diesel::insert_into(asn_netblocks::table)
+                .values(asn_netblock)
+                .execute(&self.db)?;
+            let id = AsnNetblock::get_id(self, &(asn_netblock.asn_id, asn_netblock.netblock_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
+    pub fn insert_asn_ipaddr_struct(&self, asn_ipaddr: &NewAsnIpAddr) -> Result<Option<(DbChange, i32)>> {
+        if let Some(asn_ipaddr_id) = AsnIpAddr::get_id_opt(self, &(asn_ipaddr.asn_id, asn_ipaddr.ip_addr_id))? {
+            Ok(Some((DbChange::None, asn_ipaddr_id)))
+        } else {
+            diesel::insert_into(asn_ipaddrs::table)
+                .values(asn_ipaddr)
+                .execute(&self.db)?;
+            let id = AsnIpAddr::get_id(self, &(asn_ipaddr.asn_id, asn_ipaddr.ip_addr_id))?;
+            Ok(Some((DbChange::Insert, id)))
+        }
+    }
+
     pub fn insert_breach_email_struct(&self, obj: NewBreachEmail) -> Result<Option<(DbChange, i32)>> {
         let value = &(obj.breach_id, obj.email_id, obj.password.clone());
 
@@ -261,7 +517,9 @@ impl Database {
             let update = obj.upsert(&existing);
             if update.is_dirty() {
                 update.apply(self)?;
-                Ok(Some((DbChange::Update(update.generic()), id)))
+                let update = update.generic();
+                let previous = update.previous(&existing)?;
+                Ok(Some((DbChange::Update(update, previous), id)))
             } else {
                 Ok(Some((DbChange::None, id)))
             }
@@ -292,6 +550,36 @@ impl Database {
             Update::Port(update) => self.update_port(update),
             Update::Netblock(update) => self.update_netblock(update),
             Update::CryptoAddr(update) => self.update_cryptoaddr(update),
+            Update::Certificate(update) => self.update_certificate(update),
+            Update::DnsRecord(update) => self.update_dns_record(update),
+            Update::Person(update) => self.update_person(update),
+            Update::Organization(update) => self.update_organization(update),
+            Update::Asn(update) => self.update_asn(update),
+        }
+    }
+
+    /// Load the values of the fields an update is going to change
+    pub fn previous_generic(&self, update: &Update) -> Result<Previous> {
+        match update {
+            Update::Subdomain(x) => update.previous(&Subdomain::by_id(self, x.id)?),
+            Update::IpAddr(x) => update.previous(&IpAddr::by_id(self, x.id)?),
+            Update::Url(x) => update.previous(&Url::by_id(self, x.id)?),
+            Update::Email(x) => update.previous(&Email::by_id(self, x.id)?),
+            Update::PhoneNumber(x) => update.previous(&PhoneNumber::by_id(self, x.id)?),
+            Update::Device(x) => update.previous(&Device::by_id(self, x.id)?),
+            Update::Network(x) => update.previous(&Network::by_id(self, x.id)?),
+            Update::NetworkDevice(x) => update.previous(&NetworkDevice::by_id(self, x.id)?),
+            Update::Account(x) => update.previous(&Account::by_id(self, x.id)?),
+            Update::BreachEmail(x) => update.previous(&BreachEmail::by_id(self, x.id)?),
+            Update::Image(x) => update.previous(&Image::by_id(self, x.id)?),
+            Update::Port(x) => update.previous(&Port::by_id(self, x.id)?),
+            Update::Netblock(x) => update.previous(&Netblock::by_id(self, x.id)?),
+            Update::CryptoAddr(x) => update.previous(&CryptoAddr::by_id(self, x.id)?),
+            Update::Certificate(x) => update.previous(&Certificate::by_id(self, x.id)?),
+            Update::DnsRecord(x) => update.previous(&DnsRecord::by_id(self, x.id)?),
+            Update::Person(x) => update.previous(&Person::by_id(self, x.id)?),
+            Update::Organization(x) => update.previous(&Organization::by_id(self, x.id)?),
+            Update::Asn(x) => update.previous(&Asn::by_id(self, x.id)?),
         }
     }
 
@@ -407,6 +695,46 @@ impl Database {
         Ok(cryptoaddr_update.id)
     }
 
+    pub fn update_certificate(&self, certificate_update: &CertificateUpdate) -> Result<i32> {
+        use crate::schema::certificates::columns::*;
+        diesel::update(certificates::table.filter(id.eq(certificate_update.id)))
+            .set(certificate_update)
+            .execute(&self.db)?;
+        Ok(certificate_update.id)
+    }
+
+    pub fn update_dns_record(&self, dns_record_update: &DnsRecordUpdate) -> Result<i32> {
+        use crate::schema::dns_records::columns::*;
+        diesel::update(dns_records::table.filter(id.eq(dns_record_update.id)))
+            .set(dns_record_update)
+            .execute(&self.db)?;
+        Ok(dns_record_update.id)
+    }
+
+    pub fn update_person(&self, person_update: &PersonUpdate) -> Result<i32> {
+        use crate::schema::persons::columns::*;
+        diesel::update(persons::table.filter(id.eq(person_update.id)))
+            .set(person_update)
+            .execute(&self.db)?;
+        Ok(person_update.id)
+    }
+
+    pub fn update_organization(&self, organization_update: &OrganizationUpdate) -> Result<i32> {
+        use crate::schema::organizations::columns::*;
+        diesel::update(organizations::table.filter(id.eq(organization_update.id)))
+            .set(organization_update)
+            .execute(&self.db)?;
+        Ok(organization_update.id)
+    }
+
+    pub fn update_asn(&self, asn_update: &AsnUpdate) -> Result<i32> {
+        use crate::schema::asns::columns::*;
+        diesel::update(asns::table.filter(id.eq(asn_update.id)))
+            .set(asn_update)
+            .execute(&self.db)?;
+        Ok(asn_update.id)
+    }
+
     fn get_opt_typed<T: Model + Scopable>(&self, value: &T::ID) -> Result<Option<i32>> {
         match T::get_opt(self, value)? {
             Some(ref obj) if obj.scoped() => Ok(Some(obj.id())),
@@ -433,257 +761,222 @@ impl Database {
             Family::Port => self.get_opt_typed::<Port>(value),
             Family::Netblock => self.get_opt_typed::<Netblock>(value),
             Family::Cryptoaddr => self.get_opt_typed::<CryptoAddr>(value),
+            Family::Certificate => self.get_opt_typed::<Certificate>(value),
+            Family::PortCertificate => bail!("Unsupported operation"),
+            Family::SubdomainCertificate => bail!("Unsupported operation"),
+            Family::DnsRecord => self.get_opt_typed::<DnsRecord>(value),
+            Family::Person => self.get_opt_typed::<Person>(value),
+            Family::Organization => self.get_opt_typed::<Organization>(value),
+            Family::PersonEmail => bail!("Unsupported operation"),
+            Family::PersonAccount => bail!("Unsupported operation"),
+            Family::PersonPhonenumber => bail!("Unsupported operation"),
+            Family::PersonImage => bail!("Unsupported operation"),
+            Family::PersonOrganization => bail!("Unsupported operation"),
+            Family::OrganizationDomain => bail!("Unsupported operation"),
+            Family::Asn => self.get_opt_typed::<Asn>(value),
+            Family::AsnNetblock => bail!("Unsupported operation"),
+            Family::AsnIpaddr => bail!("Unsupported operation"),
         }
     }
 
-    //
-
-    pub fn list<T: Model>(&self) -> Result<Vec<T>> {
-        T::list(self)
-    }
-
-    pub fn filter<T: Model>(&self, filter: &Filter) -> Result<Vec<T>> {
-        T::filter(self, filter)
+    /// Find the table and id of an entity by its value, including entities
+    /// that are out of scope
+    pub fn get_entity(&self, family: &Family, value: &str) -> Result<(Table, i32)> {
+        let id = match family {
+            Family::Domain => Domain::get_id_opt(self, value)?,
+            Family::Subdomain => Subdomain::get_id_opt(self, value)?,
+            Family::Ipaddr => IpAddr::get_id_opt(self, value)?,
+            Family::Url => Url::get_id_opt(self, value)?,
+            Family::Email => Email::get_id_opt(self, value)?,
+            Family::Phonenumber => PhoneNumber::get_id_opt(self, value)?,
+            Family::Device => Device::get_id_opt(self, value)?,
+            Family::Network => Network::get_id_opt(self, value)?,
+            Family::Account => Account::get_id_opt(self, value)?,
+            Family::Breach => Breach::get_id_opt(self, value)?,
+            Family::Image => Image::get_id_opt(self, value)?,
+            Family::Port => Port::get_id_opt(self, value)?,
+            Family::Netblock => Netblock::get_id_opt(self, value)?,
+            Family::Cryptoaddr => CryptoAddr::get_id_opt(self, value)?,
+            Family::Certificate => Certificate::get_id_opt(self, value)?,
+            Family::DnsRecord => DnsRecord::get_id_opt(self, value)?,
+            Family::Person => Person::get_id_opt(self, value)?,
+            Family::Organization => Organization::get_id_opt(self, value)?,
+            Family::Asn => Asn::get_id_opt(self, value)?,
+            Family::SubdomainIpaddr |
+            Family::NetworkDevice |
+            Family::BreachEmail |
+            Family::PortCertificate |
+            Family::SubdomainCertificate |
+            Family::PersonEmail |
+            Family::PersonAccount |
+            Family::PersonPhonenumber |
+            Family::PersonImage |
+            Family::PersonOrganization |
+            Family::OrganizationDomain |
+            Family::AsnNetblock |
+            Family::AsnIpaddr => bail!("Links between entities can't be selected by value"),
+        };
+        let id = id
+            .ok_or_else(|| format_err!("Entity not found: {} {:?}", family.as_str(), value))?;
+        Ok((Table::from(family), id))
     }
 
-    pub fn filter_with_param<T: Model>(&self, filter: &Filter, param: Option<&String>) -> Result<Vec<T>> {
-        match param {
-            Some(param) => T::filter_with_param(self, filter, param),
-            _ => T::filter(self, filter),
-        }
-    }
-}
-
-#[derive(Debug, PartialEq)]
-pub struct Filter {
-    query: String,
-}
-
-impl Filter {
-    pub fn new<I: Into<String>>(query: I) -> Filter {
-        Filter {
-            query: query.into(),
+    /// Returns None if the entity doesn't exist anymore
+    pub fn label_generic(&self, table: Table, id: i32) -> Result<Option<String>> {
+        // links between entities can't be turned into a string directly
+        fn label<T: Model + Printable<P>, P: fmt::Display>(db: &Database, id: i32) -> Result<Option<String>> {
+            match T::by_id(db, id) {
+                Ok(obj) => Ok(Some(obj.printable(db)?.to_string())),
+                Err(err) => match err.downcast::<diesel::result::Error>() {
+                    Ok(diesel::result::Error::NotFound) => Ok(None),
+                    Ok(err) => Err(err.into()),
+                    Err(err) => Err(err),
+                },
+            }
         }
-    }
-
-    #[inline]
-    pub fn any() -> Filter {
-        Filter::new("1")
-    }
 
-    fn escape(value: &str) -> String {
-        let mut out = String::from("'");
-        for c in value.chars() {
-            match c {
-                '\'' => out.push_str("''"),
-                c => out.push(c),
-            }
+        match table {
+            Table::Domains => label::<Domain, _>(self, id),
+            Table::Subdomains => label::<Subdomain, _>(self, id),
+            Table::Ipaddrs => label::<IpAddr, _>(self, id),
+            Table::SubdomainIpaddrs => label::<SubdomainIpAddr, _>(self, id),
+            Table::Urls => label::<Url, _>(self, id),
+            Table::Emails => label::<Email, _>(self, id),
+            Table::Phonenumbers => label::<PhoneNumber, _>(self, id),
+            Table::Devices => label::<Device, _>(self, id),
+            Table::Networks => label::<Network, _>(self, id),
+            Table::NetworkDevices => label::<NetworkDevice, _>(self, id),
+            Table::Accounts => label::<Account, _>(self, id),
+            Table::Breaches => label::<Breach, _>(self, id),
+            Table::BreachEmails => label::<BreachEmail, _>(self, id),
+            Table::Images => label::<Image, _>(self, id),
+            Table::Ports => label::<Port, _>(self, id),
+            Table::Netblocks => label::<Netblock, _>(self, id),
+            Table::Cryptoaddrs => label::<CryptoAddr, _>(self, id),
+            Table::Certificates => label::<Certificate, _>(self, id),
+            Table::PortCertificates => label::<PortCertificate, _>(self, id),
+            Table::SubdomainCertificates => label::<SubdomainCertificate, _>(self, id),
+            Table::DnsRecords => label::<DnsRecord, _>(self, id),
+            Table::Persons => label::<Person, _>(self, id),
+            Table::Organizations => label::<Organization, _>(self, id),
+            Table::PersonEmails => label::<PersonEmail, _>(self, id),
+            Table::PersonAccounts => label::<PersonAccount, _>(self, id),
+            Table::PersonPhonenumbers => label::<PersonPhoneNumber, _>(self, id),
+            Table::PersonImages => label::<PersonImage, _>(self, id),
+            Table::PersonOrganizations => label::<PersonOrganization, _>(self, id),
+            Table::OrganizationDomains => label::<OrganizationDomain, _>(self, id),
+            Table::Asns => label::<Asn, _>(self, id),
+            Table::AsnNetblocks => label::<AsnNetblock, _>(self, id),
+            Table::AsnIpaddrs => label::<AsnIpAddr, _>(self, id),
         }
-        out.push('\'');
-        out
     }
 
-    pub fn parse(mut args: &[String]) -> Result<Filter> {
-        debug!("Parsing query: {:?}", args);
-
-        if args.is_empty() {
-            bail!("Filter condition is required");
+    /// The current state of an entity, serialized the same way as in exports
+    pub fn json_generic(&self, table: Table, id: i32) -> Result<serde_json::Value> {
+        fn json<T: Model + Serialize>(db: &Database, id: i32) -> Result<serde_json::Value> {
+            let obj = T::by_id(db, id)?;
+            serde_json::to_value(obj).map_err(Error::from)
         }
 
-        if args[0].to_lowercase() == "where" {
-            args = &args[1..];
-        } else {
-            bail!("Filter must begin with WHERE");
+        match table {
+            Table::Domains => json::<Domain>(self, id),
+            Table::Subdomains => json::<Subdomain>(self, id),
+            Table::Ipaddrs => json::<IpAddr>(self, id),
+            Table::SubdomainIpaddrs => json::<SubdomainIpAddr>(self, id),
+            Table::Urls => json::<Url>(self, id),
+            Table::Emails => json::<Email>(self, id),
+            Table::Phonenumbers => json::<PhoneNumber>(self, id),
+            Table::Devices => json::<Device>(self, id),
+            Table::Networks => json::<Network>(self, id),
+            Table::NetworkDevices => json::<NetworkDevice>(self, id),
+            Table::Accounts => json::<Account>(self, id),
+            Table::Breaches => json::<Breach>(self, id),
+            Table::BreachEmails => json::<BreachEmail>(self, id),
+            Table::Images => json::<Image>(self, id),
+            Table::Ports => json::<Port>(self, id),
+            Table::Netblocks => json::<Netblock>(self, id),
+            Table::Cryptoaddrs => json::<CryptoAddr>(self, id),
+            Table::Certificates => json::<Certificate>(self, id),
+            Table::PortCertificates => json::<PortCertificate>(self, id),
+            Table::SubdomainCertificates => json::<SubdomainCertificate>(self, id),
+            Table::DnsRecords => json::<DnsRecord>(self, id),
+            Table::Persons => json::<Person>(self, id),
+            Table::Organizations => json::<Organization>(self, id),
+            Table::PersonEmails => json::<PersonEmail>(self, id),
+            Table::PersonAccounts => json::<PersonAccount>(self, id),
+            Table::PersonPhonenumbers => json::<PersonPhoneNumber>(self, id),
+            Table::PersonImages => json::<PersonImage>(self, id),
+            Table::PersonOrganizations => json::<PersonOrganization>(self, id),
+            Table::OrganizationDomains => json::<OrganizationDomain>(self, id),
+            Table::Asns => json::<Asn>(self, id),
+            Table::AsnNetblocks => json::<AsnNetblock>(self, id),
+            Table::AsnIpaddrs => json::<AsnIpAddr>(self, id),
         }
+    }
 
-        let mut query = String::new();
-
-        let mut expect_value = false;
-
-        for arg in args {
-            if ["=", "!=", "<", ">", "<=", ">=", "like"].contains(&arg.to_lowercase().as_str()) {
-                expect_value = true;
-                write!(query, " {}", arg)?;
-                continue;
-            }
-
-            if let Some(idx) = arg.find('=') {
-                if idx != 0 {
-                    let (key, value) = arg.split_at(idx);
-                    write!(query, " {} = {}", key, Self::escape(&value[1..]))?;
-                    continue;
-                }
-            }
-
-            if expect_value {
-                query.push(' ');
-                query.push_str(&Self::escape(arg));
-                expect_value = false;
-            } else {
-                write!(query, " {}", arg)?;
-            }
+    pub fn delete_generic(&self, table: Table, id: i32) -> Result<usize> {
+        match table {
+            Table::Domains => Domain::delete_id(self, id),
+            Table::Subdomains => Subdomain::delete_id(self, id),
+            Table::Ipaddrs => IpAddr::delete_id(self, id),
+            Table::SubdomainIpaddrs => SubdomainIpAddr::delete_id(self, id),
+            Table::Urls => Url::delete_id(self, id),
+            Table::Emails => Email::delete_id(self, id),
+            Table::Phonenumbers => PhoneNumber::delete_id(self, id),
+            Table::Devices => Device::delete_id(self, id),
+            Table::Networks => Network::delete_id(self, id),
+            Table::NetworkDevices => NetworkDevice::delete_id(self, id),
+            Table::Accounts => Account::delete_id(self, id),
+            Table::Breaches => Breach::delete_id(self, id),
+            Table::BreachEmails => BreachEmail::delete_id(self, id),
+            Table::Images => Image::delete_id(self, id),
+            Table::Ports => Port::delete_id(self, id),
+            Table::Netblocks => Netblock::delete_id(self, id),
+            Table::Cryptoaddrs => CryptoAddr::delete_id(self, id),
+            Table::Certificates => Certificate::delete_id(self, id),
+            Table::PortCertificates => PortCertificate::delete_id(self, id),
+            Table::SubdomainCertificates => SubdomainCertificate::delete_id(self, id),
+            Table::DnsRecords => DnsRecord::delete_id(self, id),
+            Table::Persons => Person::delete_id(self, id),
+            Table::Organizations => Organization::delete_id(self, id),
+            Table::PersonEmails => PersonEmail::delete_id(self, id),
+            Table::PersonAccounts => PersonAccount::delete_id(self, id),
+            Table::PersonPhonenumbers => PersonPhoneNumber::delete_id(self, id),
+            Table::PersonImages => PersonImage::delete_id(self, id),
+            Table::PersonOrganizations => PersonOrganization::delete_id(self, id),
+            Table::OrganizationDomains => OrganizationDomain::delete_id(self, id),
+            Table::Asns => Asn::delete_id(self, id),
+            Table::AsnNetblocks => AsnNetblock::delete_id(self, id),
+            Table::AsnIpaddrs => AsnIpAddr::delete_id(self, id),
         }
-        debug!("Parsed query: {:?}", query);
-
-        Ok(Filter::new(query))
     }
 
-    pub fn parse_optional(args: &[String]) -> Result<Filter> {
-        debug!("Parsing optional query: {:?}", args);
-
-        if args.is_empty() {
-            debug!("Using filter with no condition");
-            return Ok(Filter::any());
-        }
+    //
 
-        Self::parse(args)
+    pub fn list<T: Model>(&self) -> Result<Vec<T>> {
+        T::list(self)
     }
 
-    pub fn query(&self) -> &str {
-        &self.query
+    pub fn filter<T: Model>(&self, filter: &Filter) -> Result<Vec<T>> {
+        T::filter(self, filter)
     }
 
-    pub fn and_scoped(&self) -> Filter {
-        let query = format!("({}) AND unscoped=0", self.query);
-        Filter::new(query)
+    pub fn filter_ordered<T: Sortable>(&self, filter: &Filter, order: &Order) -> Result<Vec<T>> {
+        T::filter_ordered(self, filter, order)
     }
 
-    pub fn sql(&self) -> SqlLiteral<Bool> {
-        sql::<Bool>(&self.query)
+    pub fn filter_with_param<T: Model>(&self, filter: &Filter, param: Option<&String>) -> Result<Vec<T>> {
+        match param {
+            Some(param) => T::filter_with_param(self, filter, param),
+            _ => T::filter(self, filter),
+        }
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_filter_simple() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value=1".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = '1'"));
-    }
-
-    #[test]
-    fn test_filter_str1() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value=abc".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = 'abc'"));
-    }
-
-    #[test]
-    fn test_filter_str2() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "=".to_string(),
-                                     "asdf".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = 'asdf'"));
-    }
-
-    #[test]
-    fn test_filter_and() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "=".to_string(),
-                                     "foobar".to_string(),
-                                     "and".to_string(),
-                                     "id".to_string(),
-                                     "=".to_string(),
-                                     "1".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = 'foobar' and id = '1'"));
-    }
-
-    #[test]
-    fn test_filter_like() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "like".to_string(),
-                                     "%foobar".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value like '%foobar'"));
-    }
-
-    #[test]
-    fn test_filter_backslash1() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value=\\".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = '\\'"));
-    }
-
-    #[test]
-    fn test_filter_backslash2() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "=".to_string(),
-                                     "\\".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = '\\'"));
-    }
-
-    #[test]
-    fn test_filter_quote1() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value=a'b".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = 'a''b'"));
-    }
-
-    #[test]
-    fn test_filter_quote2() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "=".to_string(),
-                                     "a'b".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value = 'a''b'"));
-    }
-
-    #[test]
-    fn test_filter_greater() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     ">".to_string(),
-                                     "123".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value > '123'"));
-    }
-
-    #[test]
-    fn test_filter_smaller() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "<".to_string(),
-                                     "123".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value < '123'"));
-    }
-
-    #[test]
-    fn test_filter_greater_equal() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     ">=".to_string(),
-                                     "123".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value >= '123'"));
-    }
-
-    #[test]
-    fn test_filter_smaller_equal() {
-        let filter = Filter::parse(&["where".to_string(),
-                                     "value".to_string(),
-                                     "<=".to_string(),
-                                     "123".to_string(),
-                                    ]).unwrap();
-        assert_eq!(filter, Filter::new(" value <= '123'"));
-    }
-
     #[test]
     fn test_family_names() {
         assert_eq!(Family::Domain.as_str(),             "domain");
@@ -702,6 +995,15 @@ mod tests {
         assert_eq!(Family::Image.as_str(),              "image");
         assert_eq!(Family::Port.as_str(),               "port");
         assert_eq!(Family::Netblock.as_str(),           "netblock");
+        assert_eq!(Family::Certificate.as_str(),        "certificate");
+        assert_eq!(Family::PortCertificate.as_str(),    "port-certificate");
+        assert_eq!(Family::SubdomainCertificate.as_str(), "subdomain-certificate");
+        assert_eq!(Family::DnsRecord.as_str(),          "dns-record");
+        assert_eq!(Family::Person.as_str(),             "person");
+        assert_eq!(Family::PersonPhonenumber.as_str(),  "person-phonenumber");
+        assert_eq!(Family::OrganizationDomain.as_str(), "organization-domain");
+        assert_eq!(Family::Asn.as_str(),                "asn");
+        assert_eq!(Family::AsnIpaddr.as_str(),          "asn-ipaddr");
     }
 
     #[test]
@@ -723,5 +1025,25 @@ mod tests {
         assert_eq!(Table::Images.as_str(),              "images");
         assert_eq!(Table::Ports.as_str(),               "ports");
         assert_eq!(Table::Netblocks.as_str(),           "netblocks");
+        assert_eq!(Table::Certificates.as_str(),        "certificates");
+        assert_eq!(Table::PortCertificates.as_str(),    "port_certificates");
+        assert_eq!(Table::SubdomainCertificates.as_str(), "subdomain_certificates");
+        assert_eq!(Table::DnsRecords.as_str(),          "dns_records");
+        assert_eq!(Table::Persons.as_str(),             "persons");
+        assert_eq!(Table::PersonPhonenumbers.as_str(),  "person_phonenumbers");
+        assert_eq!(Table::OrganizationDomains.as_str(), "organization_domains");
+        assert_eq!(Table::Asns.as_str(),                "asns");
+        assert_eq!(Table::AsnIpaddrs.as_str(),          "asn_ipaddrs");
+    }
+
+    #[test]
+    fn test_family_table() {
+        assert_eq!(Table::from(&Family::Subdomain),     Table::Subdomains);
+        assert_eq!(Table::from(&Family::NetworkDevice), Table::NetworkDevices);
+        assert_eq!(Table::from(&Family::Cryptoaddr),    Table::Cryptoaddrs);
+        assert_eq!(Table::from(&Family::Certificate),   Table::Certificates);
+        assert_eq!(Table::from(&Family::PortCertificate), Table::PortCertificates);
+        assert_eq!(Table::from(&Family::DnsRecord),     Table::DnsRecords);
+        assert_eq!(Table::from(&Family::PersonEmail),   Table::PersonEmails);
     }
 }
@@ -0,0 +1,659 @@
+use crate::db::{Database, Previous, Table};
+use crate::db::filter::{self, ColumnType};
+use crate::db::history::History;
+use crate::engine::Module;
+use crate::errors::*;
+use crate::schema::*;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Binary, Bool, Double, Integer, Nullable, Text, Timestamp};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use strum_macros::{EnumString, IntoStaticStr};
+
+
+/// A single invocation of a module
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="runs"]
+pub struct Run {
+    pub id: i32,
+    pub module: String,
+    pub version: String,
+    pub started: NaiveDateTime,
+}
+
+impl Run {
+    pub fn create(db: &Database, my_module: &Module) -> Result<Run> {
+        use crate::schema::runs::dsl::*;
+
+        NewRun {
+            module: &my_module.canonical(),
+            version: my_module.version(),
+            started: Utc::now().naive_utc(),
+        }.insert(db)?;
+
+        runs.order(id.desc())
+            .first::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    pub fn by_id(db: &Database, my_id: i32) -> Result<Run> {
+        use crate::schema::runs::dsl::*;
+
+        runs.filter(id.eq(my_id))
+            .first::<Self>(db.db())
+            .optional()?
+            .ok_or_else(|| format_err!("Run #{} doesn't exist", my_id))
+    }
+
+    pub fn list(db: &Database) -> Result<Vec<Run>> {
+        use crate::schema::runs::dsl::*;
+
+        runs.order_by(id.asc())
+            .load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    /// Number of changes of this run with a specific action
+    pub fn count(&self, db: &Database, my_action: Action) -> Result<usize> {
+        use crate::schema::provenance::dsl::*;
+
+        provenance
+            .filter(run_id.eq(self.id))
+            .filter(action.eq(my_action.as_str()))
+            .count()
+            .get_result::<i64>(db.db())
+            .map(|x| x as usize)
+            .map_err(Error::from)
+    }
+
+    /// Everything that needs to be done to undo this run, newest changes first.
+    /// Entities that have been deleted in the meantime are skipped.
+    pub fn revert_plan(&self, db: &Database) -> Result<Vec<Revert>> {
+        use crate::schema::provenance::dsl::*;
+
+        let changes = provenance
+            .filter(run_id.eq(self.id))
+            .order_by(id.desc())
+            .load::<Provenance>(db.db())?;
+
+        let mut reverts = Vec::new();
+        for revert in plan(changes)? {
+            let (my_family, my_key) = revert.entity();
+            if exists(db, my_family, my_key)? {
+                reverts.push(revert);
+            }
+        }
+        Ok(reverts)
+    }
+
+    /// Everything a revert plan would touch that doesn't belong to this run.
+    /// Deleting an entity also deletes everything that refers to it and
+    /// restoring a field overwrites the values later runs have set.
+    pub fn conflicts(&self, db: &Database, plan: &[Revert]) -> Result<Vec<Conflict>> {
+        let deleted = plan.iter()
+            .filter(|r| matches!(r, Revert::Delete(..)))
+            .map(Revert::entity)
+            .collect::<HashSet<_>>();
+
+        let mut conflicts = Vec::new();
+        let mut cascaded = HashSet::new();
+        for revert in plan {
+            let (family, key) = revert.entity();
+            if let Some(newer) = self.newer_run(db, family, key)? {
+                conflicts.push(Conflict::Newer(family, key, newer));
+            }
+
+            if let Revert::Delete(..) = revert {
+                let mut queue = vec![(family, key)];
+                while let Some((parent, parent_key)) = queue.pop() {
+                    for (child, column) in filter::dependents(parent) {
+                        let rows = diesel::sql_query(format!("SELECT id FROM {:?} WHERE {:?} = ?", child.as_str(), column))
+                            .bind::<Integer, _>(parent_key)
+                            .load::<RowId>(db.db())?;
+
+                        for row in rows {
+                            let entity = (child, row.id);
+                            if !deleted.contains(&entity) && cascaded.insert(entity) {
+                                conflicts.push(Conflict::Cascade(child, row.id));
+                                queue.push(entity);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(conflicts)
+    }
+
+    /// The first run after this one that inserted or updated an entity
+    fn newer_run(&self, db: &Database, my_family: Table, my_key: i32) -> Result<Option<i32>> {
+        use crate::schema::provenance::dsl::*;
+
+        provenance
+            .select(run_id)
+            .filter(family.eq(my_family.as_str()))
+            .filter(key.eq(my_key))
+            .filter(run_id.gt(self.id))
+            .order_by(run_id.asc())
+            .first::<i32>(db.db())
+            .optional()
+            .map_err(Error::from)
+    }
+
+    /// Apply a revert plan and remove the run
+    pub fn revert(&self, db: &Database, plan: &[Revert]) -> Result<()> {
+        let source = format!("revert #{}", self.id);
+        db.db().transaction::<_, Error, _>(|| {
+            for revert in plan {
+                revert.apply(db, &source)?;
+            }
+            diesel::delete(self)
+                .execute(db.db())?;
+            Ok(())
+        })
+    }
+}
+
+#[derive(Insertable)]
+#[table_name="runs"]
+pub struct NewRun<'a> {
+    pub module: &'a str,
+    pub version: &'a str,
+    pub started: NaiveDateTime,
+}
+
+impl<'a> NewRun<'a> {
+    pub fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(runs::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    /// Reuse an identical run if it has been imported before, insert it otherwise
+    pub fn get_or_insert(&self, db: &Database) -> Result<i32> {
+        use crate::schema::runs::dsl::*;
+
+        let query = runs
+            .filter(module.eq(self.module))
+            .filter(version.eq(self.version))
+            .filter(started.eq(self.started))
+            .select(id);
+
+        if let Some(existing) = query.clone().first::<i32>(db.db()).optional()? {
+            return Ok(existing);
+        }
+
+        self.insert(db)?;
+        query.first::<i32>(db.db())
+            .map_err(Error::from)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(EnumString, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum Action {
+    Insert,
+    Update,
+}
+
+impl Action {
+    #[inline(always)]
+    pub fn as_str(&self) -> &'static str {
+        self.into()
+    }
+}
+
+/// Records which run inserted or updated an entity
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="provenance"]
+pub struct Provenance {
+    pub id: i32,
+    pub family: String,
+    pub key: i32,
+    pub run_id: i32,
+    pub action: String,
+    pub time: NaiveDateTime,
+    pub previous: Option<String>,
+}
+
+impl Provenance {
+    pub fn record(db: &Database, family: Table, key: i32, run_id: i32, action: Action, previous: Option<&Previous>) -> Result<()> {
+        let previous = previous
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        NewProvenance {
+            family: family.as_str(),
+            key,
+            run_id,
+            action: action.as_str(),
+            time: Utc::now().naive_utc(),
+            previous,
+        }.insert(db)
+    }
+
+    /// Every insert and update of an entity, oldest first
+    pub fn list(db: &Database, my_family: Table, my_key: i32) -> Result<Vec<(Provenance, Run)>> {
+        use crate::schema::provenance::dsl::*;
+
+        provenance
+            .inner_join(runs::table)
+            .filter(family.eq(my_family.as_str()))
+            .filter(key.eq(my_key))
+            .order_by((time.asc(), id.asc()))
+            .load::<(Self, Run)>(db.db())
+            .map_err(Error::from)
+    }
+
+    /// Every insert and update of several entities of a table, oldest first
+    pub fn list_many(db: &Database, my_family: Table, keys: &[i32]) -> Result<HashMap<i32, Vec<(Provenance, Run)>>> {
+        use crate::schema::provenance::dsl::*;
+
+        let mut entities = HashMap::<_, Vec<_>>::new();
+        // stay below the bind parameter limit of older sqlite versions
+        for chunk in keys.chunks(900) {
+            let changes = provenance
+                .inner_join(runs::table)
+                .filter(family.eq(my_family.as_str()))
+                .filter(key.eq_any(chunk))
+                .order_by((time.asc(), id.asc()))
+                .load::<(Self, Run)>(db.db())?;
+
+            for (change, run) in changes {
+                entities.entry(change.key)
+                    .or_default()
+                    .push((change, run));
+            }
+        }
+        Ok(entities)
+    }
+
+    /// Every recorded insert and update, used for exports
+    pub fn list_all(db: &Database) -> Result<Vec<Provenance>> {
+        use crate::schema::provenance::dsl::*;
+
+        provenance
+            .order_by(id.asc())
+            .load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    pub fn to_term_str(&self, run: &Run) -> String {
+        let action = match self.action.parse::<Action>() {
+            Ok(Action::Insert) => "inserted",
+            Ok(Action::Update) => "updated",
+            Err(_) => self.action.as_str(),
+        };
+        format!("{} by {} {} (run #{}) at {}", action, run.module, run.version, run.id, self.time)
+    }
+}
+
+#[derive(Insertable)]
+#[table_name="provenance"]
+pub struct NewProvenance<'a> {
+    pub family: &'a str,
+    pub key: i32,
+    pub run_id: i32,
+    pub action: &'a str,
+    pub time: NaiveDateTime,
+    pub previous: Option<String>,
+}
+
+impl<'a> NewProvenance<'a> {
+    pub fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(provenance::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    pub fn exists(&self, db: &Database) -> Result<bool> {
+        use crate::schema::provenance::dsl::*;
+
+        let n = provenance
+            .filter(family.eq(self.family))
+            .filter(key.eq(self.key))
+            .filter(run_id.eq(self.run_id))
+            .filter(action.eq(self.action))
+            .filter(time.eq(self.time))
+            .count()
+            .get_result::<i64>(db.db())?;
+        Ok(n > 0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Revert {
+    /// Delete an entity that has been added by the run
+    Delete(Table, i32),
+    /// Set the fields that have been changed by the run back to their previous values
+    Restore(Table, i32, Previous),
+}
+
+impl Revert {
+    pub fn entity(&self) -> (Table, i32) {
+        match self {
+            Revert::Delete(family, key) => (*family, *key),
+            Revert::Restore(family, key, _) => (*family, *key),
+        }
+    }
+
+    fn apply(&self, db: &Database, source: &str) -> Result<()> {
+        match self {
+            Revert::Delete(family, key) => {
+                db.delete_generic(*family, *key)?;
+            },
+            Revert::Restore(family, key, previous) => {
+                let current = db.json_generic(*family, *key)?;
+                for (column, value) in previous {
+                    restore(db, *family, *key, column, value)?;
+                }
+                History::record_restore(db, *family, *key, &current, previous, source)?;
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Something a revert would do to data that hasn't been added by the run
+#[derive(Debug, PartialEq)]
+pub enum Conflict {
+    /// Deleted together with an entity of the run because it refers to it
+    Cascade(Table, i32),
+    /// The entity has been inserted or updated by a later run
+    Newer(Table, i32, i32),
+}
+
+#[derive(QueryableByName)]
+struct RowId {
+    #[sql_type="Integer"]
+    id: i32,
+}
+
+fn exists(db: &Database, family: Table, key: i32) -> Result<bool> {
+    let rows = diesel::sql_query(format!("SELECT id FROM {:?} WHERE id = ?", family.as_str()))
+        .bind::<Integer, _>(key)
+        .load::<RowId>(db.db())?;
+    Ok(!rows.is_empty())
+}
+
+fn plan(changes: Vec<Provenance>) -> Result<Vec<Revert>> {
+    let inserted = changes.iter()
+        .filter(|c| c.action == Action::Insert.as_str())
+        .map(|c| (c.family.clone(), c.key))
+        .collect::<HashSet<_>>();
+
+    let mut plan = Vec::new();
+    for change in changes {
+        let family = change.family.parse::<Table>()?;
+        match change.action.parse::<Action>()? {
+            Action::Insert => plan.push(Revert::Delete(family, change.key)),
+            // entities that are going to be deleted don't need to be restored
+            Action::Update if inserted.contains(&(change.family.clone(), change.key)) => (),
+            Action::Update => {
+                if let Some(previous) = change.previous {
+                    let previous = serde_json::from_str(&previous)?;
+                    plan.push(Revert::Restore(family, change.key, previous));
+                }
+            },
+        }
+    }
+    Ok(plan)
+}
+
+/// Set a column of an entity back to a value that has been captured as json
+fn restore(db: &Database, family: Table, key: i32, column: &str, value: &Value) -> Result<()> {
+    let ty = filter::column_type(family, column)
+        .ok_or_else(|| format_err!("Unknown column {:?} in {}", column, family.as_str()))?;
+
+    let query = diesel::sql_query(format!("UPDATE {:?} SET {:?} = ? WHERE id = ?", family.as_str(), column));
+    match (ty, value) {
+        (_, Value::Null) => query.bind::<Nullable<Text>, _>(None::<String>)
+            .bind::<Integer, _>(key)
+            .execute(db.db())?,
+        (ColumnType::Timestamp, Value::String(time)) => {
+            let time = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M:%S%.f")?;
+            query.bind::<Timestamp, _>(time)
+                .bind::<Integer, _>(key)
+                .execute(db.db())?
+        },
+        (ColumnType::Binary, value) => {
+            let bytes = serde_json::from_value::<Vec<u8>>(value.clone())?;
+            query.bind::<Binary, _>(bytes)
+                .bind::<Integer, _>(key)
+                .execute(db.db())?
+        },
+        (_, Value::Bool(value)) => query.bind::<Bool, _>(*value)
+            .bind::<Integer, _>(key)
+            .execute(db.db())?,
+        (_, Value::Number(value)) => if let Some(value) = value.as_i64() {
+            query.bind::<BigInt, _>(value)
+                .bind::<Integer, _>(key)
+                .execute(db.db())?
+        } else {
+            let value = value.as_f64()
+                .ok_or_else(|| format_err!("Invalid number for {:?}: {}", column, value))?;
+            query.bind::<Double, _>(value)
+                .bind::<Integer, _>(key)
+                .execute(db.db())?
+        },
+        (_, Value::String(value)) => query.bind::<Text, _>(value)
+            .bind::<Integer, _>(key)
+            .execute(db.db())?,
+        (_, value) => bail!("Unsupported value for {:?}: {}", column, value),
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::*;
+    use serde_json::json;
+
+    fn change(id: i32, family: &str, key: i32, action: Action, previous: Option<Value>) -> Provenance {
+        Provenance {
+            id,
+            family: family.to_string(),
+            key,
+            run_id: 1,
+            action: action.as_str().to_string(),
+            time: NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+            previous: previous.map(|x| x.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_revert_plan() {
+        let plan = plan(vec![
+            change(4, "ipaddrs", 3, Action::Update, Some(json!({"asn": null}))),
+            change(3, "subdomains", 2, Action::Update, Some(json!({"resolvable": true}))),
+            change(2, "subdomains", 2, Action::Insert, None),
+            change(1, "ipaddrs", 3, Action::Update, Some(json!({"asn": 13335, "as_org": "Cloudflare"}))),
+        ]).unwrap();
+
+        let restore = |x: Value| match x {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        assert_eq!(plan, vec![
+            Revert::Restore(Table::Ipaddrs, 3, restore(json!({"asn": null}))),
+            Revert::Delete(Table::Subdomains, 2),
+            Revert::Restore(Table::Ipaddrs, 3, restore(json!({"asn": 13335, "as_org": "Cloudflare"}))),
+        ]);
+    }
+
+    fn new_ipaddr(value: &str) -> Insert {
+        Insert::IpAddr(NewIpAddr {
+            family: String::from("4"),
+            value: value.to_string(),
+            continent: None,
+            continent_code: None,
+            country: None,
+            country_code: None,
+            city: None,
+            latitude: None,
+            longitude: None,
+            asn: None,
+            as_org: None,
+            description: None,
+            reverse_dns: None,
+            unscoped: false,
+        })
+    }
+
+    fn new_run(db: &Database) -> Run {
+        NewRun {
+            module: "kpcyrd/asn",
+            version: "0.1.0",
+            started: Utc::now().naive_utc(),
+        }.insert(db).unwrap();
+        Run::list(db).unwrap().pop().unwrap()
+    }
+
+    #[test]
+    fn test_revert_link() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let (_, asn_id) = db.insert_generic(Insert::Asn(NewAsn {
+            value: String::from("AS13335"),
+            as_org: None,
+            unscoped: false,
+        })).unwrap().unwrap();
+        let (_, ip_addr_id) = db.insert_generic(new_ipaddr("1.1.1.1")).unwrap().unwrap();
+        let (_, link_id) = db.insert_generic(Insert::AsnIpAddr(NewAsnIpAddr {
+            asn_id,
+            ip_addr_id,
+        })).unwrap().unwrap();
+
+        let run = new_run(&db);
+        Provenance::record(&db, Table::AsnIpaddrs, link_id, run.id, Action::Insert, None).unwrap();
+
+        let plan = run.revert_plan(&db).unwrap();
+        assert_eq!(plan, vec![Revert::Delete(Table::AsnIpaddrs, link_id)]);
+        let label = db.label_generic(Table::AsnIpaddrs, link_id).unwrap();
+        assert_eq!(label.as_deref(), Some("\"AS13335\" -> \"1.1.1.1\""));
+        assert_eq!(run.conflicts(&db, &plan).unwrap(), vec![]);
+
+        run.revert(&db, &plan).unwrap();
+        assert_eq!(db.label_generic(Table::AsnIpaddrs, link_id).unwrap(), None);
+        assert!(db.label_generic(Table::Ipaddrs, ip_addr_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_revert_records_history() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let (_, ip_addr_id) = db.insert_generic(new_ipaddr("1.1.1.1")).unwrap().unwrap();
+        restore(&db, Table::Ipaddrs, ip_addr_id, "asn", &json!(13335)).unwrap();
+
+        let run = new_run(&db);
+        let previous = match json!({"asn": null}) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        Provenance::record(&db, Table::Ipaddrs, ip_addr_id, run.id, Action::Update, Some(&previous)).unwrap();
+
+        let plan = run.revert_plan(&db).unwrap();
+        run.revert(&db, &plan).unwrap();
+        assert_eq!(IpAddr::by_id(&db, ip_addr_id).unwrap().asn, None);
+
+        let history = History::list(&db, Table::Ipaddrs, ip_addr_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].field, "asn");
+        assert_eq!(history[0].old, "13335");
+        assert_eq!(history[0].new, "null");
+        assert_eq!(history[0].source, Some(format!("revert #{}", run.id)));
+    }
+
+    #[test]
+    fn test_revert_deleted_entity() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let run = new_run(&db);
+        let (_, inserted_id) = db.insert_generic(new_ipaddr("192.0.2.1")).unwrap().unwrap();
+        Provenance::record(&db, Table::Ipaddrs, inserted_id, run.id, Action::Insert, None).unwrap();
+        let (_, updated_id) = db.insert_generic(new_ipaddr("192.0.2.2")).unwrap().unwrap();
+        let previous = match json!({"asn": null}) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        Provenance::record(&db, Table::Ipaddrs, updated_id, run.id, Action::Update, Some(&previous)).unwrap();
+
+        // deleted by hand after the run
+        db.delete_generic(Table::Ipaddrs, updated_id).unwrap();
+
+        let plan = run.revert_plan(&db).unwrap();
+        assert_eq!(plan, vec![Revert::Delete(Table::Ipaddrs, inserted_id)]);
+        run.revert(&db, &plan).unwrap();
+        assert_eq!(db.label_generic(Table::Ipaddrs, inserted_id).unwrap(), None);
+        assert!(Run::by_id(&db, run.id).is_err());
+    }
+
+    #[test]
+    fn test_list_many() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let run = new_run(&db);
+        let (_, first) = db.insert_generic(new_ipaddr("192.0.2.1")).unwrap().unwrap();
+        let (_, second) = db.insert_generic(new_ipaddr("192.0.2.2")).unwrap().unwrap();
+        Provenance::record(&db, Table::Ipaddrs, first, run.id, Action::Insert, None).unwrap();
+        Provenance::record(&db, Table::Ipaddrs, second, run.id, Action::Insert, None).unwrap();
+        Provenance::record(&db, Table::Ipaddrs, first, run.id, Action::Update, None).unwrap();
+
+        let entities = Provenance::list_many(&db, Table::Ipaddrs, &[first, second]).unwrap();
+        let actions = |key| entities[&key].iter()
+            .map(|(change, _)| change.action.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(actions(first), vec!["insert", "update"]);
+        assert_eq!(actions(second), vec!["insert"]);
+        assert!(Provenance::list_many(&db, Table::Subdomains, &[first]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_revert_conflicts() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let first = new_run(&db);
+        let (_, ip_addr_id) = db.insert_generic(new_ipaddr("192.0.2.1")).unwrap().unwrap();
+        Provenance::record(&db, Table::Ipaddrs, ip_addr_id, first.id, Action::Insert, None).unwrap();
+
+        let second = new_run(&db);
+        let (_, port_id) = db.insert_generic(Insert::Port(NewPort {
+            ip_addr_id,
+            value: String::from("tcp/192.0.2.1:443"),
+            ip_addr: String::from("192.0.2.1"),
+            port: 443,
+            protocol: String::from("tcp"),
+            status: None,
+            banner: None,
+            service: None,
+            version: None,
+            unscoped: false,
+        })).unwrap().unwrap();
+        Provenance::record(&db, Table::Ports, port_id, second.id, Action::Insert, None).unwrap();
+        let previous = match json!({"asn": null}) {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+        Provenance::record(&db, Table::Ipaddrs, ip_addr_id, second.id, Action::Update, Some(&previous)).unwrap();
+
+        let plan = first.revert_plan(&db).unwrap();
+        assert_eq!(first.conflicts(&db, &plan).unwrap(), vec![
+            Conflict::Newer(Table::Ipaddrs, ip_addr_id, second.id),
+            Conflict::Cascade(Table::Ports, port_id),
+        ]);
+
+        let plan = second.revert_plan(&db).unwrap();
+        assert_eq!(second.conflicts(&db, &plan).unwrap(), vec![]);
+    }
+}
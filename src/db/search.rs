@@ -0,0 +1,119 @@
+use crate::db::Database;
+use crate::errors::*;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Text};
+use serde::Serialize;
+
+
+/// Every table that has a full-text index, together with the column that is
+/// used as label. The indexes are named `search_<table>` and are kept in sync
+/// by triggers, see the search migration.
+pub const INDEXES: &[(&str, &str)] = &[
+    ("domains", "value"),
+    ("subdomains", "value"),
+    ("ipaddrs", "value"),
+    ("urls", "value"),
+    ("emails", "value"),
+    ("phonenumbers", "value"),
+    ("devices", "value"),
+    ("networks", "value"),
+    ("accounts", "value"),
+    ("breaches", "value"),
+    ("images", "value"),
+    ("ports", "value"),
+    ("netblocks", "value"),
+    ("cryptoaddrs", "value"),
+    ("certificates", "value"),
+    ("dns_records", "value"),
+    ("persons", "value"),
+    ("organizations", "value"),
+    ("asns", "value"),
+    ("activity", "topic"),
+];
+
+#[derive(Debug, Serialize, QueryableByName)]
+pub struct Hit {
+    #[sql_type="Integer"]
+    pub id: i32,
+    #[sql_type="Text"]
+    pub label: String,
+    /// The part of the entity that matched
+    #[sql_type="Text"]
+    pub snippet: String,
+}
+
+/// All hits in a single table
+#[derive(Debug, Serialize)]
+pub struct Group {
+    pub family: &'static str,
+    pub hits: Vec<Hit>,
+}
+
+/// Turn free text into a fts5 query. Every word is quoted, so the query syntax
+/// of fts5 doesn't need to be known, and is matched as a prefix.
+fn fts_query(text: &str) -> Option<String> {
+    let words = text.split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+/// Search every index for entities that contain all words of the text. The
+/// matching words in the snippet are wrapped with the given markers.
+pub fn search(db: &Database, text: &str, limit: i64, markers: (&str, &str)) -> Result<Vec<Group>> {
+    let query = fts_query(text)
+        .ok_or_else(|| format_err!("Search text can't be empty"))?;
+
+    let mut groups = Vec::new();
+    for &(family, label) in INDEXES {
+        let hits = diesel::sql_query(format!("SELECT rowid AS id, {1} AS label, \
+                snippet(search_{0}, -1, ?, ?, '...', 8) AS snippet \
+                FROM search_{0} WHERE search_{0} MATCH ? ORDER BY rank LIMIT ?", family, label))
+            .bind::<Text, _>(markers.0)
+            .bind::<Text, _>(markers.1)
+            .bind::<Text, _>(&query)
+            .bind::<BigInt, _>(limit)
+            .load::<Hit>(db.db())?;
+
+        if !hits.is_empty() {
+            groups.push(Group {
+                family,
+                hits,
+            });
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Recreate every index from the tables they belong to
+pub fn rebuild(db: &SqliteConnection) -> Result<()> {
+    for &(family, _) in INDEXES {
+        diesel::sql_query(format!("INSERT INTO search_{0} (search_{0}) VALUES ('rebuild')", family))
+            .execute(db)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("acme-vpn").unwrap(), "\"acme-vpn\"*");
+        assert_eq!(fts_query(" John  Doe ").unwrap(), "\"John\"* \"Doe\"*");
+        assert_eq!(fts_query("say \"hi\"").unwrap(), "\"say\"* \"\"\"hi\"\"\"*");
+    }
+
+    #[test]
+    fn test_fts_query_empty() {
+        assert!(fts_query("").is_none());
+        assert!(fts_query("  \t").is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use crate::db::{Database, Table};
+use crate::errors::*;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Nullable, Timestamp};
+
+
+impl Database {
+    /// Mark an entity as confirmed right now, this sets first_observed if it's the
+    /// first time and always bumps last_observed
+    pub fn seen(&self, table: Table, id: i32) -> Result<()> {
+        let now = Utc::now().naive_utc();
+        self.merge_seen(table, id, Some(now), Some(now))
+    }
+
+    /// Widen the first_observed/last_observed range of an entity, the range is never
+    /// narrowed and missing values are ignored
+    pub fn merge_seen(&self, table: Table, id: i32, first: Option<NaiveDateTime>, last: Option<NaiveDateTime>) -> Result<()> {
+        // min() and max() return null if any argument is null
+        diesel::sql_query(format!("UPDATE {:?} SET \
+            first_observed = COALESCE(min(first_observed, ?1), first_observed, ?1), \
+            last_observed = COALESCE(max(last_observed, ?2), last_observed, ?2) \
+            WHERE id = ?3", table.as_str()))
+            .bind::<Nullable<Timestamp>, _>(first)
+            .bind::<Nullable<Timestamp>, _>(last)
+            .bind::<Integer, _>(id)
+            .execute(self.db())
+            .context("Failed to update seen timestamps")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use crate::autonoscope::RuleSet;
+use crate::blobs::BlobStorage;
+use crate::errors::*;
+use crate::workspaces::Snapshot;
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use std::env;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::{search, Database};
+
+#[derive(QueryableByName)]
+struct Name {
+    #[sql_type="Text"]
+    name: String,
+}
+
+fn path_str(path: &Path) -> Result<&str> {
+    path.to_str()
+        .ok_or_else(|| format_err!("Failed to convert path to utf-8"))
+}
+
+/// Every table that holds data, this excludes sqlite internals, the migration
+/// state and the search indexes, which are rebuilt instead
+fn tables(db: &SqliteConnection) -> Result<Vec<String>> {
+    let tables = diesel::sql_query("SELECT name FROM main.sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'search\\_%' ESCAPE '\\' AND name != '__diesel_schema_migrations'")
+        .load::<Name>(db)?;
+    Ok(tables.into_iter().map(|x| x.name).collect())
+}
+
+fn columns(db: &SqliteConnection, table: &str) -> Result<Vec<String>> {
+    let columns = diesel::sql_query("SELECT name FROM pragma_table_info(?)")
+        .bind::<Text, _>(table)
+        .load::<Name>(db)?;
+    Ok(columns.into_iter().map(|x| format!("{:?}", x.name)).collect())
+}
+
+/// A temporary copy of a snapshot, this is used to read a snapshot without
+/// migrating it. The copy is removed when it's dropped.
+pub struct SnapshotCopy {
+    db: Option<Database>,
+    path: PathBuf,
+}
+
+impl Deref for SnapshotCopy {
+    type Target = Database;
+
+    fn deref(&self) -> &Database {
+        self.db.as_ref()
+            .expect("Database has been closed")
+    }
+}
+
+impl Drop for SnapshotCopy {
+    fn drop(&mut self) {
+        // close the connection before its files are removed
+        self.db.take();
+        for suffix in &["", "-wal", "-shm"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+            match fs::remove_file(&path) {
+                Ok(_) => (),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+                Err(err) => debug!("Failed to remove {:?}: {}", path, err),
+            }
+        }
+    }
+}
+
+impl Database {
+    /// Take a consistent copy of the database and the blobs of this workspace
+    pub fn snapshot(&self, name: &str) -> Result<Snapshot> {
+        let snapshot = self.workspace.new_snapshot(name)?;
+        if let Err(err) = self.write_snapshot(&snapshot) {
+            snapshot.discard()?;
+            return Err(err);
+        }
+        Ok(snapshot)
+    }
+
+    fn write_snapshot(&self, snapshot: &Snapshot) -> Result<()> {
+        diesel::sql_query("VACUUM INTO ?")
+            .bind::<Text, _>(path_str(&snapshot.db_path())?)
+            .execute(&self.db)
+            .context("Failed to copy database")?;
+
+        let blobs = BlobStorage::workspace(&self.workspace)?;
+        snapshot.blobs().sync_from(&blobs)
+    }
+
+    /// Open a snapshot, snapshots of older versions are migrated first
+    pub fn open_snapshot(snapshot: &Snapshot) -> Result<Database> {
+        Database::establish_path(snapshot.workspace().clone(), snapshot.db_path())
+    }
+
+    /// Open a snapshot without modifying it, older versions are migrated in a
+    /// temporary copy
+    pub fn open_snapshot_copy(snapshot: &Snapshot) -> Result<SnapshotCopy> {
+        // the same snapshot might be opened twice
+        static COPIES: AtomicUsize = AtomicUsize::new(0);
+        let n = COPIES.fetch_add(1, Ordering::SeqCst);
+
+        let name = format!("sn0int-{}-{}-{}-{}.sqlite", snapshot.workspace().as_str(), snapshot.name(), process::id(), n);
+        let mut copy = SnapshotCopy {
+            db: None,
+            path: env::temp_dir().join(name),
+        };
+        fs::copy(snapshot.db_path(), &copy.path)
+            .context("Failed to copy snapshot")?;
+
+        copy.db = Some(Database::establish_path(snapshot.workspace().clone(), copy.path.clone())?);
+        Ok(copy)
+    }
+
+    /// Roll back the database and the blobs of this workspace to a snapshot.
+    /// This works on the open connection, so the workspace doesn't need to be
+    /// closed first.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<()> {
+        // make sure the snapshot has the same schema
+        Database::open_snapshot(snapshot)
+            .context("Failed to open snapshot")?;
+
+        self.restore_from(&snapshot.db_path())?;
+        self.autonoscope = RuleSet::load(&self.db)?;
+
+        let blobs = BlobStorage::workspace(&self.workspace)?;
+        blobs.sync_from(&snapshot.blobs())
+    }
+
+    fn restore_from(&self, path: &Path) -> Result<()> {
+        let path = path_str(path)?;
+        diesel::sql_query("ATTACH DATABASE ? AS snapshot")
+            .bind::<Text, _>(path)
+            .execute(&self.db)?;
+        // this can't be changed inside a transaction
+        self.db.execute("PRAGMA foreign_keys = OFF")?;
+
+        let result = self.db.transaction::<_, Error, _>(|| {
+            let tables = tables(&self.db)?;
+            // clear everything before restoring anything, deleting an entity
+            // triggers the removal of its tags, notes and ttls
+            for table in &tables {
+                diesel::sql_query(format!("DELETE FROM main.{:?}", table))
+                    .execute(&self.db)?;
+            }
+            for table in &tables {
+                let columns = columns(&self.db, table)?.join(", ");
+                diesel::sql_query(format!("INSERT INTO main.{0:?} ({1}) SELECT {1} FROM snapshot.{0:?}", table, columns))
+                    .execute(&self.db)?;
+            }
+            search::rebuild(&self.db)
+        });
+
+        self.db.execute("PRAGMA foreign_keys = ON")?;
+        self.db.execute("DETACH DATABASE snapshot")?;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Table;
+    use crate::db::tags::Tag;
+    use crate::models::*;
+
+    #[test]
+    fn test_restore_keeps_tags() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let (_, domain_id) = db.insert_generic(Insert::Domain(NewDomain {
+            value: String::from("example.com"),
+            unscoped: false,
+        })).unwrap().unwrap();
+        let (_, certificate_id) = db.insert_generic(Insert::Certificate(NewCertificate {
+            value: String::from("5f0b6f3c1e2d4a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a5b"),
+            subject: None,
+            issuer: None,
+            sans: None,
+            serial: None,
+            key_type: None,
+            not_before: None,
+            not_after: None,
+            unscoped: false,
+        })).unwrap().unwrap();
+        let (_, asn_id) = db.insert_generic(Insert::Asn(NewAsn {
+            value: String::from("13335"),
+            as_org: None,
+            unscoped: false,
+        })).unwrap().unwrap();
+        Tag::add(&db, Table::Domains, domain_id, "keep").unwrap();
+        Tag::add(&db, Table::Certificates, certificate_id, "keep").unwrap();
+        Tag::add(&db, Table::Asns, asn_id, "keep").unwrap();
+
+        let snapshot = dir.path().join("snapshot.sqlite");
+        diesel::sql_query("VACUUM INTO ?")
+            .bind::<Text, _>(path_str(&snapshot).unwrap())
+            .execute(db.db())
+            .unwrap();
+
+        Tag::add(&db, Table::Asns, asn_id, "drop").unwrap();
+        db.restore_from(&snapshot).unwrap();
+
+        let tags = Tag::list(&db).unwrap()
+            .into_iter()
+            .map(|tag| (tag.family, tag.key, tag.name))
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec![
+            (String::from("domains"), domain_id, String::from("keep")),
+            (String::from("certificates"), certificate_id, String::from("keep")),
+            (String::from("asns"), asn_id, String::from("keep")),
+        ]);
+        assert!(Certificate::by_id(&db, certificate_id).is_ok());
+        assert!(Asn::by_id(&db, asn_id).is_ok());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::db::{Database, Table};
+use crate::errors::*;
+use crate::fmt::colors::*;
+use crate::schema::*;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="tags"]
+pub struct Tag {
+    pub id: i32,
+    pub family: String,
+    pub key: i32,
+    pub name: String,
+}
+
+impl Tag {
+    pub fn validate(name: &str) -> Result<()> {
+        if name.is_empty() {
+            bail!("Tag can't be empty");
+        }
+        if name.contains(|c: char| c.is_whitespace() || c.is_control()) {
+            bail!("Tag can't contain whitespace: {:?}", name);
+        }
+        Ok(())
+    }
+
+    /// Returns false if the entity already had this tag
+    pub fn add(db: &Database, family: Table, key: i32, name: &str) -> Result<bool> {
+        Self::validate(name)?;
+        let n = diesel::insert_or_ignore_into(tags::table)
+            .values(&NewTag {
+                family: family.as_str(),
+                key,
+                name,
+            })
+            .execute(db.db())?;
+        Ok(n > 0)
+    }
+
+    /// Returns false if the entity didn't have this tag
+    pub fn remove(db: &Database, my_family: Table, my_key: i32, my_name: &str) -> Result<bool> {
+        use crate::schema::tags::dsl::*;
+
+        let n = diesel::delete(tags
+                .filter(family.eq(my_family.as_str()))
+                .filter(key.eq(my_key))
+                .filter(name.eq(my_name)))
+            .execute(db.db())?;
+        Ok(n > 0)
+    }
+
+    pub fn list(db: &Database) -> Result<Vec<Tag>> {
+        use crate::schema::tags::dsl::*;
+
+        tags.load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    /// The tags of an entity, sorted by name
+    pub fn names(db: &Database, my_family: Table, my_key: i32) -> Result<Vec<String>> {
+        use crate::schema::tags::dsl::*;
+
+        tags
+            .filter(family.eq(my_family.as_str()))
+            .filter(key.eq(my_key))
+            .select(name)
+            .order_by(name.asc())
+            .load::<String>(db.db())
+            .map_err(Error::from)
+    }
+
+    /// All tags that are in use and how many entities have them
+    pub fn count(db: &Database) -> Result<BTreeMap<String, usize>> {
+        let counts = diesel::sql_query("SELECT name, COUNT(*) AS count FROM tags GROUP BY name")
+            .load::<TagCount>(db.db())?;
+
+        Ok(counts.into_iter()
+            .map(|x| (x.name, x.count as usize))
+            .collect())
+    }
+}
+
+#[derive(QueryableByName)]
+struct TagCount {
+    #[sql_type="Text"]
+    name: String,
+    #[sql_type="BigInt"]
+    count: i64,
+}
+
+#[derive(Insertable)]
+#[table_name="tags"]
+pub struct NewTag<'a> {
+    pub family: &'a str,
+    pub key: i32,
+    pub name: &'a str,
+}
+
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="notes"]
+pub struct Note {
+    pub id: i32,
+    pub family: String,
+    pub key: i32,
+    pub body: String,
+    pub time: NaiveDateTime,
+}
+
+impl Note {
+    pub fn add(db: &Database, family: Table, key: i32, body: &str) -> Result<()> {
+        if body.trim().is_empty() {
+            bail!("Note can't be empty");
+        }
+        NewNote {
+            family: family.as_str(),
+            key,
+            body,
+            time: Utc::now().naive_utc(),
+        }.insert(db)
+    }
+
+    pub fn delete(db: &Database, my_id: i32) -> Result<bool> {
+        use crate::schema::notes::dsl::*;
+
+        let n = diesel::delete(notes.filter(id.eq(my_id)))
+            .execute(db.db())?;
+        Ok(n > 0)
+    }
+
+    pub fn list(db: &Database) -> Result<Vec<Note>> {
+        use crate::schema::notes::dsl::*;
+
+        notes.load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    /// The notes of an entity, oldest first
+    pub fn for_entity(db: &Database, my_family: Table, my_key: i32) -> Result<Vec<Note>> {
+        use crate::schema::notes::dsl::*;
+
+        notes
+            .filter(family.eq(my_family.as_str()))
+            .filter(key.eq(my_key))
+            .order_by((time.asc(), id.asc()))
+            .load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
+    pub fn exists(&self, db: &Database) -> Result<bool> {
+        use crate::schema::notes::dsl::*;
+
+        let n = notes
+            .filter(family.eq(&self.family))
+            .filter(key.eq(self.key))
+            .filter(body.eq(&self.body))
+            .filter(time.eq(self.time))
+            .count()
+            .get_result::<i64>(db.db())?;
+        Ok(n > 0)
+    }
+}
+
+#[derive(Insertable)]
+#[table_name="notes"]
+pub struct NewNote<'a> {
+    pub family: &'a str,
+    pub key: i32,
+    pub body: &'a str,
+    pub time: NaiveDateTime,
+}
+
+impl<'a> NewNote<'a> {
+    pub fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(notes::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
+/// Tags and notes that are shown below the details of an entity
+pub struct Annotations {
+    tags: Vec<String>,
+    notes: Vec<Note>,
+}
+
+impl Annotations {
+    pub fn load(db: &Database, family: Table, key: i32) -> Result<Annotations> {
+        Ok(Annotations {
+            tags: Tag::names(db, family, key)?,
+            notes: Note::for_entity(db, family, key)?,
+        })
+    }
+}
+
+impl fmt::Display for Annotations {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        if !self.tags.is_empty() {
+            w.write_str("\n\t")?;
+            for (i, tag) in self.tags.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(' ')?;
+                }
+                Yellow::display(w, format!("[{}]", tag))?;
+            }
+        }
+        for note in &self.notes {
+            w.write_str("\n\t")?;
+            Grey::display(w, format!("note #{} at {}:", note.id, note.time))?;
+            write!(w, " {}", note.body)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_tag() {
+        assert!(Tag::validate("phishing-infra").is_ok());
+        assert!(Tag::validate("owned-by-vendor").is_ok());
+        assert!(Tag::validate("").is_err());
+        assert!(Tag::validate("two words").is_err());
+        assert!(Tag::validate("tab\tseparated").is_err());
+    }
+
+    #[test]
+    fn test_count() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        Tag::add(&db, Table::Domains, 1, "phishing-infra").unwrap();
+        Tag::add(&db, Table::Subdomains, 2, "phishing-infra").unwrap();
+        Tag::add(&db, Table::Subdomains, 2, "cdn").unwrap();
+
+        let counts = Tag::count(&db).unwrap();
+        assert_eq!(counts.into_iter().collect::<Vec<_>>(), vec![
+            (String::from("cdn"), 1),
+            (String::from("phishing-infra"), 2),
+        ]);
+    }
+
+    #[test]
+    fn test_delete_annotations() {
+        use crate::models::*;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let (_, domain_id) = db.insert_generic(Insert::Domain(NewDomain {
+            value: String::from("example.com"),
+            unscoped: false,
+        })).unwrap().unwrap();
+        let (_, subdomain_id) = db.insert_generic(Insert::Subdomain(NewSubdomain {
+            domain_id,
+            value: String::from("www.example.com"),
+            resolvable: None,
+            unscoped: false,
+        })).unwrap().unwrap();
+        Tag::add(&db, Table::Domains, domain_id, "phishing-infra").unwrap();
+        Tag::add(&db, Table::Subdomains, subdomain_id, "phishing-infra").unwrap();
+        Note::add(&db, Table::Subdomains, subdomain_id, "seen in a campaign").unwrap();
+
+        // the subdomain is removed by ON DELETE CASCADE
+        db.delete_generic(Table::Domains, domain_id).unwrap();
+        assert_eq!(Tag::list(&db).unwrap(), vec![]);
+        assert_eq!(Note::list(&db).unwrap(), vec![]);
+    }
+}
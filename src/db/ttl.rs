@@ -7,10 +7,11 @@ use crate::shell::Shell;
 use crate::term::{self, Term};
 use chrono::{NaiveDateTime, Duration, Utc};
 use diesel::prelude::*;
+use serde::{Serialize, Deserialize};
 use sn0int_std::ratelimits::Ratelimiter;
 
 
-#[derive(Identifiable, Queryable, AsChangeset, PartialEq, Debug)]
+#[derive(Identifiable, Queryable, AsChangeset, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="ttls"]
 pub struct Ttl {
     pub id: i32,
@@ -21,6 +22,13 @@ pub struct Ttl {
 }
 
 impl Ttl {
+    pub fn list(db: &Database) -> Result<Vec<Ttl>> {
+        use crate::schema::ttls::dsl::*;
+
+        ttls.load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
     pub fn find(obj: &Insert, my_key: i32, db: &Database) -> Result<Option<Ttl>> {
         use crate::schema::ttls::dsl::*;
 
@@ -51,16 +59,12 @@ impl Ttl {
         debug!("Creating ttl on record");
         let expire = Self::ttl_to_datetime(ttl);
 
-        diesel::insert_into(ttls::table)
-            .values(NewTtl {
-                family: obj.table(),
-                key,
-                value,
-                expire,
-            })
-            .execute(db.db())?;
-
-        Ok(())
+        NewTtl {
+            family: obj.table(),
+            key,
+            value,
+            expire,
+        }.insert(db)
     }
 
     pub fn bump(obj: &Insert, my_key: i32, ttl: i32, db: &Database) -> Result<()> {
@@ -89,25 +93,7 @@ impl Ttl {
 
     pub fn delete(&self, db: &Database) -> Result<()> {
         let family = self.family.parse::<Table>()?;
-        match family {
-            Table::Domains => Domain::delete_id(db, self.key)?,
-            Table::Subdomains => Subdomain::delete_id(db, self.key)?,
-            Table::Ipaddrs => IpAddr::delete_id(db, self.key)?,
-            Table::SubdomainIpaddrs => SubdomainIpAddr::delete_id(db, self.key)?,
-            Table::Urls => Url::delete_id(db, self.key)?,
-            Table::Emails => Email::delete_id(db, self.key)?,
-            Table::Phonenumbers => PhoneNumber::delete_id(db, self.key)?,
-            Table::Devices => Device::delete_id(db, self.key)?,
-            Table::Networks => Network::delete_id(db, self.key)?,
-            Table::NetworkDevices => NetworkDevice::delete_id(db, self.key)?,
-            Table::Accounts => Account::delete_id(db, self.key)?,
-            Table::Breaches => Breach::delete_id(db, self.key)?,
-            Table::BreachEmails => BreachEmail::delete_id(db, self.key)?,
-            Table::Images => Image::delete_id(db, self.key)?,
-            Table::Ports => Port::delete_id(db, self.key)?,
-            Table::Netblocks => Netblock::delete_id(db, self.key)?,
-            Table::Cryptoaddrs => CryptoAddr::delete_id(db, self.key)?,
-        };
+        db.delete_generic(family, self.key)?;
 
         diesel::delete(self)
                 .execute(db.db())?;
@@ -125,6 +111,15 @@ pub struct NewTtl<'a> {
     pub expire: NaiveDateTime,
 }
 
+impl<'a> NewTtl<'a> {
+    pub fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(ttls::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
 pub fn reap_expired(rl: &mut Shell) -> Result<()> {
     debug!("Reaping expired entities");
 
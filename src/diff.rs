@@ -0,0 +1,435 @@
+use crate::db::{Database, Family};
+use crate::errors::*;
+use crate::fmt::colors::*;
+use crate::models::*;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A difference of a single entity, entities are identified by their value
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub family: &'static str,
+    pub value: String,
+    pub change: ChangeKind,
+    /// The old and new value of every field that changed
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub fields: Map<String, Value>,
+    #[serde(skip)]
+    update: Option<Update>,
+}
+
+impl Change {
+    pub fn to_term_str(&self) -> Result<String> {
+        let mut out = String::new();
+        match self.change {
+            ChangeKind::Added => Green::display(&mut out, "+")?,
+            ChangeKind::Removed => Red::display(&mut out, "-")?,
+            ChangeKind::Changed => Yellow::display(&mut out, "~")?,
+        }
+        write!(out, " {} {:?}", self.family, self.value)?;
+
+        let mut updates = Vec::new();
+        let mut rendered = Map::new();
+        if let Some(update) = &self.update {
+            updates.push(update.to_term_str());
+            rendered = update.fields()?;
+        }
+        // fields that have been cleared or can't be set by an insert
+        for (key, field) in &self.fields {
+            if !rendered.contains_key(key) {
+                let mut update = format!("{} => ", key);
+                Yellow::display(&mut update, &field["new"])?;
+                updates.push(update);
+            }
+        }
+        if !updates.is_empty() {
+            write!(out, " ({})", updates.join(", "))?;
+        }
+        Ok(out)
+    }
+}
+
+/// Row ids are different in every workspace and the observation times change
+/// every time an entity is seen again, neither is compared
+fn is_ignored(key: &str) -> bool {
+    key == "id" || key.ends_with("_id") || key == "first_observed" || key == "last_observed"
+}
+
+/// The old and new value of every field that differs between two versions of an entity
+fn fields<T: Serialize>(old: &T, new: &T) -> Result<Map<String, Value>> {
+    let (old, new) = match (serde_json::to_value(old)?, serde_json::to_value(new)?) {
+        (Value::Object(old), Value::Object(new)) => (old, new),
+        _ => bail!("Entity isn't serialized as an object"),
+    };
+
+    let mut fields = Map::new();
+    for (key, new) in new {
+        if is_ignored(&key) {
+            continue;
+        }
+        let old = old.get(&key).cloned().unwrap_or(Value::Null);
+        if old != new {
+            fields.insert(key, json!({
+                "old": old,
+                "new": new,
+            }));
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Render a change with the logic that is used for inserts, this is only used
+/// for display since it doesn't cover fields that have been cleared
+fn upsert<T: IntoNew>(old: &T, new: T) -> Option<Update>
+where
+    T::Target: Upsertable<T>,
+{
+    let update = new.into_new().upsert(old);
+    if update.is_dirty() {
+        Some(update.generic())
+    } else {
+        None
+    }
+}
+
+/// Keep the value of every row id, this is needed to identify relations
+type Values = HashMap<i32, String>;
+
+fn lookup(values: &Values, id: i32) -> Result<&str> {
+    values.get(&id)
+        .map(String::as_str)
+        .ok_or_else(|| format_err!("Relation references unknown id: {}", id))
+}
+
+/// Index the entities of a table by their value
+fn by_value<T: Model<ID=str>>(db: &Database) -> Result<(BTreeMap<String, T>, Values)> {
+    let mut entities = BTreeMap::new();
+    let mut values = Values::new();
+    for x in T::list(db)? {
+        values.insert(x.id(), x.value().to_string());
+        entities.insert(x.value().to_string(), x);
+    }
+    Ok((entities, values))
+}
+
+fn by_key<T: Model, F: Fn(&T) -> Result<String>>(db: &Database, key: F) -> Result<BTreeMap<String, T>> {
+    let mut entities = BTreeMap::new();
+    for x in T::list(db)? {
+        entities.insert(key(&x)?, x);
+    }
+    Ok(entities)
+}
+
+#[derive(Debug, Default)]
+pub struct Diff {
+    pub changes: Vec<Change>,
+}
+
+impl Diff {
+    /// Everything that needs to be applied to `a` to get `b`
+    pub fn compare(a: &Database, b: &Database) -> Result<Diff> {
+        let mut diff = Diff::default();
+
+        let domains = diff.entities::<Domain>(Family::Domain, a, b)?;
+        let subdomains = diff.entities::<Subdomain>(Family::Subdomain, a, b)?;
+        let ipaddrs = diff.entities::<IpAddr>(Family::Ipaddr, a, b)?;
+
+        let old = by_key(a, |x: &SubdomainIpAddr| Ok(format!("{}+{}",
+            lookup(&subdomains.0, x.subdomain_id)?,
+            lookup(&ipaddrs.0, x.ip_addr_id)?)))?;
+        let new = by_key(b, |x: &SubdomainIpAddr| Ok(format!("{}+{}",
+            lookup(&subdomains.1, x.subdomain_id)?,
+            lookup(&ipaddrs.1, x.ip_addr_id)?)))?;
+        // relations without additional fields can only be added or removed
+        diff.compare(Family::SubdomainIpaddr, old, new, |_, _| None)?;
+
+        diff.entities::<Url>(Family::Url, a, b)?;
+        let emails = diff.entities::<Email>(Family::Email, a, b)?;
+        let phonenumbers = diff.entities::<PhoneNumber>(Family::Phonenumber, a, b)?;
+        let devices = diff.entities::<Device>(Family::Device, a, b)?;
+        let networks = diff.entities::<Network>(Family::Network, a, b)?;
+
+        let old = by_key(a, |x: &NetworkDevice| Ok(format!("{}+{}",
+            lookup(&networks.0, x.network_id)?,
+            lookup(&devices.0, x.device_id)?)))?;
+        let new = by_key(b, |x: &NetworkDevice| Ok(format!("{}+{}",
+            lookup(&networks.1, x.network_id)?,
+            lookup(&devices.1, x.device_id)?)))?;
+        diff.compare(Family::NetworkDevice, old, new, upsert)?;
+
+        let accounts = diff.entities::<Account>(Family::Account, a, b)?;
+        let breaches = diff.entities::<Breach>(Family::Breach, a, b)?;
+
+        let old = by_key(a, |x: &BreachEmail| Ok(format!("{}+{}+{}",
+            lookup(&breaches.0, x.breach_id)?,
+            lookup(&emails.0, x.email_id)?,
+            x.password.as_deref().unwrap_or(""))))?;
+        let new = by_key(b, |x: &BreachEmail| Ok(format!("{}+{}+{}",
+            lookup(&breaches.1, x.breach_id)?,
+            lookup(&emails.1, x.email_id)?,
+            x.password.as_deref().unwrap_or(""))))?;
+        diff.compare(Family::BreachEmail, old, new, upsert)?;
+
+        let images = diff.entities::<Image>(Family::Image, a, b)?;
+        let ports = diff.entities::<Port>(Family::Port, a, b)?;
+        let netblocks = diff.entities::<Netblock>(Family::Netblock, a, b)?;
+        diff.entities::<CryptoAddr>(Family::Cryptoaddr, a, b)?;
+        let certificates = diff.entities::<Certificate>(Family::Certificate, a, b)?;
+
+        let old = by_key(a, |x: &PortCertificate| Ok(format!("{}+{}",
+            lookup(&ports.0, x.port_id)?,
+            lookup(&certificates.0, x.certificate_id)?)))?;
+        let new = by_key(b, |x: &PortCertificate| Ok(format!("{}+{}",
+            lookup(&ports.1, x.port_id)?,
+            lookup(&certificates.1, x.certificate_id)?)))?;
+        diff.compare(Family::PortCertificate, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &SubdomainCertificate| Ok(format!("{}+{}",
+            lookup(&subdomains.0, x.subdomain_id)?,
+            lookup(&certificates.0, x.certificate_id)?)))?;
+        let new = by_key(b, |x: &SubdomainCertificate| Ok(format!("{}+{}",
+            lookup(&subdomains.1, x.subdomain_id)?,
+            lookup(&certificates.1, x.certificate_id)?)))?;
+        diff.compare(Family::SubdomainCertificate, old, new, |_, _| None)?;
+
+        diff.entities::<DnsRecord>(Family::DnsRecord, a, b)?;
+
+        let persons = diff.entities::<Person>(Family::Person, a, b)?;
+        let organizations = diff.entities::<Organization>(Family::Organization, a, b)?;
+
+        let old = by_key(a, |x: &PersonEmail| Ok(format!("{}+{}",
+            lookup(&persons.0, x.person_id)?,
+            lookup(&emails.0, x.email_id)?)))?;
+        let new = by_key(b, |x: &PersonEmail| Ok(format!("{}+{}",
+            lookup(&persons.1, x.person_id)?,
+            lookup(&emails.1, x.email_id)?)))?;
+        diff.compare(Family::PersonEmail, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &PersonAccount| Ok(format!("{}+{}",
+            lookup(&persons.0, x.person_id)?,
+            lookup(&accounts.0, x.account_id)?)))?;
+        let new = by_key(b, |x: &PersonAccount| Ok(format!("{}+{}",
+            lookup(&persons.1, x.person_id)?,
+            lookup(&accounts.1, x.account_id)?)))?;
+        diff.compare(Family::PersonAccount, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &PersonPhoneNumber| Ok(format!("{}+{}",
+            lookup(&persons.0, x.person_id)?,
+            lookup(&phonenumbers.0, x.phonenumber_id)?)))?;
+        let new = by_key(b, |x: &PersonPhoneNumber| Ok(format!("{}+{}",
+            lookup(&persons.1, x.person_id)?,
+            lookup(&phonenumbers.1, x.phonenumber_id)?)))?;
+        diff.compare(Family::PersonPhonenumber, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &PersonImage| Ok(format!("{}+{}",
+            lookup(&persons.0, x.person_id)?,
+            lookup(&images.0, x.image_id)?)))?;
+        let new = by_key(b, |x: &PersonImage| Ok(format!("{}+{}",
+            lookup(&persons.1, x.person_id)?,
+            lookup(&images.1, x.image_id)?)))?;
+        diff.compare(Family::PersonImage, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &PersonOrganization| Ok(format!("{}+{}",
+            lookup(&persons.0, x.person_id)?,
+            lookup(&organizations.0, x.organization_id)?)))?;
+        let new = by_key(b, |x: &PersonOrganization| Ok(format!("{}+{}",
+            lookup(&persons.1, x.person_id)?,
+            lookup(&organizations.1, x.organization_id)?)))?;
+        diff.compare(Family::PersonOrganization, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &OrganizationDomain| Ok(format!("{}+{}",
+            lookup(&organizations.0, x.organization_id)?,
+            lookup(&domains.0, x.domain_id)?)))?;
+        let new = by_key(b, |x: &OrganizationDomain| Ok(format!("{}+{}",
+            lookup(&organizations.1, x.organization_id)?,
+            lookup(&domains.1, x.domain_id)?)))?;
+        diff.compare(Family::OrganizationDomain, old, new, |_, _| None)?;
+
+        let asns = diff.entities::<Asn>(Family::Asn, a, b)?;
+
+        let old = by_key(a, |x: &AsnNetblock| Ok(format!("{}+{}",
+            lookup(&asns.0, x.asn_id)?,
+            lookup(&netblocks.0, x.netblock_id)?)))?;
+        let new = by_key(b, |x: &AsnNetblock| Ok(format!("{}+{}",
+            lookup(&asns.1, x.asn_id)?,
+            lookup(&netblocks.1, x.netblock_id)?)))?;
+        diff.compare(Family::AsnNetblock, old, new, |_, _| None)?;
+
+        let old = by_key(a, |x: &AsnIpAddr| Ok(format!("{}+{}",
+            lookup(&asns.0, x.asn_id)?,
+            lookup(&ipaddrs.0, x.ip_addr_id)?)))?;
+        let new = by_key(b, |x: &AsnIpAddr| Ok(format!("{}+{}",
+            lookup(&asns.1, x.asn_id)?,
+            lookup(&ipaddrs.1, x.ip_addr_id)?)))?;
+        diff.compare(Family::AsnIpaddr, old, new, |_, _| None)?;
+
+        Ok(diff)
+    }
+
+    /// Compare a table of entities that are identified by their value, returns
+    /// the values of all row ids of both databases
+    fn entities<T>(&mut self, family: Family, a: &Database, b: &Database) -> Result<(Values, Values)>
+    where
+        T: Model<ID=str> + IntoNew + Serialize,
+        T::Target: Upsertable<T>,
+    {
+        let (old, old_values) = by_value::<T>(a)?;
+        let (new, new_values) = by_value::<T>(b)?;
+        self.compare(family, old, new, upsert)?;
+        Ok((old_values, new_values))
+    }
+
+    fn compare<T, F>(&mut self, family: Family, mut old: BTreeMap<String, T>, mut new: BTreeMap<String, T>, upsert: F) -> Result<()>
+    where
+        T: Serialize,
+        F: Fn(&T, T) -> Option<Update>,
+    {
+        let keys = old.keys()
+            .chain(new.keys())
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        for value in keys {
+            let (change, fields, update) = match (old.remove(&value), new.remove(&value)) {
+                (Some(old), Some(new)) => {
+                    let fields = fields(&old, &new)?;
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    (ChangeKind::Changed, fields, upsert(&old, new))
+                },
+                (Some(_), None) => (ChangeKind::Removed, Map::new(), None),
+                (None, Some(_)) => (ChangeKind::Added, Map::new(), None),
+                (None, None) => unreachable!(),
+            };
+
+            self.changes.push(Change {
+                family: family.as_str(),
+                value,
+                change,
+                fields,
+                update,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter()
+            .filter(|x| x.change == kind)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn url(status: Option<i32>, title: Option<&str>) -> Url {
+        Url {
+            id: 1,
+            subdomain_id: 1,
+            value: "https://example.com/".to_string(),
+            path: "/".to_string(),
+            status,
+            body: None,
+            unscoped: false,
+            online: None,
+            title: title.map(String::from),
+            redirect: None,
+            blob: None,
+            first_observed: None,
+            last_observed: None,
+        }
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let mut old = url(Some(200), None);
+        let new = url(Some(200), None);
+        old.id = 2;
+        old.first_observed = NaiveDateTime::from_timestamp_opt(0, 0);
+        assert!(fields(&old, &new).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let old = url(Some(301), None);
+        let fields = fields(&old, &url(Some(200), Some("Example"))).unwrap();
+        assert_eq!(Value::Object(fields), json!({
+            "status": {
+                "old": 301,
+                "new": 200,
+            },
+            "title": {
+                "old": null,
+                "new": "Example",
+            },
+        }));
+    }
+
+    #[test]
+    fn test_cleared_fields() {
+        let old = url(Some(200), Some("Example"));
+        let mut new = url(Some(200), None);
+        new.unscoped = true;
+
+        let mut diff = Diff::default();
+        let old = vec![(old.value.clone(), old)].into_iter().collect();
+        let new = vec![(new.value.clone(), new)].into_iter().collect();
+        diff.compare(Family::Url, old, new, upsert).unwrap();
+
+        assert_eq!(diff.changes.len(), 1);
+        let change = &diff.changes[0];
+        assert_eq!(change.change, ChangeKind::Changed);
+        assert_eq!(Value::Object(change.fields.clone()), json!({
+            "title": {
+                "old": "Example",
+                "new": null,
+            },
+            "unscoped": {
+                "old": false,
+                "new": true,
+            },
+        }));
+        assert!(change.update.is_none());
+        let term = change.to_term_str().unwrap();
+        assert!(term.contains("title => "));
+        assert!(term.contains("unscoped => "));
+    }
+
+    #[test]
+    fn test_added_and_removed() {
+        let mut diff = Diff::default();
+        let old = vec![
+            ("a.example.com".to_string(), 1),
+            ("b.example.com".to_string(), 2),
+        ].into_iter().collect();
+        let new = vec![
+            ("b.example.com".to_string(), 2),
+            ("c.example.com".to_string(), 3),
+        ].into_iter().collect();
+        diff.compare(Family::Subdomain, old, new, |_, _| None).unwrap();
+
+        let changes = diff.changes.iter()
+            .map(|x| (x.value.as_str(), x.change))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![
+            ("a.example.com", ChangeKind::Removed),
+            ("c.example.com", ChangeKind::Added),
+        ]);
+    }
+}
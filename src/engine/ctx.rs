@@ -120,6 +120,20 @@ pub trait State {
             .map_err(Error::from)
     }
 
+    fn db_tag(&self, family: Family, value: String, tag: String) -> Result<DatabaseResponse> {
+        self.send(&Event::Database(Box::new(DatabaseEvent::Tag((family, value, tag)))));
+        self.db_recv()
+            .context("Failed to tag entity")
+            .map_err(Error::from)
+    }
+
+    fn db_tags(&self, family: Family, value: String) -> Result<DatabaseResponse> {
+        self.send(&Event::Database(Box::new(DatabaseEvent::Tags((family, value)))));
+        self.db_recv()
+            .context("Failed to query database")
+            .map_err(Error::from)
+    }
+
     fn stdin_read_line(&self) -> Result<Option<String>> {
         self.send(&Event::Stdio(StdioEvent::Readline));
         let reply = self.recv()?;
@@ -504,6 +518,8 @@ pub fn ctx<'a>(env: Environment, logger: Arc<Mutex<Box<dyn IpcChild>>>) -> (hlua
     runtime::db_add_ttl(&mut lua, state.clone());
     runtime::db_activity(&mut lua, state.clone());
     runtime::db_select(&mut lua, state.clone());
+    runtime::db_tag(&mut lua, state.clone());
+    runtime::db_tags(&mut lua, state.clone());
     runtime::db_update(&mut lua, state.clone());
     runtime::debug(&mut lua, state.clone());
     runtime::dns(&mut lua, state.clone());
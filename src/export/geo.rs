@@ -0,0 +1,277 @@
+use crate::db::{Database, Family};
+use crate::errors::*;
+use crate::models::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use super::{Attrs, attrs, escape_xml};
+use std::f64::consts::PI;
+use std::io::Write;
+
+/// Mean earth radius in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+/// Number of segments used to approximate a circle
+const CIRCLE_SEGMENTS: usize = 32;
+
+#[derive(Debug, PartialEq)]
+enum Geometry {
+    /// (longitude, latitude)
+    Point(f64, f64),
+    /// A closed ring of (longitude, latitude)
+    Polygon(Vec<(f64, f64)>),
+}
+
+impl Geometry {
+    fn to_geojson(&self) -> Value {
+        match self {
+            Geometry::Point(lon, lat) => json!({
+                "type": "Point",
+                "coordinates": [lon, lat],
+            }),
+            Geometry::Polygon(ring) => json!({
+                "type": "Polygon",
+                "coordinates": [
+                    ring.iter()
+                        .map(|(lon, lat)| [*lon, *lat])
+                        .collect::<Vec<_>>(),
+                ],
+            }),
+        }
+    }
+
+    fn to_kml(&self) -> String {
+        match self {
+            Geometry::Point(lon, lat) => format!("<Point><coordinates>{},{}</coordinates></Point>", lon, lat),
+            Geometry::Polygon(ring) => {
+                let coordinates = ring.iter()
+                    .map(|(lon, lat)| format!("{},{}", lon, lat))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!("<Polygon><outerBoundaryIs><LinearRing><coordinates>{}</coordinates></LinearRing></outerBoundaryIs></Polygon>", coordinates)
+            },
+        }
+    }
+}
+
+/// Approximate a circle around a position, counterclockwise as required by geojson
+fn circle(lat: f64, lon: f64, radius: f64) -> Vec<(f64, f64)> {
+    let distance = radius / EARTH_RADIUS;
+    let (lat1, lon1) = (lat.to_radians(), lon.to_radians());
+
+    let mut ring = (0..CIRCLE_SEGMENTS)
+        .map(|i| {
+            let bearing = -2.0 * PI * (i as f64) / (CIRCLE_SEGMENTS as f64);
+            let lat2 = (lat1.sin() * distance.cos() + lat1.cos() * distance.sin() * bearing.cos()).asin();
+            let lon2 = lon1 + (bearing.sin() * distance.sin() * lat1.cos())
+                .atan2(distance.cos() - lat1.sin() * lat2.sin());
+            (lon2.to_degrees(), lat2.to_degrees())
+        })
+        .collect::<Vec<_>>();
+    ring.push(ring[0]);
+    ring
+}
+
+#[derive(Debug, PartialEq)]
+struct Feature {
+    name: String,
+    family: &'static str,
+    geometry: Geometry,
+    properties: Attrs,
+}
+
+/// Everything in the workspace that has coordinates
+#[derive(Debug, Default, PartialEq)]
+pub struct FeatureCollection {
+    features: Vec<Feature>,
+}
+
+impl FeatureCollection {
+    pub fn load(db: &Database) -> Result<FeatureCollection> {
+        let mut collection = FeatureCollection::default();
+
+        for x in IpAddr::list(db)? {
+            collection.add_point(Family::Ipaddr, &x.value, x.latitude, x.longitude, &x)?;
+        }
+
+        for x in Network::list(db)? {
+            collection.add_point(Family::Network, &x.value, x.latitude, x.longitude, &x)?;
+        }
+
+        for x in Image::list(db)? {
+            let name = x.filename.as_ref().unwrap_or(&x.value).to_string();
+            collection.add_point(Family::Image, &name, x.latitude, x.longitude, &x)?;
+        }
+
+        for x in Activity::list(db)? {
+            let (lat, lon) = match (x.latitude, x.longitude) {
+                (Some(lat), Some(lon)) => (lat as f64, lon as f64),
+                _ => continue,
+            };
+
+            let geometry = match x.radius {
+                Some(radius) if radius > 0 => Geometry::Polygon(circle(lat, lon, radius as f64)),
+                _ => Geometry::Point(lon, lat),
+            };
+
+            let properties = attrs(&x, &["latitude", "longitude"])?;
+            collection.features.push(Feature {
+                name: x.topic,
+                family: "activity",
+                geometry,
+                properties,
+            });
+        }
+
+        Ok(collection)
+    }
+
+    fn add_point<T: Serialize>(&mut self, family: Family, name: &str, lat: Option<f32>, lon: Option<f32>, obj: &T) -> Result<()> {
+        if let (Some(lat), Some(lon)) = (lat, lon) {
+            self.features.push(Feature {
+                name: name.to_string(),
+                family: family.as_str(),
+                geometry: Geometry::Point(lon as f64, lat as f64),
+                // the ip family of ipaddrs would overwrite the family of the feature
+                properties: attrs(obj, &["latitude", "longitude", "body", "family"])?,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn write_geojson<W: Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        let features = self.features.iter()
+            .map(|feature| {
+                let mut properties = serde_json::Map::new();
+                properties.insert("name".to_string(), Value::String(feature.name.clone()));
+                properties.insert("family".to_string(), Value::String(feature.family.to_string()));
+                for (key, value) in &feature.properties {
+                    properties.insert(key.to_string(), value.clone());
+                }
+
+                json!({
+                    "type": "Feature",
+                    "geometry": feature.geometry.to_geojson(),
+                    "properties": properties,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_writer(&mut *w, &json!({
+            "type": "FeatureCollection",
+            "features": features,
+        }))?;
+        w.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    pub fn write_kml<W: Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(w, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#)?;
+        writeln!(w, "  <Document>")?;
+        for feature in &self.features {
+            writeln!(w, "    <Placemark>")?;
+            writeln!(w, "      <name>{}</name>", escape_xml(&feature.name))?;
+            writeln!(w, "      <ExtendedData>")?;
+            writeln!(w, r#"        <Data name="family"><value>{}</value></Data>"#, feature.family)?;
+            for (key, value) in &feature.properties {
+                let value = match value {
+                    Value::String(s) => s.to_string(),
+                    v => v.to_string(),
+                };
+                writeln!(w, r#"        <Data name="{}"><value>{}</value></Data>"#, escape_xml(key), escape_xml(&value))?;
+            }
+            writeln!(w, "      </ExtendedData>")?;
+            writeln!(w, "      {}", feature.geometry.to_kml())?;
+            writeln!(w, "    </Placemark>")?;
+        }
+        writeln!(w, "  </Document>")?;
+        writeln!(w, "</kml>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+        let (lat1, lon1, lat2, lon2) = (lat1.to_radians(), lon1.to_radians(), lat2.to_radians(), lon2.to_radians());
+        let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+
+    #[test]
+    fn test_circle_is_closed() {
+        let ring = circle(52.52, 13.405, 100.0);
+        assert_eq!(ring.len(), CIRCLE_SEGMENTS + 1);
+        assert_eq!(ring.first(), ring.last());
+    }
+
+    #[test]
+    fn test_circle_radius() {
+        for (lon, lat) in circle(52.52, 13.405, 250.0) {
+            let d = distance(52.52, 13.405, lat, lon);
+            assert!((d - 250.0).abs() < 0.5, "distance was {}", d);
+        }
+    }
+
+    #[test]
+    fn test_circle_counterclockwise() {
+        let ring = circle(0.0, 0.0, 1000.0);
+        // shoelace formula, positive area means counterclockwise
+        let area = ring.windows(2)
+            .map(|x| x[0].0 * x[1].1 - x[1].0 * x[0].1)
+            .sum::<f64>();
+        assert!(area > 0.0);
+    }
+
+    #[test]
+    fn test_ip_family_is_skipped() {
+        let mut collection = FeatureCollection::default();
+        collection.add_point(Family::Ipaddr, "192.0.2.1", Some(52.5), Some(13.5), &json!({
+            "family": "4",
+            "value": "192.0.2.1",
+        })).unwrap();
+
+        let mut buf = Vec::new();
+        collection.write_geojson(&mut buf).unwrap();
+        let json = serde_json::from_slice::<Value>(&buf).unwrap();
+        assert_eq!(json["features"][0]["properties"]["family"], "ipaddr");
+
+        let mut buf = Vec::new();
+        collection.write_kml(&mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out.matches(r#"<Data name="family">"#).count(), 1);
+    }
+
+    #[test]
+    fn test_geojson_point() {
+        let collection = FeatureCollection {
+            features: vec![Feature {
+                name: "192.0.2.1".to_string(),
+                family: "ipaddr",
+                geometry: Geometry::Point(13.5, 52.5),
+                properties: vec![("city".to_string(), Value::String("Berlin".to_string()))],
+            }],
+        };
+        let mut buf = Vec::new();
+        collection.write_geojson(&mut buf).unwrap();
+        let json = serde_json::from_slice::<Value>(&buf).unwrap();
+        assert_eq!(json, json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [13.5, 52.5],
+                },
+                "properties": {
+                    "name": "192.0.2.1",
+                    "family": "ipaddr",
+                    "city": "Berlin",
+                },
+            }],
+        }));
+    }
+}
@@ -0,0 +1,405 @@
+use crate::db::{Database, Family};
+use crate::errors::*;
+use crate::models::*;
+use serde::Serialize;
+use serde_json::Value;
+use super::{Attrs, attrs, escape_xml};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+#[derive(Debug, PartialEq)]
+pub struct Node {
+    id: String,
+    label: String,
+    family: &'static str,
+    attrs: Attrs,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Edge {
+    source: String,
+    target: String,
+    label: &'static str,
+    attrs: Attrs,
+}
+
+/// The workspace as a graph with one node per entity and one edge per relation
+#[derive(Debug, Default, PartialEq)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[inline]
+fn node_id(family: &Family, id: i32) -> String {
+    format!("{}:{}", family.as_str(), id)
+}
+
+fn attr_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "double",
+        Value::Number(_) => "long",
+        _ => "string",
+    }
+}
+
+fn attr_str(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_string(),
+        v => v.to_string(),
+    }
+}
+
+/// Collect every attribute name and its type, falls back to string if the type is ambiguous
+fn attr_keys<'a, I: Iterator<Item=&'a Attrs>>(attrs: I) -> BTreeMap<&'a str, &'static str> {
+    let mut keys = BTreeMap::new();
+    for attrs in attrs {
+        for (key, value) in attrs {
+            let ty = attr_type(value);
+            keys.entry(key.as_str())
+                .and_modify(|x| if *x != ty { *x = "string" })
+                .or_insert(ty);
+        }
+    }
+    keys
+}
+
+fn escape_dot(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => (),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Graph {
+    pub fn load(db: &Database) -> Result<Graph> {
+        let mut graph = Graph::default();
+
+        let domains = Domain::list(db)?;
+        graph.add_nodes(Family::Domain, &domains)?;
+
+        let subdomains = Subdomain::list(db)?;
+        graph.add_nodes(Family::Subdomain, &subdomains)?;
+        for x in &subdomains {
+            graph.add_edge(&Family::Domain, x.domain_id, &Family::Subdomain, x.id, "subdomain", Vec::new());
+        }
+
+        graph.add_nodes(Family::Ipaddr, &IpAddr::list(db)?)?;
+        for x in SubdomainIpAddr::list(db)? {
+            graph.add_edge(&Family::Subdomain, x.subdomain_id, &Family::Ipaddr, x.ip_addr_id, "ipaddr", Vec::new());
+        }
+
+        let ports = Port::list(db)?;
+        graph.add_nodes(Family::Port, &ports)?;
+        for x in &ports {
+            graph.add_edge(&Family::Ipaddr, x.ip_addr_id, &Family::Port, x.id, "port", Vec::new());
+        }
+
+        let urls = Url::list(db)?;
+        graph.add_nodes(Family::Url, &urls)?;
+        for x in &urls {
+            graph.add_edge(&Family::Subdomain, x.subdomain_id, &Family::Url, x.id, "url", Vec::new());
+        }
+
+        graph.add_nodes(Family::Network, &Network::list(db)?)?;
+        graph.add_nodes(Family::Device, &Device::list(db)?)?;
+        for x in NetworkDevice::list(db)? {
+            let attrs = attrs(&x, &["network_id", "device_id"])?;
+            graph.add_edge(&Family::Network, x.network_id, &Family::Device, x.device_id, "device", attrs);
+        }
+
+        graph.add_nodes(Family::Breach, &Breach::list(db)?)?;
+        graph.add_nodes(Family::Email, &Email::list(db)?)?;
+        for x in BreachEmail::list(db)? {
+            let attrs = attrs(&x, &["breach_id", "email_id"])?;
+            graph.add_edge(&Family::Breach, x.breach_id, &Family::Email, x.email_id, "email", attrs);
+        }
+
+        graph.add_nodes(Family::Phonenumber, &PhoneNumber::list(db)?)?;
+        graph.add_nodes(Family::Account, &Account::list(db)?)?;
+        graph.add_nodes(Family::Image, &Image::list(db)?)?;
+        graph.add_nodes(Family::Netblock, &Netblock::list(db)?)?;
+        graph.add_nodes(Family::Cryptoaddr, &CryptoAddr::list(db)?)?;
+
+        graph.add_nodes(Family::Certificate, &Certificate::list(db)?)?;
+        for x in PortCertificate::list(db)? {
+            graph.add_edge(&Family::Port, x.port_id, &Family::Certificate, x.certificate_id, "certificate", Vec::new());
+        }
+        for x in SubdomainCertificate::list(db)? {
+            graph.add_edge(&Family::Subdomain, x.subdomain_id, &Family::Certificate, x.certificate_id, "certificate", Vec::new());
+        }
+
+        let dns_records = DnsRecord::list(db)?;
+        graph.add_nodes(Family::DnsRecord, &dns_records)?;
+        for x in &dns_records {
+            graph.add_edge(&Family::Subdomain, x.subdomain_id, &Family::DnsRecord, x.id, "dns_record", Vec::new());
+        }
+
+        graph.add_nodes(Family::Person, &Person::list(db)?)?;
+        graph.add_nodes(Family::Organization, &Organization::list(db)?)?;
+        for x in PersonEmail::list(db)? {
+            graph.add_edge(&Family::Person, x.person_id, &Family::Email, x.email_id, "email", Vec::new());
+        }
+        for x in PersonAccount::list(db)? {
+            graph.add_edge(&Family::Person, x.person_id, &Family::Account, x.account_id, "account", Vec::new());
+        }
+        for x in PersonPhoneNumber::list(db)? {
+            graph.add_edge(&Family::Person, x.person_id, &Family::Phonenumber, x.phonenumber_id, "phonenumber", Vec::new());
+        }
+        for x in PersonImage::list(db)? {
+            graph.add_edge(&Family::Person, x.person_id, &Family::Image, x.image_id, "image", Vec::new());
+        }
+        for x in PersonOrganization::list(db)? {
+            graph.add_edge(&Family::Person, x.person_id, &Family::Organization, x.organization_id, "organization", Vec::new());
+        }
+        for x in OrganizationDomain::list(db)? {
+            graph.add_edge(&Family::Organization, x.organization_id, &Family::Domain, x.domain_id, "domain", Vec::new());
+        }
+
+        graph.add_nodes(Family::Asn, &Asn::list(db)?)?;
+        for x in AsnNetblock::list(db)? {
+            graph.add_edge(&Family::Asn, x.asn_id, &Family::Netblock, x.netblock_id, "netblock", Vec::new());
+        }
+        for x in AsnIpAddr::list(db)? {
+            graph.add_edge(&Family::Asn, x.asn_id, &Family::Ipaddr, x.ip_addr_id, "ipaddr", Vec::new());
+        }
+
+        Ok(graph)
+    }
+
+    fn add_nodes<T: Model + Serialize>(&mut self, family: Family, rows: &[T]) -> Result<()> {
+        for row in rows {
+            self.nodes.push(Node {
+                id: node_id(&family, row.id()),
+                label: row.to_string(),
+                family: family.as_str(),
+                // large blobs like http bodies are skipped, the ip family of
+                // ipaddrs and netblocks would collide with the family of the node
+                attrs: attrs(row, &["domain_id", "subdomain_id", "ip_addr_id", "body", "family"])?,
+            });
+        }
+        Ok(())
+    }
+
+    fn add_edge(&mut self, source: &Family, source_id: i32, target: &Family, target_id: i32, label: &'static str, attrs: Attrs) {
+        self.edges.push(Edge {
+            source: node_id(source, source_id),
+            target: node_id(target, target_id),
+            label,
+            attrs,
+        });
+    }
+
+    pub fn write_graphml<W: Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        let node_keys = attr_keys(self.nodes.iter().map(|x| &x.attrs));
+        let edge_keys = attr_keys(self.edges.iter().map(|x| &x.attrs));
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(w, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+        writeln!(w, r#"  <key id="n_label" for="node" attr.name="label" attr.type="string"/>"#)?;
+        writeln!(w, r#"  <key id="n_family" for="node" attr.name="family" attr.type="string"/>"#)?;
+        for (key, ty) in &node_keys {
+            writeln!(w, r#"  <key id="n_{0}" for="node" attr.name="{0}" attr.type="{1}"/>"#, escape_xml(key), ty)?;
+        }
+        writeln!(w, r#"  <key id="e_label" for="edge" attr.name="label" attr.type="string"/>"#)?;
+        for (key, ty) in &edge_keys {
+            writeln!(w, r#"  <key id="e_{0}" for="edge" attr.name="{0}" attr.type="{1}"/>"#, escape_xml(key), ty)?;
+        }
+
+        writeln!(w, r#"  <graph id="sn0int" edgedefault="directed">"#)?;
+        for node in &self.nodes {
+            writeln!(w, r#"    <node id="{}">"#, escape_xml(&node.id))?;
+            writeln!(w, r#"      <data key="n_label">{}</data>"#, escape_xml(&node.label))?;
+            writeln!(w, r#"      <data key="n_family">{}</data>"#, node.family)?;
+            for (key, value) in &node.attrs {
+                writeln!(w, r#"      <data key="n_{}">{}</data>"#, escape_xml(key), escape_xml(&attr_str(value)))?;
+            }
+            writeln!(w, "    </node>")?;
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            writeln!(w, r#"    <edge id="e{}" source="{}" target="{}">"#, i, escape_xml(&edge.source), escape_xml(&edge.target))?;
+            writeln!(w, r#"      <data key="e_label">{}</data>"#, edge.label)?;
+            for (key, value) in &edge.attrs {
+                writeln!(w, r#"      <data key="e_{}">{}</data>"#, escape_xml(key), escape_xml(&attr_str(value)))?;
+            }
+            writeln!(w, "    </edge>")?;
+        }
+        writeln!(w, "  </graph>")?;
+        writeln!(w, "</graphml>")?;
+
+        Ok(())
+    }
+
+    pub fn write_gexf<W: Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        let node_keys = attr_keys(self.nodes.iter().map(|x| &x.attrs));
+        let edge_keys = attr_keys(self.edges.iter().map(|x| &x.attrs));
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(w, r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#)?;
+        writeln!(w, r#"  <graph defaultedgetype="directed">"#)?;
+
+        writeln!(w, r#"    <attributes class="node">"#)?;
+        writeln!(w, r#"      <attribute id="family" title="family" type="string"/>"#)?;
+        for (key, ty) in &node_keys {
+            writeln!(w, r#"      <attribute id="{0}" title="{0}" type="{1}"/>"#, escape_xml(key), ty)?;
+        }
+        writeln!(w, "    </attributes>")?;
+        writeln!(w, r#"    <attributes class="edge">"#)?;
+        for (key, ty) in &edge_keys {
+            writeln!(w, r#"      <attribute id="{0}" title="{0}" type="{1}"/>"#, escape_xml(key), ty)?;
+        }
+        writeln!(w, "    </attributes>")?;
+
+        writeln!(w, "    <nodes>")?;
+        for node in &self.nodes {
+            writeln!(w, r#"      <node id="{}" label="{}">"#, escape_xml(&node.id), escape_xml(&node.label))?;
+            writeln!(w, "        <attvalues>")?;
+            writeln!(w, r#"          <attvalue for="family" value="{}"/>"#, node.family)?;
+            for (key, value) in &node.attrs {
+                writeln!(w, r#"          <attvalue for="{}" value="{}"/>"#, escape_xml(key), escape_xml(&attr_str(value)))?;
+            }
+            writeln!(w, "        </attvalues>")?;
+            writeln!(w, "      </node>")?;
+        }
+        writeln!(w, "    </nodes>")?;
+
+        writeln!(w, "    <edges>")?;
+        for (i, edge) in self.edges.iter().enumerate() {
+            writeln!(w, r#"      <edge id="{}" source="{}" target="{}" label="{}">"#, i, escape_xml(&edge.source), escape_xml(&edge.target), edge.label)?;
+            writeln!(w, "        <attvalues>")?;
+            for (key, value) in &edge.attrs {
+                writeln!(w, r#"          <attvalue for="{}" value="{}"/>"#, escape_xml(key), escape_xml(&attr_str(value)))?;
+            }
+            writeln!(w, "        </attvalues>")?;
+            writeln!(w, "      </edge>")?;
+        }
+        writeln!(w, "    </edges>")?;
+
+        writeln!(w, "  </graph>")?;
+        writeln!(w, "</gexf>")?;
+
+        Ok(())
+    }
+
+    pub fn write_dot<W: Write + ?Sized>(&self, w: &mut W) -> Result<()> {
+        writeln!(w, "digraph sn0int {{")?;
+        for node in &self.nodes {
+            write!(w, "    {} [label={}, family={}", escape_dot(&node.id), escape_dot(&node.label), escape_dot(node.family))?;
+            for (key, value) in &node.attrs {
+                write!(w, ", {}={}", escape_dot(key), escape_dot(&attr_str(value)))?;
+            }
+            writeln!(w, "];")?;
+        }
+        for edge in &self.edges {
+            write!(w, "    {} -> {} [label={}", escape_dot(&edge.source), escape_dot(&edge.target), escape_dot(edge.label))?;
+            for (key, value) in &edge.attrs {
+                write!(w, ", {}={}", escape_dot(key), escape_dot(&attr_str(value)))?;
+            }
+            writeln!(w, "];")?;
+        }
+        writeln!(w, "}}")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::default();
+        graph.add_nodes(Family::Domain, &[Domain {
+            id: 1,
+            value: "example.com".to_string(),
+            unscoped: false,
+            first_observed: None,
+            last_observed: None,
+        }]).unwrap();
+        graph.add_nodes(Family::Subdomain, &[Subdomain {
+            id: 2,
+            domain_id: 1,
+            value: "\"www\".example.com".to_string(),
+            unscoped: true,
+            resolvable: None,
+            first_observed: None,
+            last_observed: None,
+        }]).unwrap();
+        graph.add_edge(&Family::Domain, 1, &Family::Subdomain, 2, "subdomain", Vec::new());
+        graph
+    }
+
+    #[test]
+    fn test_attrs_skip_ids_and_nulls() {
+        let subdomain = Subdomain {
+            id: 2,
+            domain_id: 1,
+            value: "www.example.com".to_string(),
+            unscoped: false,
+            resolvable: None,
+            first_observed: None,
+            last_observed: None,
+        };
+        let attrs = attrs(&subdomain, &["domain_id"]).unwrap();
+        assert_eq!(attrs, vec![
+            ("unscoped".to_string(), Value::Bool(false)),
+            ("value".to_string(), Value::String("www.example.com".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_write_dot() {
+        let mut buf = Vec::new();
+        graph().write_dot(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), r#"digraph sn0int {
+    "domain:1" [label="example.com", family="domain", "unscoped"="false", "value"="example.com"];
+    "subdomain:2" [label="\"www\".example.com", family="subdomain", "unscoped"="true", "value"="\"www\".example.com"];
+    "domain:1" -> "subdomain:2" [label="subdomain"];
+}
+"#);
+    }
+
+    #[test]
+    fn test_ip_family_is_skipped() {
+        let mut graph = Graph::default();
+        graph.add_nodes(Family::Netblock, &[Netblock {
+            id: 1,
+            family: "4".to_string(),
+            value: "192.0.2.0/24".to_string(),
+            unscoped: false,
+            asn: None,
+            as_org: None,
+            description: None,
+            first_observed: None,
+            last_observed: None,
+        }]).unwrap();
+
+        let mut buf = Vec::new();
+        graph.write_graphml(&mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out.matches(r#"<key id="n_family""#).count(), 1);
+        assert!(out.contains(r#"<data key="n_family">netblock</data>"#));
+
+        let mut buf = Vec::new();
+        graph.write_gexf(&mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert_eq!(out.matches(r#"<attribute id="family""#).count(), 1);
+    }
+
+    #[test]
+    fn test_write_graphml_keys() {
+        let mut buf = Vec::new();
+        graph().write_graphml(&mut buf).unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains(r#"<key id="n_unscoped" for="node" attr.name="unscoped" attr.type="boolean"/>"#));
+        assert!(out.contains(r#"<edge id="e0" source="domain:1" target="subdomain:2">"#));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::errors::*;
+use serde::Serialize;
+use serde_json::Value;
+
+pub mod geo;
+pub mod graph;
+pub mod ndjson;
+pub mod stix;
+
+pub(crate) type Attrs = Vec<(String, Value)>;
+
+/// Flatten an entity into its scalar fields, the row id and foreign keys are skipped
+pub(crate) fn attrs<T: Serialize>(obj: &T, skip: &[&str]) -> Result<Attrs> {
+    let obj = match serde_json::to_value(obj)? {
+        Value::Object(obj) => obj,
+        _ => bail!("Entity didn't serialize to an object"),
+    };
+
+    let attrs = obj.into_iter()
+        .filter(|(k, _)| k != "id" && !skip.contains(&k.as_str()))
+        .filter(|(_, v)| matches!(v, Value::Bool(_) | Value::Number(_) | Value::String(_)))
+        .collect();
+    Ok(attrs)
+}
+
+pub(crate) fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // not allowed in xml 1.0
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => (),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a href=\"x\">&'</a>"), "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;");
+        assert_eq!(escape_xml("a\x00b"), "ab");
+    }
+}
@@ -0,0 +1,134 @@
+use crate::autonoscope::Autonoscope;
+use crate::blobs::{Blob, BlobStorage};
+use crate::cmd::export_cmd::FORMAT_VERSION;
+use crate::db::Database;
+use crate::db::history::History;
+use crate::db::provenance::{Provenance, Run};
+use crate::db::tags::{Note, Tag};
+use crate::db::ttl::Ttl;
+use crate::errors::*;
+use crate::models::*;
+use crate::schema::*;
+use diesel::prelude::*;
+use serde::Serialize;
+use std::io::Write;
+
+/// Number of rows that are loaded from the database at once
+const PAGE_SIZE: i64 = 1024;
+
+#[derive(Serialize)]
+struct Header {
+    format_version: u32,
+    sn0int_version: &'static str,
+}
+
+#[derive(Serialize)]
+struct Record<'a, T: Serialize> {
+    #[serde(rename="type")]
+    kind: &'a str,
+    data: T,
+}
+
+#[derive(Serialize)]
+struct BlobRecord<'a> {
+    id: &'a str,
+    bytes: &'a Blob,
+}
+
+fn write_record<W: Write, T: Serialize>(w: &mut W, kind: &str, data: T) -> Result<()> {
+    serde_json::to_writer(&mut *w, &Record {
+        kind,
+        data,
+    })?;
+    w.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Write every row of a table, loading at most PAGE_SIZE rows at a time
+macro_rules! stream_table {
+    ( $w:expr, $db:expr, $table:ident, $model:ty ) => {{
+        let mut last_id = 0;
+        loop {
+            let rows = $table::table
+                .filter($table::id.gt(last_id))
+                .order_by($table::id.asc())
+                .limit(PAGE_SIZE)
+                .load::<$model>($db.db())?;
+
+            let rows_len = rows.len();
+            for row in rows {
+                last_id = row.id;
+                write_record($w, stringify!($table), &row)?;
+            }
+
+            if (rows_len as i64) < PAGE_SIZE {
+                break;
+            }
+        }
+    }};
+}
+
+/// Stream the workspace as one tagged json record per line
+///
+/// Entities are written in an order that guarantees that every row an entity
+/// references has been written before.
+pub fn export<W: Write>(w: &mut W, db: &Database, storage: Option<&BlobStorage>) -> Result<()> {
+    write_record(w, "header", Header {
+        format_version: FORMAT_VERSION,
+        sn0int_version: env!("CARGO_PKG_VERSION"),
+    })?;
+
+    stream_table!(w, db, domains, Domain);
+    stream_table!(w, db, subdomains, Subdomain);
+    stream_table!(w, db, ipaddrs, IpAddr);
+    stream_table!(w, db, subdomain_ipaddrs, SubdomainIpAddr);
+    stream_table!(w, db, urls, Url);
+    stream_table!(w, db, emails, Email);
+    stream_table!(w, db, phonenumbers, PhoneNumber);
+    stream_table!(w, db, devices, Device);
+    stream_table!(w, db, networks, Network);
+    stream_table!(w, db, network_devices, NetworkDevice);
+    stream_table!(w, db, accounts, Account);
+    stream_table!(w, db, breaches, Breach);
+    stream_table!(w, db, breach_emails, BreachEmail);
+    stream_table!(w, db, images, Image);
+    stream_table!(w, db, ports, Port);
+    stream_table!(w, db, netblocks, Netblock);
+    stream_table!(w, db, cryptoaddrs, CryptoAddr);
+    stream_table!(w, db, certificates, Certificate);
+    stream_table!(w, db, port_certificates, PortCertificate);
+    stream_table!(w, db, subdomain_certificates, SubdomainCertificate);
+    stream_table!(w, db, dns_records, DnsRecord);
+    stream_table!(w, db, persons, Person);
+    stream_table!(w, db, organizations, Organization);
+    stream_table!(w, db, person_emails, PersonEmail);
+    stream_table!(w, db, person_accounts, PersonAccount);
+    stream_table!(w, db, person_phonenumbers, PersonPhoneNumber);
+    stream_table!(w, db, person_images, PersonImage);
+    stream_table!(w, db, person_organizations, PersonOrganization);
+    stream_table!(w, db, organization_domains, OrganizationDomain);
+    stream_table!(w, db, asns, Asn);
+    stream_table!(w, db, asn_netblocks, AsnNetblock);
+    stream_table!(w, db, asn_ipaddrs, AsnIpAddr);
+    stream_table!(w, db, autonoscope, Autonoscope);
+    stream_table!(w, db, ttls, Ttl);
+    stream_table!(w, db, tags, Tag);
+    stream_table!(w, db, notes, Note);
+    stream_table!(w, db, activity, Activity);
+    stream_table!(w, db, runs, Run);
+    stream_table!(w, db, provenance, Provenance);
+    stream_table!(w, db, history, History);
+
+    if let Some(storage) = storage {
+        for id in storage.list()? {
+            let blob = storage.load(&id)?;
+            write_record(w, "blobs", BlobRecord {
+                id: &blob.id,
+                bytes: &blob,
+            })?;
+        }
+    }
+
+    w.flush()?;
+    Ok(())
+}
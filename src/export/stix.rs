@@ -0,0 +1,218 @@
+use crate::db::Database;
+use crate::errors::*;
+use crate::models::*;
+use serde_json::{json, Value};
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::net;
+
+/// Namespace for deterministic STIX Cyber-observable Object ids, STIX 2.1 section 2.9
+const STIX_NAMESPACE: [u8; 16] = [
+    0x00, 0xab, 0xed, 0xb4, 0xaa, 0x42, 0x46, 0x6c,
+    0x9c, 0x01, 0xfe, 0xd2, 0x33, 0x15, 0xa9, 0xb7,
+];
+
+/// Relationships require timestamps, use a fixed one to keep the bundle deterministic
+const STIX_TIMESTAMP: &str = "1970-01-01T00:00:00.000Z";
+
+pub fn uuid5(namespace: &[u8; 16], name: &str) -> String {
+    let mut h = Sha1::new();
+    h.update(namespace);
+    h.update(name.as_bytes());
+    let hash = h.finalize();
+
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = bytes.iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// Derive the id of an observable from its value, this is stable across exports
+fn sco_id(stix_type: &str, value: &str) -> String {
+    let contributing = json!({ "value": value });
+    format!("{}--{}", stix_type, uuid5(&STIX_NAMESPACE, &contributing.to_string()))
+}
+
+fn ipaddr_type(value: &str) -> Result<&'static str> {
+    let ipaddr = value.parse::<net::IpAddr>()?;
+    Ok(match ipaddr {
+        net::IpAddr::V4(_) => "ipv4-addr",
+        net::IpAddr::V6(_) => "ipv6-addr",
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct Bundle {
+    // sorted by id so the output doesn't depend on the order of the database
+    objects: BTreeMap<String, Value>,
+}
+
+impl Bundle {
+    pub fn load(db: &Database) -> Result<Bundle> {
+        let mut bundle = Bundle::default();
+
+        let mut domains = BTreeMap::new();
+        for x in Domain::list(db)? {
+            let id = bundle.add_observable("domain-name", &x.value, json!({}));
+            domains.insert(x.id, id);
+        }
+
+        let mut subdomains = BTreeMap::new();
+        for x in Subdomain::list(db)? {
+            let id = bundle.add_observable("domain-name", &x.value, json!({}));
+            if let Some(domain) = domains.get(&x.domain_id) {
+                bundle.add_relationship("related-to", domain, &id);
+            }
+            subdomains.insert(x.id, id);
+        }
+
+        let mut ipaddrs = BTreeMap::new();
+        for x in IpAddr::list(db)? {
+            let id = bundle.add_observable(ipaddr_type(&x.value)?, &x.value, json!({}));
+            ipaddrs.insert(x.id, id);
+        }
+
+        for x in SubdomainIpAddr::list(db)? {
+            if let (Some(subdomain), Some(ipaddr)) = (subdomains.get(&x.subdomain_id), ipaddrs.get(&x.ip_addr_id)) {
+                bundle.add_relationship("resolves-to", subdomain, ipaddr);
+            }
+        }
+
+        for x in Url::list(db)? {
+            let id = bundle.add_observable("url", &x.value, json!({}));
+            if let Some(subdomain) = subdomains.get(&x.subdomain_id) {
+                bundle.add_relationship("related-to", subdomain, &id);
+            }
+        }
+
+        for x in Email::list(db)? {
+            bundle.add_observable("email-addr", &x.value, json!({
+                "display_name": x.displayname,
+            }));
+        }
+
+        for x in CryptoAddr::list(db)? {
+            bundle.add_observable("x-sn0int-cryptoaddr", &x.value, json!({
+                "currency": x.currency,
+                "denominator": x.denominator,
+                "balance": x.balance,
+                "received": x.received,
+                "first_seen": x.first_seen,
+                "last_withdrawal": x.last_withdrawal,
+                "description": x.description,
+            }));
+        }
+
+        Ok(bundle)
+    }
+
+    fn add_observable(&mut self, stix_type: &str, value: &str, extra: Value) -> String {
+        let id = sco_id(stix_type, value);
+
+        let mut obj = json!({
+            "type": stix_type,
+            "spec_version": "2.1",
+            "id": id,
+            "value": value,
+        });
+        if let (Value::Object(obj), Value::Object(extra)) = (&mut obj, extra) {
+            obj.extend(extra.into_iter().filter(|(_, v)| !v.is_null()));
+        }
+
+        // a domain and a subdomain with the same value are the same observable
+        self.objects.entry(id.clone())
+            .or_insert(obj);
+        id
+    }
+
+    fn add_relationship(&mut self, relationship_type: &str, source: &str, target: &str) {
+        if source == target {
+            return;
+        }
+
+        let name = format!("{}+{}+{}", relationship_type, source, target);
+        let id = format!("relationship--{}", uuid5(&STIX_NAMESPACE, &name));
+
+        self.objects.insert(id.clone(), json!({
+            "type": "relationship",
+            "spec_version": "2.1",
+            "id": id,
+            "created": STIX_TIMESTAMP,
+            "modified": STIX_TIMESTAMP,
+            "relationship_type": relationship_type,
+            "source_ref": source,
+            "target_ref": target,
+        }));
+    }
+
+    pub fn write<W: Write + ?Sized>(self, w: &mut W) -> Result<()> {
+        let ids = self.objects.keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(",");
+        let id = format!("bundle--{}", uuid5(&STIX_NAMESPACE, &ids));
+
+        let objects = self.objects.into_values().collect::<Vec<_>>();
+        serde_json::to_writer(&mut *w, &json!({
+            "type": "bundle",
+            "id": id,
+            "objects": objects,
+        }))?;
+        w.write_all(b"\n")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // uuid.NAMESPACE_DNS
+    const DNS_NAMESPACE: [u8; 16] = [
+        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1,
+        0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8,
+    ];
+
+    #[test]
+    fn test_uuid5() {
+        let uuid = uuid5(&DNS_NAMESPACE, "python.org");
+        assert_eq!(uuid, "886313e1-3b8a-5372-9b90-0c9aee199e5d");
+    }
+
+    #[test]
+    fn test_domain_and_subdomain_dedupe() {
+        let mut bundle = Bundle::default();
+        let a = bundle.add_observable("domain-name", "example.com", json!({}));
+        let b = bundle.add_observable("domain-name", "example.com", json!({}));
+        bundle.add_relationship("related-to", &a, &b);
+        assert_eq!(a, b);
+        assert_eq!(bundle.objects.len(), 1);
+    }
+
+    #[test]
+    fn test_ipaddr_type() {
+        assert_eq!(ipaddr_type("192.0.2.1").unwrap(), "ipv4-addr");
+        assert_eq!(ipaddr_type("2001:db8::1").unwrap(), "ipv6-addr");
+    }
+
+    #[test]
+    fn test_bundle_is_deterministic() {
+        let build = |values: &[&str]| {
+            let mut bundle = Bundle::default();
+            for value in values {
+                bundle.add_observable("domain-name", value, json!({}));
+            }
+            let mut buf = Vec::new();
+            bundle.write(&mut buf).unwrap();
+            buf
+        };
+        assert_eq!(build(&["a.example.com", "b.example.com"]), build(&["b.example.com", "a.example.com"]));
+    }
+}
@@ -46,6 +46,21 @@ pub enum Target {
     /// On crypto currency addresses
     #[command(name="cryptoaddrs")]
     CryptoAddrs(Filter),
+    /// On x509 certificates
+    #[command(name="certificates")]
+    Certificates(Filter),
+    /// On dns records
+    #[command(name="dns-records")]
+    DnsRecords(Filter),
+    /// On persons
+    #[command(name="persons")]
+    Persons(Filter),
+    /// On organizations
+    #[command(name="organizations")]
+    Organizations(Filter),
+    /// On autonomous systems
+    #[command(name="asns")]
+    Asns(Filter),
 }
 
 impl Target {
@@ -96,6 +111,14 @@ impl Target {
             None
         }
     }
+
+    pub fn asns(&self) -> Option<&Filter> {
+        if let Target::Asns(f) = self {
+            Some(f)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
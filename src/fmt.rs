@@ -68,6 +68,25 @@ pub mod colors {
             write!(w, "\x1b[33m{:?}\x1b[0m", v)
         }
     }
+
+    pub struct Grey;
+
+    impl Color for Grey {
+        #[inline]
+        fn color<W: Write>(w: &mut W) -> fmt::Result {
+            write!(w, "\x1b[90m")
+        }
+
+        #[inline]
+        fn display<W: Write, D: fmt::Display>(w: &mut W, v: D) -> fmt::Result {
+            write!(w, "\x1b[90m{}\x1b[0m", v)
+        }
+
+        #[inline]
+        fn debug<W: Write, D: fmt::Debug>(w: &mut W, v: D) -> fmt::Result {
+            write!(w, "\x1b[90m{:?}\x1b[0m", v)
+        }
+    }
 }
 use self::colors::*;
 
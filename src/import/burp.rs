@@ -0,0 +1,179 @@
+use crate::errors::*;
+use crate::xml::{self, XmlElement};
+use data_encoding::BASE64;
+use super::{is_http, Response};
+
+/// A raw http response split into its parts
+#[derive(Debug, PartialEq)]
+struct HttpResponse {
+    status: Option<i32>,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len())
+        .position(|x| x == needle)
+}
+
+fn parse_http_response(bytes: &[u8]) -> HttpResponse {
+    let (head, body) = if let Some(idx) = find_subslice(bytes, b"\r\n\r\n") {
+        (&bytes[..idx], &bytes[idx+4..])
+    } else if let Some(idx) = find_subslice(bytes, b"\n\n") {
+        (&bytes[..idx], &bytes[idx+2..])
+    } else {
+        (bytes, &b""[..])
+    };
+
+    let head = String::from_utf8_lossy(head);
+    let mut lines = head.lines();
+
+    let status = lines.next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok());
+
+    let headers = lines
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect();
+
+    HttpResponse {
+        status,
+        headers,
+        body: body.to_vec(),
+    }
+}
+
+fn child_text<'a>(element: &'a XmlElement, name: &str) -> Option<&'a str> {
+    element.children.iter()
+        .find(|x| x.name == name)
+        .and_then(|x| x.text.as_deref())
+}
+
+fn parse_item(item: &XmlElement) -> Result<Option<Response>> {
+    let url = child_text(item, "url")
+        .ok_or_else(|| format_err!("Item is missing url"))?;
+    if !is_http(url) {
+        return Ok(None);
+    }
+
+    let raw = item.children.iter()
+        .find(|x| x.name == "response");
+    let raw = match raw {
+        Some(raw) => raw,
+        None => return Ok(None),
+    };
+    let text = match &raw.text {
+        Some(text) => text,
+        // the request didn't receive a response
+        None => return Ok(Some(Response {
+            url: url.to_string(),
+            ..Default::default()
+        })),
+    };
+
+    let bytes = if raw.attrs.get("base64").map(String::as_str) == Some("true") {
+        BASE64.decode(text.as_bytes())
+            .context("Failed to decode response")?
+    } else {
+        text.as_bytes().to_vec()
+    };
+    let response = parse_http_response(&bytes);
+
+    let status = child_text(item, "status")
+        .and_then(|x| x.parse().ok())
+        .or(response.status);
+    let redirect = response.header("location")
+        .map(String::from);
+    let mime = response.header("content-type")
+        .map(String::from);
+    let body = Some(response.body)
+        .filter(|x| !x.is_empty());
+
+    Ok(Some(Response {
+        url: url.to_string(),
+        status,
+        redirect,
+        mime,
+        body,
+    }))
+}
+
+/// Parse the xml written by burp's "Save items"
+pub fn parse(x: &str) -> Result<Vec<Response>> {
+    let doc = xml::decode_raw(x)?;
+    let items = doc.children.iter()
+        .find(|x| x.name == "items")
+        .ok_or_else(|| format_err!("Not a burp xml file"))?;
+
+    let mut responses = Vec::new();
+    for item in items.children.iter().filter(|x| x.name == "item") {
+        if let Some(response) = parse_item(item)? {
+            responses.push(response);
+        }
+    }
+
+    Ok(responses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_response() {
+        let response = parse_http_response(b"HTTP/1.1 302 Found\r\nLocation: /login\r\nContent-Length: 0\r\n\r\n");
+        assert_eq!(response, HttpResponse {
+            status: Some(302),
+            headers: vec![
+                ("Location".to_string(), "/login".to_string()),
+                ("Content-Length".to_string(), "0".to_string()),
+            ],
+            body: Vec::new(),
+        });
+        assert_eq!(response.header("location"), Some("/login"));
+    }
+
+    #[test]
+    fn test_parse_burp() {
+        let responses = parse(r#"<?xml version="1.0"?>
+<items burpVersion="2023.10.3.4" exportTime="Mon Oct 16 12:00:00 CEST 2023">
+  <item>
+    <time>Mon Oct 16 12:00:00 CEST 2023</time>
+    <url><![CDATA[https://example.com/]]></url>
+    <host ip="93.184.216.34">example.com</host>
+    <port>443</port>
+    <protocol>https</protocol>
+    <method><![CDATA[GET]]></method>
+    <path><![CDATA[/]]></path>
+    <extension>null</extension>
+    <request base64="true"><![CDATA[R0VUIC8gSFRUUC8xLjENCkhvc3Q6IGV4YW1wbGUuY29tDQoNCg==]]></request>
+    <status>200</status>
+    <responselength>95</responselength>
+    <mimetype>HTML</mimetype>
+    <response base64="true"><![CDATA[SFRUUC8xLjEgMjAwIE9LDQpDb250ZW50LVR5cGU6IHRleHQvaHRtbA0KDQo8aHRtbD48aGVhZD48dGl0bGU+RXhhbXBsZTwvdGl0bGU+PC9oZWFkPjwvaHRtbD4=]]></response>
+    <comment></comment>
+  </item>
+</items>
+"#).unwrap();
+
+        assert_eq!(responses, vec![Response {
+            url: "https://example.com/".to_string(),
+            status: Some(200),
+            redirect: None,
+            mime: Some("text/html".to_string()),
+            body: Some(b"<html><head><title>Example</title></head></html>".to_vec()),
+        }]);
+        assert_eq!(responses[0].title(), Some("Example".to_string()));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::errors::*;
+use data_encoding::BASE64;
+use serde::Deserialize;
+use super::{is_http, Response};
+
+#[derive(Debug, Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Debug, Deserialize)]
+struct Log {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    request: HarRequest,
+    response: HarResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarRequest {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HarResponse {
+    status: i32,
+    #[serde(rename="redirectURL", default)]
+    redirect_url: String,
+    #[serde(default)]
+    headers: Vec<Header>,
+    content: Option<Content>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Content {
+    #[serde(rename="mimeType")]
+    mime_type: Option<String>,
+    text: Option<String>,
+    encoding: Option<String>,
+}
+
+impl Content {
+    fn body(&self) -> Result<Option<Vec<u8>>> {
+        let text = match &self.text {
+            Some(text) if !text.is_empty() => text,
+            _ => return Ok(None),
+        };
+
+        let body = if self.encoding.as_deref() == Some("base64") {
+            BASE64.decode(text.as_bytes())
+                .context("Failed to decode response body")?
+        } else {
+            text.as_bytes().to_vec()
+        };
+
+        Ok(Some(body))
+    }
+}
+
+fn parse_entry(entry: Entry) -> Result<Response> {
+    let response = entry.response;
+
+    // a status of 0 means the request didn't receive a response
+    if response.status == 0 {
+        return Ok(Response {
+            url: entry.request.url,
+            ..Default::default()
+        });
+    }
+
+    let redirect = Some(response.redirect_url)
+        .filter(|x| !x.is_empty())
+        .or_else(|| {
+            response.headers.into_iter()
+                .find(|h| h.name.eq_ignore_ascii_case("location"))
+                .map(|h| h.value)
+        });
+
+    let (mime, body) = match response.content {
+        Some(content) => (content.mime_type.clone(), content.body()?),
+        None => (None, None),
+    };
+
+    Ok(Response {
+        url: entry.request.url,
+        status: Some(response.status),
+        redirect,
+        mime,
+        body,
+    })
+}
+
+/// Parse a HTTP Archive as exported by browsers and proxies
+pub fn parse(x: &str) -> Result<Vec<Response>> {
+    let har = serde_json::from_str::<Har>(x)
+        .context("Failed to parse har file")?;
+
+    let mut responses = Vec::new();
+    for entry in har.log.entries {
+        if !is_http(&entry.request.url) {
+            continue;
+        }
+        responses.push(parse_entry(entry)?);
+    }
+
+    Ok(responses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_har() {
+        let responses = parse(r#"{
+  "log": {
+    "version": "1.2",
+    "creator": {"name": "Firefox", "version": "115.0"},
+    "entries": [
+      {
+        "request": {"method": "GET", "url": "http://example.com/", "headers": []},
+        "response": {
+          "status": 301,
+          "statusText": "Moved Permanently",
+          "headers": [{"name": "Location", "value": "https://example.com/"}],
+          "content": {"size": 0, "mimeType": "text/html"},
+          "redirectURL": ""
+        }
+      },
+      {
+        "request": {"method": "GET", "url": "https://example.com/", "headers": []},
+        "response": {
+          "status": 200,
+          "statusText": "OK",
+          "headers": [],
+          "content": {"size": 13, "mimeType": "text/html; charset=utf-8", "text": "PGh0bWw+PC9odG1sPg==", "encoding": "base64"},
+          "redirectURL": ""
+        }
+      },
+      {
+        "request": {"method": "GET", "url": "data:image/png;base64,AAAA", "headers": []},
+        "response": {"status": 200, "headers": [], "content": {"size": 3, "mimeType": "image/png"}}
+      },
+      {
+        "request": {"method": "GET", "url": "https://example.com/blocked", "headers": []},
+        "response": {"status": 0, "headers": [], "content": {"size": 0, "mimeType": "x-unknown"}}
+      }
+    ]
+  }
+}"#).unwrap();
+
+        assert_eq!(responses, vec![
+            Response {
+                url: "http://example.com/".to_string(),
+                status: Some(301),
+                redirect: Some("https://example.com/".to_string()),
+                mime: Some("text/html".to_string()),
+                body: None,
+            },
+            Response {
+                url: "https://example.com/".to_string(),
+                status: Some(200),
+                redirect: None,
+                mime: Some("text/html; charset=utf-8".to_string()),
+                body: Some(b"<html></html>".to_vec()),
+            },
+            Response {
+                url: "https://example.com/blocked".to_string(),
+                ..Default::default()
+            },
+        ]);
+    }
+}
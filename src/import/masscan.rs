@@ -0,0 +1,111 @@
+use crate::errors::*;
+use serde::Deserialize;
+use super::{Hosts, Service};
+use std::net;
+
+#[derive(Debug, Deserialize)]
+struct Record {
+    ip: net::IpAddr,
+    #[serde(default)]
+    ports: Vec<RecordPort>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordPort {
+    port: u16,
+    proto: String,
+    status: Option<String>,
+    service: Option<RecordService>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordService {
+    name: Option<String>,
+    banner: Option<String>,
+}
+
+/// Parse the output of `masscan -oJ`, this is either a json array with one
+/// record per line or newline delimited json
+pub fn parse(x: &str) -> Result<Hosts> {
+    let mut hosts = Hosts::new();
+
+    for (i, line) in x.lines().enumerate() {
+        let line = line.trim();
+        let line = line.strip_suffix(',').unwrap_or(line);
+        if line.is_empty() || line == "[" || line == "]" {
+            continue;
+        }
+        // older masscan versions terminate the output with an invalid json object
+        if line.starts_with("{finished:") {
+            continue;
+        }
+
+        let record = serde_json::from_str::<Record>(line)
+            .context(format!("Invalid masscan record in line {}", i + 1))?;
+
+        let host = hosts.entry(record.ip).or_default();
+        for port in record.ports {
+            let (service, banner) = match port.service {
+                Some(service) => (service.name, service.banner),
+                None => (None, None),
+            };
+
+            // masscan reports open ports without an explicit status
+            let status = port.status.unwrap_or_else(|| String::from("open"));
+            if status != "open" && status != "closed" {
+                continue;
+            }
+
+            host.add_port(port.proto, port.port, Service {
+                status: Some(status),
+                service: service.filter(|x| !x.is_empty()),
+                version: None,
+                banner: banner.filter(|x| !x.is_empty()),
+            });
+        }
+    }
+
+    Ok(hosts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_masscan() {
+        let hosts = parse(r#"[
+{   "ip": "192.0.2.1",   "timestamp": "1700000000", "ports": [ {"port": 80, "proto": "tcp", "status": "open", "reason": "syn-ack", "ttl": 54} ] },
+{   "ip": "192.0.2.1",   "timestamp": "1700000001", "ports": [ {"port": 80, "proto": "tcp", "service": {"name": "http", "banner": "HTTP/1.1 200 OK"} } ] },
+{   "ip": "192.0.2.2",   "timestamp": "1700000002", "ports": [ {"port": 53, "proto": "udp", "status": "open", "reason": "none", "ttl": 54} ] }
+]
+"#).unwrap();
+
+        assert_eq!(hosts.len(), 2);
+        let host = &hosts[&"192.0.2.1".parse::<net::IpAddr>().unwrap()];
+        assert_eq!(host.ports.len(), 1);
+        assert_eq!(host.ports[&("tcp".to_string(), 80)], Service {
+            status: Some("open".to_string()),
+            service: Some("http".to_string()),
+            version: None,
+            banner: Some("HTTP/1.1 200 OK".to_string()),
+        });
+
+        let host = &hosts[&"192.0.2.2".parse::<net::IpAddr>().unwrap()];
+        assert!(host.ports.contains_key(&("udp".to_string(), 53)));
+    }
+
+    #[test]
+    fn test_parse_masscan_ndjson() {
+        let hosts = parse(r#"{"ip": "2001:db8::1", "ports": [{"port": 443, "proto": "tcp", "status": "open"}]}
+{"ip": "2001:db8::2", "ports": [{"port": 443, "proto": "tcp", "status": "filtered"}]}
+"#).unwrap();
+        assert_eq!(hosts.len(), 2);
+        assert!(hosts[&"2001:db8::2".parse::<net::IpAddr>().unwrap()].ports.is_empty());
+    }
+
+    #[test]
+    fn test_parse_masscan_invalid() {
+        assert!(parse("[\n{\"foo\": 1}\n]").is_err());
+    }
+}
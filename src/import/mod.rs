@@ -0,0 +1,307 @@
+use crate::errors::*;
+use bytes::Bytes;
+use crate::blobs::Blob;
+use crate::html;
+use crate::models::*;
+use crate::shell::Shell;
+use crate::term::{self, Term};
+use crate::worker::{DatabaseEvent, DatabaseResponse};
+use sn0int_std::ratelimits::Ratelimiter;
+use std::collections::BTreeMap;
+use std::net;
+use std::sync::mpsc;
+
+pub mod burp;
+pub mod har;
+pub mod masscan;
+pub mod nmap;
+
+/// Response bodies larger than this are stored as blobs
+const BLOB_THRESHOLD: usize = 64 * 1024;
+
+/// A port as reported by a scanner
+#[derive(Debug, Default, PartialEq)]
+pub struct Service {
+    pub status: Option<String>,
+    pub service: Option<String>,
+    pub version: Option<String>,
+    pub banner: Option<String>,
+}
+
+impl Service {
+    /// Merge a second report of the same port, known fields are kept
+    fn merge(&mut self, other: Service) {
+        self.status = self.status.take().or(other.status);
+        self.service = self.service.take().or(other.service);
+        self.version = self.version.take().or(other.version);
+        self.banner = self.banner.take().or(other.banner);
+    }
+}
+
+/// A host as reported by a scanner
+#[derive(Debug, Default, PartialEq)]
+pub struct Host {
+    pub hostnames: Vec<String>,
+    pub reverse_dns: Option<String>,
+    /// Keyed by (protocol, port)
+    pub ports: BTreeMap<(String, u16), Service>,
+}
+
+impl Host {
+    fn add_port(&mut self, protocol: String, port: u16, service: Service) {
+        self.ports.entry((protocol, port))
+            .or_default()
+            .merge(service);
+    }
+}
+
+/// All hosts of a scan, sorted by address
+pub type Hosts = BTreeMap<net::IpAddr, Host>;
+
+/// A http response captured by a browser or proxy
+#[derive(Debug, Default, PartialEq)]
+pub struct Response {
+    pub url: String,
+    pub status: Option<i32>,
+    pub redirect: Option<String>,
+    pub mime: Option<String>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl Response {
+    fn title(&self) -> Option<String> {
+        let mime = self.mime.as_deref()?;
+        if !mime.starts_with("text/html") {
+            return None;
+        }
+
+        let body = self.body.as_ref()?;
+        let body = String::from_utf8_lossy(body);
+        let title = html::html_select(&body, "title").ok()?;
+        let title = title.text.trim();
+
+        if !title.is_empty() {
+            Some(title.to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// Only http urls can be added to the database
+fn is_http(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Insert entities the same way a module would, this applies autonoscope rules
+/// and sends notifications
+pub struct Inserter<'a> {
+    rl: &'a mut Shell,
+    ratelimit: Ratelimiter,
+}
+
+impl<'a> Inserter<'a> {
+    pub fn new(rl: &'a mut Shell) -> Inserter<'a> {
+        Inserter {
+            rl,
+            ratelimit: Ratelimiter::new(),
+        }
+    }
+
+    /// Returns None if the entity is out of scope
+    pub fn insert(&mut self, object: Insert) -> Result<Option<i32>> {
+        let (tx, rx) = mpsc::channel();
+        DatabaseEvent::Insert(object).apply(self.rl, &mut Term, &mut self.ratelimit, tx, 0, None);
+
+        let response = rx.recv()?
+            .map_err(|err| format_err!("Failed to insert: {}", err))?;
+
+        Ok(match response {
+            DatabaseResponse::Inserted(id) => Some(id),
+            DatabaseResponse::Updated(id) => Some(id),
+            DatabaseResponse::Found(id) => Some(id),
+            DatabaseResponse::NoChange(id) => Some(id),
+            DatabaseResponse::Tags(_) => None,
+            DatabaseResponse::None => None,
+        })
+    }
+
+    /// Insert a subdomain and the domain it belongs to
+    pub fn insert_subdomain(&mut self, subdomain: &str) -> Result<Option<i32>> {
+        let dns_name = self.rl.psl()?.parse_dns_name(subdomain)
+            .map_err(|e| format_err!("Failed to parse dns_name: {}", e))?;
+
+        let domain_id = match self.insert(Insert::Domain(NewDomain {
+            value: dns_name.root,
+            unscoped: false,
+        }))? {
+            Some(domain_id) => domain_id,
+            None => return Ok(None),
+        };
+
+        self.insert(Insert::Subdomain(NewSubdomain {
+            domain_id,
+            value: subdomain.to_string(),
+            resolvable: None,
+            unscoped: false,
+        }))
+    }
+
+    pub fn insert_hosts(&mut self, hosts: Hosts) -> Result<()> {
+        for (addr, host) in hosts {
+            let family = match addr {
+                net::IpAddr::V4(_) => "4",
+                net::IpAddr::V6(_) => "6",
+            };
+
+            let ip_addr_id = match self.insert(Insert::IpAddr(NewIpAddr {
+                family: family.to_string(),
+                value: addr.to_string(),
+                continent: None,
+                continent_code: None,
+                country: None,
+                country_code: None,
+                city: None,
+                latitude: None,
+                longitude: None,
+                asn: None,
+                as_org: None,
+                description: None,
+                reverse_dns: host.reverse_dns,
+                unscoped: false,
+            }))? {
+                Some(ip_addr_id) => ip_addr_id,
+                None => continue,
+            };
+
+            for hostname in host.hostnames {
+                let subdomain_id = match self.insert_subdomain(&hostname) {
+                    Ok(Some(subdomain_id)) => subdomain_id,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        term::error(&format!("Skipping hostname {:?}: {}", hostname, err));
+                        continue;
+                    },
+                };
+
+                self.insert(Insert::SubdomainIpAddr(NewSubdomainIpAddr {
+                    subdomain_id,
+                    ip_addr_id,
+                }))?;
+            }
+
+            for ((protocol, port), service) in host.ports {
+                let port = InsertPort {
+                    ip_addr_id,
+                    ip_addr: addr,
+                    port: port as i32,
+                    protocol,
+                    status: service.status,
+                    banner: service.banner,
+                    service: service.service,
+                    version: service.version,
+                }.try_into_new()?;
+
+                self.insert(Insert::Port(port))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn insert_response(&mut self, response: Response) -> Result<Option<i32>> {
+        let url = ::url::Url::parse(&response.url)?;
+        let host = match url.host() {
+            Some(::url::Host::Domain(host)) => host.to_string(),
+            _ => bail!("Url doesn't have a domain"),
+        };
+
+        let subdomain_id = match self.insert_subdomain(&host)? {
+            Some(subdomain_id) => subdomain_id,
+            None => return Ok(None),
+        };
+
+        let title = response.title();
+        let mut url = InsertUrl {
+            subdomain_id,
+            value: response.url,
+            status: response.status,
+            body: None,
+            online: response.status.map(|_| true),
+            title,
+            redirect: response.redirect,
+        }.try_into_new()?;
+
+        let blob = match response.body {
+            Some(body) if body.len() > BLOB_THRESHOLD => {
+                let blob = Blob::create(Bytes::from(body));
+                url.blob = Some(blob.id.clone());
+                Some(blob)
+            },
+            body => {
+                url.body = body;
+                None
+            },
+        };
+
+        let url_id = self.insert(Insert::Url(url))?;
+        // blobs are only written for urls that have been added, a skipped url
+        // doesn't leave an orphaned file behind
+        if let (Some(_), Some(blob)) = (url_id, blob) {
+            self.rl.blobs().save(&blob)?;
+        }
+
+        Ok(url_id)
+    }
+
+    pub fn insert_responses(&mut self, responses: Vec<Response>) -> Result<()> {
+        for response in responses {
+            let url = response.url.clone();
+            if let Err(err) = self.insert_response(response) {
+                term::error(&format!("Skipping url {:?}: {}", url, err));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Print the hosts of a scan instead of inserting them
+pub fn print_hosts(hosts: &Hosts) {
+    for (addr, host) in hosts {
+        term::info(&format!("ipaddr {}", addr));
+        for hostname in &host.hostnames {
+            term::info(&format!("  subdomain {}", hostname));
+        }
+        for ((protocol, port), service) in &host.ports {
+            let mut line = format!("  port {}/{}", protocol, port);
+            if let Some(status) = &service.status {
+                line.push_str(&format!(" {}", status));
+            }
+            if let Some(name) = &service.service {
+                line.push_str(&format!(" {}", name));
+            }
+            if let Some(version) = &service.version {
+                line.push_str(&format!(" ({})", version));
+            }
+            term::info(&line);
+        }
+    }
+}
+
+/// Print captured responses instead of inserting them
+pub fn print_responses(responses: &[Response]) {
+    for response in responses {
+        let mut line = format!("url {:?}", response.url);
+        if let Some(status) = response.status {
+            line.push_str(&format!(" ({}", status));
+            if let Some(redirect) = &response.redirect {
+                line.push_str(&format!(" => {:?}", redirect));
+            }
+            line.push(')');
+        }
+        if let Some(body) = &response.body {
+            line.push_str(&format!(" [{} bytes]", body.len()));
+        }
+        term::info(&line);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::errors::*;
+use crate::xml::{self, XmlElement};
+use super::{Host, Hosts, Service};
+use std::net;
+
+fn children<'a>(element: &'a XmlElement, name: &'a str) -> impl Iterator<Item=&'a XmlElement> {
+    element.children.iter()
+        .filter(move |x| x.name == name)
+}
+
+#[inline]
+fn child<'a>(element: &'a XmlElement, name: &'a str) -> Option<&'a XmlElement> {
+    children(element, name).next()
+}
+
+#[inline]
+fn attr(element: &XmlElement, name: &str) -> Option<String> {
+    element.attrs.get(name)
+        .filter(|x| !x.is_empty())
+        .cloned()
+}
+
+fn parse_service(port: &XmlElement) -> Service {
+    let status = child(port, "state")
+        .and_then(|x| attr(x, "state"));
+
+    let (service, version) = if let Some(service) = child(port, "service") {
+        let version = [attr(service, "product"), attr(service, "version")]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let mut version = version.join(" ");
+        if let Some(extrainfo) = attr(service, "extrainfo") {
+            version.push_str(&format!(" ({})", extrainfo));
+        }
+        let version = Some(version.trim().to_string())
+            .filter(|x| !x.is_empty());
+        (attr(service, "name"), version)
+    } else {
+        (None, None)
+    };
+
+    let banner = children(port, "script")
+        .find(|x| x.attrs.get("id").map(String::as_str) == Some("banner"))
+        .and_then(|x| attr(x, "output"));
+
+    Service {
+        status,
+        service,
+        version,
+        banner,
+    }
+}
+
+fn parse_host(element: &XmlElement) -> Result<Option<(net::IpAddr, Host)>> {
+    let addr = children(element, "address")
+        .find(|x| matches!(x.attrs.get("addrtype").map(String::as_str), Some("ipv4") | Some("ipv6")))
+        .and_then(|x| x.attrs.get("addr"));
+    let addr = match addr {
+        Some(addr) => addr.parse::<net::IpAddr>()?,
+        None => return Ok(None),
+    };
+
+    let mut host = Host::default();
+
+    if let Some(hostnames) = child(element, "hostnames") {
+        for hostname in children(hostnames, "hostname") {
+            let name = match attr(hostname, "name") {
+                Some(name) => name,
+                None => continue,
+            };
+
+            // reverse dns doesn't mean the name resolves to this address
+            if hostname.attrs.get("type").map(String::as_str) == Some("PTR") {
+                host.reverse_dns = Some(name);
+            } else if !host.hostnames.contains(&name) {
+                host.hostnames.push(name);
+            }
+        }
+    }
+
+    if let Some(ports) = child(element, "ports") {
+        for port in children(ports, "port") {
+            let protocol = attr(port, "protocol")
+                .ok_or_else(|| format_err!("Port is missing protocol"))?;
+            let portid = attr(port, "portid")
+                .ok_or_else(|| format_err!("Port is missing portid"))?
+                .parse::<u16>()?;
+
+            let service = parse_service(port);
+            // sn0int only tracks open and closed ports
+            if !matches!(service.status.as_deref(), Some("open") | Some("closed")) {
+                continue;
+            }
+            host.add_port(protocol, portid, service);
+        }
+    }
+
+    Ok(Some((addr, host)))
+}
+
+/// Parse the output of `nmap -oX`
+pub fn parse(x: &str) -> Result<Hosts> {
+    let doc = xml::decode_raw(x)?;
+    let nmaprun = doc.children.iter()
+        .find(|x| x.name == "nmaprun")
+        .ok_or_else(|| format_err!("Not an nmap xml report"))?;
+
+    let mut hosts = Hosts::new();
+    for element in children(nmaprun, "host") {
+        if let Some((addr, host)) = parse_host(element)? {
+            let existing = hosts.entry(addr).or_default();
+            existing.reverse_dns = existing.reverse_dns.take().or(host.reverse_dns);
+            for hostname in host.hostnames {
+                if !existing.hostnames.contains(&hostname) {
+                    existing.hostnames.push(hostname);
+                }
+            }
+            for ((protocol, port), service) in host.ports {
+                existing.add_port(protocol, port, service);
+            }
+        }
+    }
+
+    Ok(hosts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<nmaprun scanner="nmap" args="nmap -sV -oX - scanme.nmap.org" version="7.94">
+<host starttime="1700000000" endtime="1700000010"><status state="up" reason="syn-ack" reason_ttl="0"/>
+<address addr="45.33.32.156" addrtype="ipv4"/>
+<hostnames>
+<hostname name="scanme.nmap.org" type="user"/>
+<hostname name="scanme.nmap.org" type="PTR"/>
+</hostnames>
+<ports><extraports state="filtered" count="996"/>
+<port protocol="tcp" portid="22"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="ssh" product="OpenSSH" version="6.6.1p1 Ubuntu 2ubuntu2.13" extrainfo="Ubuntu Linux; protocol 2.0" ostype="Linux" method="probed" conf="10"/><script id="banner" output="SSH-2.0-OpenSSH_6.6.1p1 Ubuntu-2ubuntu2.13"/></port>
+<port protocol="tcp" portid="80"><state state="open" reason="syn-ack" reason_ttl="0"/><service name="http" product="Apache httpd" version="2.4.7" method="probed" conf="10"/></port>
+<port protocol="tcp" portid="9929"><state state="filtered" reason="no-response" reason_ttl="0"/><service name="nping-echo" method="table" conf="3"/></port>
+</ports>
+</host>
+<host><status state="down" reason="no-response" reason_ttl="0"/>
+<address addr="2001:db8::1" addrtype="ipv6"/>
+</host>
+</nmaprun>
+"#;
+
+    #[test]
+    fn test_parse_nmap() {
+        let hosts = parse(REPORT).unwrap();
+        assert_eq!(hosts.len(), 2);
+
+        let host = &hosts[&"45.33.32.156".parse::<net::IpAddr>().unwrap()];
+        assert_eq!(host.hostnames, vec!["scanme.nmap.org".to_string()]);
+        assert_eq!(host.reverse_dns, Some("scanme.nmap.org".to_string()));
+        assert_eq!(host.ports.len(), 2);
+        assert_eq!(host.ports[&("tcp".to_string(), 22)], Service {
+            status: Some("open".to_string()),
+            service: Some("ssh".to_string()),
+            version: Some("OpenSSH 6.6.1p1 Ubuntu 2ubuntu2.13 (Ubuntu Linux; protocol 2.0)".to_string()),
+            banner: Some("SSH-2.0-OpenSSH_6.6.1p1 Ubuntu-2ubuntu2.13".to_string()),
+        });
+        assert_eq!(host.ports[&("tcp".to_string(), 80)].version, Some("Apache httpd 2.4.7".to_string()));
+
+        let host = &hosts[&"2001:db8::1".parse::<net::IpAddr>().unwrap()];
+        assert!(host.ports.is_empty());
+    }
+
+    #[test]
+    fn test_parse_not_nmap() {
+        assert!(parse("<foo></foo>").is_err());
+    }
+}
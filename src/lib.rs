@@ -20,10 +20,13 @@ pub mod cmd;
 pub mod config;
 use sn0int_std::crt;
 pub mod db;
+pub mod diff;
 pub mod errors;
+pub mod export;
 pub mod engine;
 pub mod filters;
 pub mod fmt;
+pub mod import;
 use sn0int_std::geo;
 pub use sn0int_std::geoip;
 use sn0int_std::gfx;
@@ -134,12 +134,18 @@ fn run() -> Result<()> {
         Some(SubCommand::Autoscope(autoscope)) => run_cmd(&args, autoscope, &config),
         Some(SubCommand::Autonoscope(autonoscope)) => run_cmd(&args, autonoscope, &config),
         Some(SubCommand::Rescope(rescope)) => run_cmd(&args, rescope, &config),
+        Some(SubCommand::Workspace(workspace)) if workspace.requires_workspace() => run_cmd(&args, workspace, &config),
         Some(SubCommand::Workspace(workspace)) => workspace.run(&config),
         Some(SubCommand::Fsck(fsck)) => run_cmd(&args, fsck, &config),
         Some(SubCommand::Export(export)) => run_cmd(&args, export, &config),
+        Some(SubCommand::Import(import)) => run_cmd(&args, import, &config),
         Some(SubCommand::Cal(cal)) => run_cmd(&args, cal, &config),
         Some(SubCommand::Notify(notify)) => run_cmd(&args, notify, &config),
         Some(SubCommand::Stats(stats)) => run_cmd(&args, stats, &config),
+        Some(SubCommand::Tag(tag)) => run_cmd(&args, tag, &config),
+        Some(SubCommand::Note(note)) => run_cmd(&args, note, &config),
+        Some(SubCommand::History(history)) => run_cmd(&args, history, &config),
+        Some(SubCommand::Runs(runs)) => run_cmd(&args, runs, &config),
         Some(SubCommand::Repl) => repl::run(&config),
         Some(SubCommand::Paths) => paths::run(&config),
         Some(SubCommand::Completions(completions)) => completions.generate(),
@@ -2,6 +2,7 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
 use chrono::NaiveDateTime;
 
@@ -20,6 +21,8 @@ pub struct Account {
     pub phonenumber: Option<String>,
     pub profile_pic: Option<String>,
     pub birthday: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Account {
@@ -40,7 +43,7 @@ impl Model for Account {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::accounts::dsl::*;
 
-        let query = accounts.filter(filter.sql());
+        let query = accounts.filter(filter.sql(Table::Accounts)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -51,7 +54,7 @@ impl Model for Account {
 
         let query = accounts
             .filter(service.eq(param))
-            .filter(filter.sql());
+            .filter(filter.sql(Table::Accounts)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -60,7 +63,7 @@ impl Model for Account {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::accounts::dsl::*;
 
-        diesel::delete(accounts.filter(filter.sql()))
+        diesel::delete(accounts.filter(filter.sql(Table::Accounts)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -110,6 +113,18 @@ impl Model for Account {
     }
 }
 
+impl Sortable for Account {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::accounts::dsl::*;
+
+        let query = accounts.filter(filter.sql(Table::Accounts)?).into_boxed();
+        let results = order.apply(Table::Accounts, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Account {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -126,7 +141,7 @@ impl Scopable for Account {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::accounts::dsl::*;
 
-        diesel::update(accounts.filter(filter.sql()))
+        diesel::update(accounts.filter(filter.sql(Table::Accounts)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -135,7 +150,7 @@ impl Scopable for Account {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::accounts::dsl::*;
 
-        diesel::update(accounts.filter(filter.sql()))
+        diesel::update(accounts.filter(filter.sql(Table::Accounts)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -276,6 +291,26 @@ impl Upsertable<Account> for NewAccount {
     }
 }
 
+impl IntoNew for Account {
+    type Target = NewAccount;
+
+    fn into_new(self) -> NewAccount {
+        NewAccount {
+            value: self.value,
+            service: self.service,
+            username: self.username,
+            displayname: self.displayname,
+            email: self.email,
+            url: self.url,
+            last_seen: self.last_seen,
+            unscoped: self.unscoped,
+            birthday: self.birthday,
+            phonenumber: self.phonenumber,
+            profile_pic: self.profile_pic,
+        }
+    }
+}
+
 impl Printable<PrintableAccount> for NewAccount {
     fn printable(&self, _db: &Database) -> Result<PrintableAccount> {
         Ok(PrintableAccount {
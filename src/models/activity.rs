@@ -84,6 +84,14 @@ impl Activity {
             .map_err(Error::from)
     }
 
+    pub fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::activity::dsl::*;
+        activity
+            .order_by((time.asc(), id.asc()))
+            .load::<Self>(db.db())
+            .map_err(Error::from)
+    }
+
     pub fn count(db: &Database) -> Result<usize> {
         use crate::schema::activity::dsl::*;
         activity.count()
@@ -158,6 +166,20 @@ pub struct NewActivity {
 }
 
 impl NewActivity {
+    /// Check if an identical event has already been logged
+    pub fn exists(&self, db: &Database) -> Result<bool> {
+        use crate::schema::activity::dsl::*;
+        activity
+            .filter(topic.eq(&self.topic))
+            .filter(time.eq(&self.time))
+            .filter(content.eq(&self.content))
+            .select(id)
+            .first::<i32>(db.db())
+            .optional()
+            .map(|x| x.is_some())
+            .map_err(Error::from)
+    }
+
     pub fn insert(&self, db: &Database) -> Result<()> {
         diesel::insert_into(activity::table)
             .values(self)
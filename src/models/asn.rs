@@ -0,0 +1,397 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use crate::fmt::colors::*;
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="asns"]
+pub struct Asn {
+    pub id: i32,
+    pub value: String,
+    pub as_org: Option<String>,
+    pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for Asn {
+    type ID = str;
+
+    fn to_string(&self) -> String {
+        self.value.to_owned()
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::asns::dsl::*;
+
+        let results = asns.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::asns::dsl::*;
+
+        let query = asns.filter(filter.sql(Table::Asns)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::asns::dsl::*;
+
+        diesel::delete(asns.filter(filter.sql(Table::Asns)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::asns::dsl::*;
+
+        diesel::delete(asns.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn value(&self) -> &Self::ID {
+        &self.value
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::asns::dsl::*;
+
+        let asn = asns.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(asn)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::asns::dsl::*;
+
+        let asn = asns.filter(value.eq(query))
+            .first::<Self>(db.db())?;
+
+        Ok(asn)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::asns::dsl::*;
+
+        let asn = asns.filter(value.eq(query))
+            .first::<Self>(db.db())
+            .optional()?;
+
+        Ok(asn)
+    }
+}
+
+impl Sortable for Asn {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::asns::dsl::*;
+
+        let query = asns.filter(filter.sql(Table::Asns)?).into_boxed();
+        let results = order.apply(Table::Asns, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
+impl Scopable for Asn {
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    fn set_scoped(&self, db: &Database, my_value: bool) -> Result<()> {
+        use crate::schema::asns::dsl::*;
+        diesel::update(asns.filter(id.eq(self.id)))
+            .set(unscoped.eq(!my_value))
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    fn scope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::asns::dsl::*;
+
+        diesel::update(asns.filter(filter.sql(Table::Asns)?))
+            .set(unscoped.eq(false))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::asns::dsl::*;
+
+        diesel::update(asns.filter(filter.sql(Table::Asns)?))
+            .set(unscoped.eq(true))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+}
+
+impl Asn {
+    fn netblocks(&self, db: &Database) -> Result<Vec<Netblock>> {
+        let netblock_ids = AsnNetblock::belonging_to(self).select(asn_netblocks::netblock_id);
+
+        netblocks::table
+            .filter(netblocks::id.eq_any(netblock_ids))
+            .load::<Netblock>(db.db())
+            .map_err(Error::from)
+    }
+
+    fn ipaddrs(&self, db: &Database) -> Result<Vec<IpAddr>> {
+        let ipaddr_ids = AsnIpAddr::belonging_to(self).select(asn_ipaddrs::ip_addr_id);
+
+        ipaddrs::table
+            .filter(ipaddrs::id.eq_any(ipaddr_ids))
+            .load::<IpAddr>(db.db())
+            .map_err(Error::from)
+    }
+}
+
+/// Parse an autonomous system number, with or without the `AS` prefix
+pub fn parse_asn(value: &str) -> Result<u32> {
+    let value = value.trim();
+    let num = match value.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("as") => &value[2..],
+        _ => value,
+    };
+    num.parse::<u32>()
+        .map_err(|_| format_err!("Invalid autonomous system number: {:?}", value))
+}
+
+pub struct PrintableAsn {
+    value: String,
+    as_org: Option<String>,
+}
+
+impl fmt::Display for PrintableAsn {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{}", self.value)?;
+        if let Some(ref as_org) = self.as_org {
+            write!(w, " ({:?})", as_org)?;
+        }
+        Ok(())
+    }
+}
+
+impl Printable<PrintableAsn> for Asn {
+    fn printable(&self, _db: &Database) -> Result<PrintableAsn> {
+        Ok(PrintableAsn {
+            value: self.value.to_string(),
+            as_org: self.as_org.clone(),
+        })
+    }
+}
+
+pub struct DetailedAsn {
+    id: i32,
+    value: String,
+    unscoped: bool,
+    as_org: Option<String>,
+    netblocks: Vec<PrintableNetblock>,
+    ipaddrs: Vec<PrintableIpAddr>,
+}
+
+impl DisplayableDetailed for DetailedAsn {
+    #[inline]
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    #[inline]
+    fn print(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        w.id(self.id)?;
+        w.display::<Green, _>(&self.value)?;
+
+        w.start_group();
+        w.opt_debug::<Yellow, _>(&self.as_org)?;
+        w.end_group()?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn children(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        for netblock in &self.netblocks {
+            w.child(netblock)?;
+        }
+        for ipaddr in &self.ipaddrs {
+            w.child(ipaddr)?;
+        }
+        Ok(())
+    }
+}
+
+display_detailed!(DetailedAsn);
+
+impl Detailed for Asn {
+    type T = DetailedAsn;
+
+    fn detailed(&self, db: &Database) -> Result<Self::T> {
+        let netblocks = self.netblocks(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let ipaddrs = self.ipaddrs(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        Ok(DetailedAsn {
+            id: self.id,
+            value: self.value.to_string(),
+            unscoped: self.unscoped,
+            as_org: self.as_org.clone(),
+            netblocks,
+            ipaddrs,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[table_name="asns"]
+pub struct NewAsn {
+    pub value: String,
+    pub as_org: Option<String>,
+    pub unscoped: bool,
+}
+
+impl InsertableStruct<Asn> for NewAsn {
+    fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn set_scoped(&mut self, scoped: bool) {
+        self.unscoped = !scoped;
+    }
+
+    fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(asns::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
+impl Upsertable<Asn> for NewAsn {
+    type Update = AsnUpdate;
+
+    fn upsert(self, existing: &Asn) -> Self::Update {
+        Self::Update {
+            id: existing.id,
+            as_org: Self::upsert_opt(self.as_org, &existing.as_org),
+        }
+    }
+}
+
+impl IntoNew for Asn {
+    type Target = NewAsn;
+
+    fn into_new(self) -> NewAsn {
+        NewAsn {
+            value: self.value,
+            as_org: self.as_org,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
+impl Printable<PrintableAsn> for NewAsn {
+    fn printable(&self, _db: &Database) -> Result<PrintableAsn> {
+        Ok(PrintableAsn {
+            value: self.value.to_string(),
+            as_org: self.as_org.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InsertAsn {
+    pub asn: u32,
+    pub as_org: Option<String>,
+}
+
+impl InsertToNew for InsertAsn {
+    type Target = NewAsn;
+
+    fn try_into_new(self) -> Result<NewAsn> {
+        Ok(NewAsn {
+            value: format!("AS{}", self.asn),
+            as_org: self.as_org,
+            unscoped: false,
+        })
+    }
+}
+
+#[derive(Identifiable, AsChangeset, Serialize, Deserialize, Debug)]
+#[table_name="asns"]
+pub struct AsnUpdate {
+    pub id: i32,
+    pub as_org: Option<String>,
+}
+
+impl Upsert for AsnUpdate {
+    fn is_dirty(&self) -> bool {
+        self.as_org.is_some()
+    }
+
+    fn generic(self) -> Update {
+        Update::Asn(self)
+    }
+
+    fn apply(&self, db: &Database) -> Result<i32> {
+        db.update_asn(self)
+    }
+}
+
+impl Updateable<Asn> for AsnUpdate {
+    fn changeset(&mut self, existing: &Asn) {
+        Self::clear_if_equal(&mut self.as_org, &existing.as_org);
+    }
+
+    fn fmt(&self, updates: &mut Vec<String>, colors: bool) {
+        Self::push_value(updates, "as_org", &self.as_org, colors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_new() {
+        let asn = InsertAsn {
+            asn: 13335,
+            as_org: Some("CLOUDFLARENET".to_string()),
+        };
+        assert_eq!(asn.try_into_new().unwrap(), NewAsn {
+            value: "AS13335".to_string(),
+            as_org: Some("CLOUDFLARENET".to_string()),
+            unscoped: false,
+        });
+    }
+
+    #[test]
+    fn test_parse_asn() {
+        assert_eq!(parse_asn("AS13335").unwrap(), 13335);
+        assert_eq!(parse_asn("as13335").unwrap(), 13335);
+        assert_eq!(parse_asn(" 13335 ").unwrap(), 13335);
+    }
+
+    #[test]
+    fn test_parse_asn_invalid() {
+        assert!(parse_asn("").is_err());
+        assert!(parse_asn("AS").is_err());
+        assert!(parse_asn("ASX").is_err());
+        assert!(parse_asn("AS-1").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Asn)]
+#[belongs_to(IpAddr)]
+#[table_name="asn_ipaddrs"]
+pub struct AsnIpAddr {
+    pub id: i32,
+    pub asn_id: i32,
+    pub ip_addr_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for AsnIpAddr {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("AsnIpAddr can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        let results = asn_ipaddrs.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        let query = asn_ipaddrs.filter(filter.sql(Table::AsnIpaddrs)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        diesel::delete(asn_ipaddrs.filter(filter.sql(Table::AsnIpaddrs)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        diesel::delete(asn_ipaddrs.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        let asn_ipaddr = asn_ipaddrs.filter(id.eq(my_id))
+                                    .first::<Self>(db.db())?;
+
+        Ok(asn_ipaddr)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        let (my_asn_id, my_ip_addr_id) = query;
+        let asn_ipaddr = asn_ipaddrs.filter(asn_id.eq(my_asn_id))
+                                    .filter(ip_addr_id.eq(my_ip_addr_id))
+                                    .first::<Self>(db.db())?;
+
+        Ok(asn_ipaddr)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::asn_ipaddrs::dsl::*;
+
+        let (my_asn_id, my_ip_addr_id) = query;
+        let asn_ipaddr = asn_ipaddrs.filter(asn_id.eq(my_asn_id))
+                                    .filter(ip_addr_id.eq(my_ip_addr_id))
+                                    .first::<Self>(db.db())
+                                    .optional()?;
+
+        Ok(asn_ipaddr)
+    }
+}
+
+pub struct PrintableAsnIpAddr {
+    asn: String,
+    ipaddr: String,
+}
+
+impl fmt::Display for PrintableAsnIpAddr {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.asn, self.ipaddr)
+    }
+}
+
+impl Printable<PrintableAsnIpAddr> for AsnIpAddr {
+    fn printable(&self, db: &Database) -> Result<PrintableAsnIpAddr> {
+        let asn = Asn::by_id(db, self.asn_id)?;
+        let ipaddr = IpAddr::by_id(db, self.ip_addr_id)?;
+        Ok(PrintableAsnIpAddr {
+            asn: asn.value,
+            ipaddr: ipaddr.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="asn_ipaddrs"]
+pub struct NewAsnIpAddr {
+    pub asn_id: i32,
+    pub ip_addr_id: i32,
+}
+
+impl IntoNew for AsnIpAddr {
+    type Target = NewAsnIpAddr;
+
+    fn into_new(self) -> NewAsnIpAddr {
+        NewAsnIpAddr {
+            asn_id: self.asn_id,
+            ip_addr_id: self.ip_addr_id,
+        }
+    }
+}
+
+impl Printable<PrintableAsnIpAddr> for NewAsnIpAddr {
+    fn printable(&self, db: &Database) -> Result<PrintableAsnIpAddr> {
+        let asn = Asn::by_id(db, self.asn_id)?;
+        let ipaddr = IpAddr::by_id(db, self.ip_addr_id)?;
+        Ok(PrintableAsnIpAddr {
+            asn: asn.value,
+            ipaddr: ipaddr.value,
+        })
+    }
+}
+
+pub type InsertAsnIpAddr = NewAsnIpAddr;
+
+impl InsertToNew for InsertAsnIpAddr {
+    type Target = NewAsnIpAddr;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewAsnIpAddr> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Asn)]
+#[belongs_to(Netblock)]
+#[table_name="asn_netblocks"]
+pub struct AsnNetblock {
+    pub id: i32,
+    pub asn_id: i32,
+    pub netblock_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for AsnNetblock {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("AsnNetblock can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        let results = asn_netblocks.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        let query = asn_netblocks.filter(filter.sql(Table::AsnNetblocks)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        diesel::delete(asn_netblocks.filter(filter.sql(Table::AsnNetblocks)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        diesel::delete(asn_netblocks.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        let asn_netblock = asn_netblocks.filter(id.eq(my_id))
+                                        .first::<Self>(db.db())?;
+
+        Ok(asn_netblock)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        let (my_asn_id, my_netblock_id) = query;
+        let asn_netblock = asn_netblocks.filter(asn_id.eq(my_asn_id))
+                                        .filter(netblock_id.eq(my_netblock_id))
+                                        .first::<Self>(db.db())?;
+
+        Ok(asn_netblock)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::asn_netblocks::dsl::*;
+
+        let (my_asn_id, my_netblock_id) = query;
+        let asn_netblock = asn_netblocks.filter(asn_id.eq(my_asn_id))
+                                        .filter(netblock_id.eq(my_netblock_id))
+                                        .first::<Self>(db.db())
+                                        .optional()?;
+
+        Ok(asn_netblock)
+    }
+}
+
+pub struct PrintableAsnNetblock {
+    asn: String,
+    netblock: String,
+}
+
+impl fmt::Display for PrintableAsnNetblock {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.asn, self.netblock)
+    }
+}
+
+impl Printable<PrintableAsnNetblock> for AsnNetblock {
+    fn printable(&self, db: &Database) -> Result<PrintableAsnNetblock> {
+        let asn = Asn::by_id(db, self.asn_id)?;
+        let netblock = Netblock::by_id(db, self.netblock_id)?;
+        Ok(PrintableAsnNetblock {
+            asn: asn.value,
+            netblock: netblock.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="asn_netblocks"]
+pub struct NewAsnNetblock {
+    pub asn_id: i32,
+    pub netblock_id: i32,
+}
+
+impl IntoNew for AsnNetblock {
+    type Target = NewAsnNetblock;
+
+    fn into_new(self) -> NewAsnNetblock {
+        NewAsnNetblock {
+            asn_id: self.asn_id,
+            netblock_id: self.netblock_id,
+        }
+    }
+}
+
+impl Printable<PrintableAsnNetblock> for NewAsnNetblock {
+    fn printable(&self, db: &Database) -> Result<PrintableAsnNetblock> {
+        let asn = Asn::by_id(db, self.asn_id)?;
+        let netblock = Netblock::by_id(db, self.netblock_id)?;
+        Ok(PrintableAsnNetblock {
+            asn: asn.value,
+            netblock: netblock.value,
+        })
+    }
+}
+
+pub type InsertAsnNetblock = NewAsnNetblock;
+
+impl InsertToNew for InsertAsnNetblock {
+    type Target = NewAsnNetblock;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewAsnNetblock> {
+        Ok(self)
+    }
+}
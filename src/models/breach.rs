@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 
 #[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="breaches"]
@@ -10,6 +12,8 @@ pub struct Breach {
     pub id: i32,
     pub value: String,
     pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Breach {
@@ -30,7 +34,7 @@ impl Model for Breach {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::breaches::dsl::*;
 
-        let query = breaches.filter(filter.sql());
+        let query = breaches.filter(filter.sql(Table::Breaches)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -39,7 +43,7 @@ impl Model for Breach {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::breaches::dsl::*;
 
-        diesel::delete(breaches.filter(filter.sql()))
+        diesel::delete(breaches.filter(filter.sql(Table::Breaches)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -89,6 +93,18 @@ impl Model for Breach {
     }
 }
 
+impl Sortable for Breach {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::breaches::dsl::*;
+
+        let query = breaches.filter(filter.sql(Table::Breaches)?).into_boxed();
+        let results = order.apply(Table::Breaches, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Breach {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -105,7 +121,7 @@ impl Scopable for Breach {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::breaches::dsl::*;
 
-        diesel::update(breaches.filter(filter.sql()))
+        diesel::update(breaches.filter(filter.sql(Table::Breaches)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -114,7 +130,7 @@ impl Scopable for Breach {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::breaches::dsl::*;
 
-        diesel::update(breaches.filter(filter.sql()))
+        diesel::update(breaches.filter(filter.sql(Table::Breaches)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -259,6 +275,17 @@ impl Upsertable<Breach> for NewBreach {
     }
 }
 
+impl IntoNew for Breach {
+    type Target = NewBreach;
+
+    fn into_new(self) -> NewBreach {
+        NewBreach {
+            value: self.value,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableBreach> for NewBreach {
     fn printable(&self, _db: &Database) -> Result<PrintableBreach> {
         Ok(PrintableBreach {
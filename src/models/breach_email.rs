@@ -1,7 +1,9 @@
 use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 
 #[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
 #[belongs_to(Breach)]
@@ -12,6 +14,8 @@ pub struct BreachEmail {
     pub breach_id: i32,
     pub email_id: i32,
     pub password: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for BreachEmail {
@@ -32,7 +36,7 @@ impl Model for BreachEmail {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::breach_emails::dsl::*;
 
-        let query = breach_emails.filter(filter.sql());
+        let query = breach_emails.filter(filter.sql(Table::BreachEmails)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -41,7 +45,7 @@ impl Model for BreachEmail {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::breach_emails::dsl::*;
 
-        diesel::delete(breach_emails.filter(filter.sql()))
+        diesel::delete(breach_emails.filter(filter.sql(Table::BreachEmails)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -161,6 +165,18 @@ impl Upsertable<BreachEmail> for NewBreachEmail {
     }
 }
 
+impl IntoNew for BreachEmail {
+    type Target = NewBreachEmail;
+
+    fn into_new(self) -> NewBreachEmail {
+        NewBreachEmail {
+            breach_id: self.breach_id,
+            email_id: self.email_id,
+            password: self.password,
+        }
+    }
+}
+
 impl Printable<PrintableBreachEmail> for NewBreachEmail {
     fn printable(&self, db: &Database) -> Result<PrintableBreachEmail> {
         let breach = Breach::by_id(db, self.breach_id)?;
@@ -0,0 +1,498 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use crate::fmt::colors::*;
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+use std::result;
+
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="certificates"]
+pub struct Certificate {
+    pub id: i32,
+    pub value: String,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub sans: Option<String>,
+    pub serial: Option<String>,
+    pub key_type: Option<String>,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+    pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for Certificate {
+    type ID = str;
+
+    fn to_string(&self) -> String {
+        self.value.to_owned()
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::certificates::dsl::*;
+
+        let results = certificates.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::certificates::dsl::*;
+
+        let query = certificates.filter(filter.sql(Table::Certificates)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::certificates::dsl::*;
+
+        diesel::delete(certificates.filter(filter.sql(Table::Certificates)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::certificates::dsl::*;
+
+        diesel::delete(certificates.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn value(&self) -> &Self::ID {
+        &self.value
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::certificates::dsl::*;
+
+        let certificate = certificates.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(certificate)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::certificates::dsl::*;
+
+        let certificate = certificates.filter(value.eq(query))
+            .first::<Self>(db.db())?;
+
+        Ok(certificate)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::certificates::dsl::*;
+
+        let certificate = certificates.filter(value.eq(query))
+            .first::<Self>(db.db())
+            .optional()?;
+
+        Ok(certificate)
+    }
+}
+
+impl Sortable for Certificate {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::certificates::dsl::*;
+
+        let query = certificates.filter(filter.sql(Table::Certificates)?).into_boxed();
+        let results = order.apply(Table::Certificates, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
+impl Scopable for Certificate {
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    fn set_scoped(&self, db: &Database, my_value: bool) -> Result<()> {
+        use crate::schema::certificates::dsl::*;
+        diesel::update(certificates.filter(id.eq(self.id)))
+            .set(unscoped.eq(!my_value))
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    fn scope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::certificates::dsl::*;
+
+        diesel::update(certificates.filter(filter.sql(Table::Certificates)?))
+            .set(unscoped.eq(false))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::certificates::dsl::*;
+
+        diesel::update(certificates.filter(filter.sql(Table::Certificates)?))
+            .set(unscoped.eq(true))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+}
+
+impl Certificate {
+    fn ports(&self, db: &Database) -> Result<Vec<Port>> {
+        let port_ids = PortCertificate::belonging_to(self)
+            .select(port_certificates::port_id)
+            .load::<i32>(db.db())?;
+
+        port_ids.into_iter()
+            .map(|port_id| ports::table
+                .filter(ports::id.eq(port_id))
+                .first::<Port>(db.db())
+            )
+            .collect::<result::Result<_, _>>()
+            .map_err(Error::from)
+    }
+
+    fn subdomains(&self, db: &Database) -> Result<Vec<Subdomain>> {
+        let subdomain_ids = SubdomainCertificate::belonging_to(self)
+            .select(subdomain_certificates::subdomain_id)
+            .load::<i32>(db.db())?;
+
+        subdomain_ids.into_iter()
+            .map(|subdomain_id| subdomains::table
+                .filter(subdomains::id.eq(subdomain_id))
+                .first::<Subdomain>(db.db())
+            )
+            .collect::<result::Result<_, _>>()
+            .map_err(Error::from)
+    }
+}
+
+pub struct PrintableCertificate {
+    value: String,
+}
+
+impl fmt::Display for PrintableCertificate {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?}", self.value)
+    }
+}
+
+impl Printable<PrintableCertificate> for Certificate {
+    fn printable(&self, _db: &Database) -> Result<PrintableCertificate> {
+        Ok(PrintableCertificate {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+pub struct DetailedCertificate {
+    id: i32,
+    value: String,
+    ports: Vec<PrintablePort>,
+    subdomains: Vec<PrintableSubdomain>,
+    unscoped: bool,
+    subject: Option<String>,
+    issuer: Option<String>,
+    key_type: Option<String>,
+    not_before: Option<NaiveDateTime>,
+    not_after: Option<NaiveDateTime>,
+}
+
+impl DisplayableDetailed for DetailedCertificate {
+    #[inline]
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    #[inline]
+    fn print(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        w.id(self.id)?;
+        w.debug::<Green, _>(&self.value)?;
+
+        w.start_group();
+        w.opt_debug_label::<Yellow, _>("subject", &self.subject)?;
+        w.opt_debug_label::<Yellow, _>("issuer", &self.issuer)?;
+        w.opt_debug_label::<Yellow, _>("key_type", &self.key_type)?;
+        w.opt_debug_label::<Yellow, _>("not_before", &self.not_before)?;
+        w.opt_debug_label::<Yellow, _>("not_after", &self.not_after)?;
+        w.end_group()?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn children(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        for port in &self.ports {
+            w.child(port)?;
+        }
+        for subdomain in &self.subdomains {
+            w.child(subdomain)?;
+        }
+        Ok(())
+    }
+}
+
+display_detailed!(DetailedCertificate);
+
+impl Detailed for Certificate {
+    type T = DetailedCertificate;
+
+    fn detailed(&self, db: &Database) -> Result<Self::T> {
+        let ports = self.ports(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let subdomains = self.subdomains(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        Ok(DetailedCertificate {
+            id: self.id,
+            value: self.value.to_string(),
+            ports,
+            subdomains,
+            unscoped: self.unscoped,
+            subject: self.subject.clone(),
+            issuer: self.issuer.clone(),
+            key_type: self.key_type.clone(),
+            not_before: self.not_before,
+            not_after: self.not_after,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="certificates"]
+pub struct NewCertificate {
+    pub value: String,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub sans: Option<String>,
+    pub serial: Option<String>,
+    pub key_type: Option<String>,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+    pub unscoped: bool,
+}
+
+impl NewCertificate {
+    /// The dns names the certificate is valid for
+    pub fn names(&self) -> impl Iterator<Item=&str> {
+        self.sans.iter()
+            .flat_map(|sans| sans.split_whitespace())
+    }
+}
+
+impl InsertableStruct<Certificate> for NewCertificate {
+    fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn set_scoped(&mut self, scoped: bool) {
+        self.unscoped = !scoped;
+    }
+
+    fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(certificates::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
+impl Upsertable<Certificate> for NewCertificate {
+    type Update = CertificateUpdate;
+
+    fn upsert(self, existing: &Certificate) -> Self::Update {
+        Self::Update {
+            id: existing.id,
+            subject: Self::upsert_opt(self.subject, &existing.subject),
+            issuer: Self::upsert_opt(self.issuer, &existing.issuer),
+            sans: Self::upsert_opt(self.sans, &existing.sans),
+            serial: Self::upsert_opt(self.serial, &existing.serial),
+            key_type: Self::upsert_opt(self.key_type, &existing.key_type),
+            not_before: Self::upsert_opt(self.not_before, &existing.not_before),
+            not_after: Self::upsert_opt(self.not_after, &existing.not_after),
+        }
+    }
+}
+
+impl IntoNew for Certificate {
+    type Target = NewCertificate;
+
+    fn into_new(self) -> NewCertificate {
+        NewCertificate {
+            value: self.value,
+            subject: self.subject,
+            issuer: self.issuer,
+            sans: self.sans,
+            serial: self.serial,
+            key_type: self.key_type,
+            not_before: self.not_before,
+            not_after: self.not_after,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
+impl Printable<PrintableCertificate> for NewCertificate {
+    fn printable(&self, _db: &Database) -> Result<PrintableCertificate> {
+        Ok(PrintableCertificate {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+/// This is compatible with the output of `x509_parse_pem`, so the result can
+/// be passed to `db_add` directly
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InsertCertificate {
+    pub fingerprint: String,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub serial: Option<String>,
+    pub key_type: Option<String>,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub valid_names: Vec<String>,
+}
+
+impl InsertToNew for InsertCertificate {
+    type Target = NewCertificate;
+
+    fn try_into_new(self) -> Result<NewCertificate> {
+        let value = self.fingerprint.replace(':', "").to_lowercase();
+        if value.is_empty() || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Certificate fingerprint must be hex encoded: {:?}", self.fingerprint);
+        }
+
+        // sorted, so the same certificate doesn't trigger an update every time
+        let mut names = self.valid_names.into_iter()
+            .map(|name| name.to_lowercase())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+
+        let sans = if names.is_empty() {
+            None
+        } else {
+            Some(names.join(" "))
+        };
+
+        Ok(NewCertificate {
+            value,
+            subject: self.subject,
+            issuer: self.issuer,
+            sans,
+            serial: self.serial,
+            key_type: self.key_type,
+            not_before: self.not_before,
+            not_after: self.not_after,
+            unscoped: false,
+        })
+    }
+}
+
+#[derive(Identifiable, AsChangeset, Serialize, Deserialize, Debug)]
+#[table_name="certificates"]
+pub struct CertificateUpdate {
+    pub id: i32,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub sans: Option<String>,
+    pub serial: Option<String>,
+    pub key_type: Option<String>,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+}
+
+impl Upsert for CertificateUpdate {
+    fn is_dirty(&self) -> bool {
+        self.subject.is_some() ||
+        self.issuer.is_some() ||
+        self.sans.is_some() ||
+        self.serial.is_some() ||
+        self.key_type.is_some() ||
+        self.not_before.is_some() ||
+        self.not_after.is_some()
+    }
+
+    fn generic(self) -> Update {
+        Update::Certificate(self)
+    }
+
+    fn apply(&self, db: &Database) -> Result<i32> {
+        db.update_certificate(self)
+    }
+}
+
+impl Updateable<Certificate> for CertificateUpdate {
+    fn changeset(&mut self, existing: &Certificate) {
+        Self::clear_if_equal(&mut self.subject, &existing.subject);
+        Self::clear_if_equal(&mut self.issuer, &existing.issuer);
+        Self::clear_if_equal(&mut self.sans, &existing.sans);
+        Self::clear_if_equal(&mut self.serial, &existing.serial);
+        Self::clear_if_equal(&mut self.key_type, &existing.key_type);
+        Self::clear_if_equal(&mut self.not_before, &existing.not_before);
+        Self::clear_if_equal(&mut self.not_after, &existing.not_after);
+    }
+
+    fn fmt(&self, updates: &mut Vec<String>, colors: bool) {
+        Self::push_value(updates, "subject", &self.subject, colors);
+        Self::push_value(updates, "issuer", &self.issuer, colors);
+        Self::push_value(updates, "sans", &self.sans, colors);
+        Self::push_value(updates, "serial", &self.serial, colors);
+        Self::push_value(updates, "key_type", &self.key_type, colors);
+        Self::push_value(updates, "not_before", &self.not_before, colors);
+        Self::push_value(updates, "not_after", &self.not_after, colors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert(fingerprint: &str, valid_names: &[&str]) -> InsertCertificate {
+        InsertCertificate {
+            fingerprint: fingerprint.to_string(),
+            subject: None,
+            issuer: None,
+            serial: None,
+            key_type: None,
+            not_before: None,
+            not_after: None,
+            valid_names: valid_names.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_insert_certificate_normalize() {
+        let x = insert("31:11:50:0C", &["www.github.com", "GitHub.com", "github.com"])
+            .try_into_new().unwrap();
+        assert_eq!(x.value, "3111500c");
+        assert_eq!(x.sans.as_deref(), Some("github.com www.github.com"));
+        assert_eq!(x.names().collect::<Vec<_>>(), vec!["github.com", "www.github.com"]);
+    }
+
+    #[test]
+    fn test_insert_certificate_no_names() {
+        let x = insert("3111500c", &[]).try_into_new().unwrap();
+        assert_eq!(x.sans, None);
+        assert_eq!(x.names().count(), 0);
+    }
+
+    #[test]
+    fn test_insert_certificate_invalid_fingerprint() {
+        assert!(insert("github.com", &[]).try_into_new().is_err());
+        assert!(insert("", &[]).try_into_new().is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
 use chrono::NaiveDateTime;
 
@@ -18,6 +19,8 @@ pub struct CryptoAddr {
     pub last_withdrawal: Option<NaiveDateTime>,
     pub unscoped: bool,
     pub description: Option<String>,
+    pub last_observed: Option<NaiveDateTime>,
+    pub first_observed: Option<NaiveDateTime>,
 }
 
 impl Model for CryptoAddr {
@@ -38,7 +41,7 @@ impl Model for CryptoAddr {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::cryptoaddrs::dsl::*;
 
-        let query = cryptoaddrs.filter(filter.sql());
+        let query = cryptoaddrs.filter(filter.sql(Table::Cryptoaddrs)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -49,7 +52,7 @@ impl Model for CryptoAddr {
 
         let query = cryptoaddrs
             .filter(currency.eq(param))
-            .filter(filter.sql());
+            .filter(filter.sql(Table::Cryptoaddrs)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -58,7 +61,7 @@ impl Model for CryptoAddr {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::cryptoaddrs::dsl::*;
 
-        diesel::delete(cryptoaddrs.filter(filter.sql()))
+        diesel::delete(cryptoaddrs.filter(filter.sql(Table::Cryptoaddrs)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -108,6 +111,18 @@ impl Model for CryptoAddr {
     }
 }
 
+impl Sortable for CryptoAddr {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::cryptoaddrs::dsl::*;
+
+        let query = cryptoaddrs.filter(filter.sql(Table::Cryptoaddrs)?).into_boxed();
+        let results = order.apply(Table::Cryptoaddrs, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for CryptoAddr {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -124,7 +139,7 @@ impl Scopable for CryptoAddr {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::cryptoaddrs::dsl::*;
 
-        diesel::update(cryptoaddrs.filter(filter.sql()))
+        diesel::update(cryptoaddrs.filter(filter.sql(Table::Cryptoaddrs)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -133,7 +148,7 @@ impl Scopable for CryptoAddr {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::cryptoaddrs::dsl::*;
 
-        diesel::update(cryptoaddrs.filter(filter.sql()))
+        diesel::update(cryptoaddrs.filter(filter.sql(Table::Cryptoaddrs)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -286,6 +301,24 @@ impl Upsertable<CryptoAddr> for NewCryptoAddr {
     }
 }
 
+impl IntoNew for CryptoAddr {
+    type Target = NewCryptoAddr;
+
+    fn into_new(self) -> NewCryptoAddr {
+        NewCryptoAddr {
+            value: self.value,
+            currency: self.currency,
+            denominator: self.denominator,
+            balance: self.balance,
+            received: self.received,
+            first_seen: self.first_seen,
+            last_withdrawal: self.last_withdrawal,
+            unscoped: self.unscoped,
+            description: self.description,
+        }
+    }
+}
+
 impl Printable<PrintableCryptoAddr> for NewCryptoAddr {
     fn printable(&self, _db: &Database) -> Result<PrintableCryptoAddr> {
         Ok(PrintableCryptoAddr {
@@ -2,6 +2,7 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
 use chrono::NaiveDateTime;
 
@@ -15,6 +16,8 @@ pub struct Device {
     pub vendor: Option<String>,
     pub unscoped: bool,
     pub last_seen: Option<NaiveDateTime>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Device {
@@ -35,7 +38,7 @@ impl Model for Device {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::devices::dsl::*;
 
-        let query = devices.filter(filter.sql());
+        let query = devices.filter(filter.sql(Table::Devices)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -44,7 +47,7 @@ impl Model for Device {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::devices::dsl::*;
 
-        diesel::delete(devices.filter(filter.sql()))
+        diesel::delete(devices.filter(filter.sql(Table::Devices)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -94,6 +97,18 @@ impl Model for Device {
     }
 }
 
+impl Sortable for Device {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::devices::dsl::*;
+
+        let query = devices.filter(filter.sql(Table::Devices)?).into_boxed();
+        let results = order.apply(Table::Devices, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Device {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -110,7 +125,7 @@ impl Scopable for Device {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::devices::dsl::*;
 
-        diesel::update(devices.filter(filter.sql()))
+        diesel::update(devices.filter(filter.sql(Table::Devices)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -119,7 +134,7 @@ impl Scopable for Device {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::devices::dsl::*;
 
-        diesel::update(devices.filter(filter.sql()))
+        diesel::update(devices.filter(filter.sql(Table::Devices)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -270,6 +285,21 @@ impl Upsertable<Device> for NewDevice {
     }
 }
 
+impl IntoNew for Device {
+    type Target = NewDevice;
+
+    fn into_new(self) -> NewDevice {
+        NewDevice {
+            value: self.value,
+            name: self.name,
+            hostname: self.hostname,
+            vendor: self.vendor,
+            last_seen: self.last_seen,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableDevice> for NewDevice {
     fn printable(&self, _db: &Database) -> Result<PrintableDevice> {
         Ok(PrintableDevice {
@@ -0,0 +1,420 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use crate::fmt::colors::*;
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize, PartialEq, Debug)]
+#[belongs_to(Subdomain)]
+#[table_name="dns_records"]
+pub struct DnsRecord {
+    pub id: i32,
+    pub subdomain_id: i32,
+    pub value: String,
+    pub name: String,
+    pub record_type: String,
+    pub rdata: String,
+    pub ttl: Option<i32>,
+    pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for DnsRecord {
+    type ID = str;
+
+    fn to_string(&self) -> String {
+        self.value.to_owned()
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::dns_records::dsl::*;
+
+        let results = dns_records.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::dns_records::dsl::*;
+
+        let query = dns_records.filter(filter.sql(Table::DnsRecords)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::dns_records::dsl::*;
+
+        diesel::delete(dns_records.filter(filter.sql(Table::DnsRecords)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::dns_records::dsl::*;
+
+        diesel::delete(dns_records.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn value(&self) -> &Self::ID {
+        &self.value
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::dns_records::dsl::*;
+
+        let dns_record = dns_records.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(dns_record)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::dns_records::dsl::*;
+
+        let dns_record = dns_records.filter(value.eq(query))
+            .first::<Self>(db.db())?;
+
+        Ok(dns_record)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::dns_records::dsl::*;
+
+        let dns_record = dns_records.filter(value.eq(query))
+            .first::<Self>(db.db())
+            .optional()?;
+
+        Ok(dns_record)
+    }
+}
+
+impl Sortable for DnsRecord {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::dns_records::dsl::*;
+
+        let query = dns_records.filter(filter.sql(Table::DnsRecords)?).into_boxed();
+        let results = order.apply(Table::DnsRecords, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
+impl Scopable for DnsRecord {
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    fn set_scoped(&self, db: &Database, my_value: bool) -> Result<()> {
+        use crate::schema::dns_records::dsl::*;
+        diesel::update(dns_records.filter(id.eq(self.id)))
+            .set(unscoped.eq(!my_value))
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    fn scope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::dns_records::dsl::*;
+
+        diesel::update(dns_records.filter(filter.sql(Table::DnsRecords)?))
+            .set(unscoped.eq(false))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::dns_records::dsl::*;
+
+        diesel::update(dns_records.filter(filter.sql(Table::DnsRecords)?))
+            .set(unscoped.eq(true))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+}
+
+pub struct PrintableDnsRecord {
+    value: String,
+}
+
+impl fmt::Display for PrintableDnsRecord {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?}", self.value)
+    }
+}
+
+impl Printable<PrintableDnsRecord> for DnsRecord {
+    fn printable(&self, _db: &Database) -> Result<PrintableDnsRecord> {
+        Ok(PrintableDnsRecord {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+pub struct DetailedDnsRecord {
+    id: i32,
+    value: String,
+    unscoped: bool,
+    ttl: Option<i32>,
+}
+
+impl DisplayableDetailed for DetailedDnsRecord {
+    #[inline]
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    #[inline]
+    fn print(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        w.id(self.id)?;
+        w.debug::<Green, _>(&self.value)?;
+
+        w.start_group();
+        w.opt_debug_label::<Yellow, _>("ttl", &self.ttl)?;
+        w.end_group()?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn children(&self, _w: &mut fmt::DetailFormatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+display_detailed!(DetailedDnsRecord);
+
+impl Detailed for DnsRecord {
+    type T = DetailedDnsRecord;
+
+    fn detailed(&self, _db: &Database) -> Result<Self::T> {
+        Ok(DetailedDnsRecord {
+            id: self.id,
+            value: self.value.to_string(),
+            unscoped: self.unscoped,
+            ttl: self.ttl,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[table_name="dns_records"]
+pub struct NewDnsRecord {
+    pub subdomain_id: i32,
+    pub value: String,
+    pub name: String,
+    pub record_type: String,
+    pub rdata: String,
+    pub ttl: Option<i32>,
+    pub unscoped: bool,
+}
+
+impl InsertableStruct<DnsRecord> for NewDnsRecord {
+    fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn set_scoped(&mut self, scoped: bool) {
+        self.unscoped = !scoped;
+    }
+
+    fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(dns_records::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
+impl Upsertable<DnsRecord> for NewDnsRecord {
+    type Update = DnsRecordUpdate;
+
+    fn upsert(self, existing: &DnsRecord) -> Self::Update {
+        Self::Update {
+            id: existing.id,
+            ttl: Self::upsert_opt(self.ttl, &existing.ttl),
+        }
+    }
+}
+
+impl IntoNew for DnsRecord {
+    type Target = NewDnsRecord;
+
+    fn into_new(self) -> NewDnsRecord {
+        NewDnsRecord {
+            subdomain_id: self.subdomain_id,
+            value: self.value,
+            name: self.name,
+            record_type: self.record_type,
+            rdata: self.rdata,
+            ttl: self.ttl,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
+impl Printable<PrintableDnsRecord> for NewDnsRecord {
+    fn printable(&self, _db: &Database) -> Result<PrintableDnsRecord> {
+        Ok(PrintableDnsRecord {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InsertDnsRecord {
+    pub subdomain_id: i32,
+    pub name: String,
+    pub record_type: String,
+    pub rdata: String,
+    pub ttl: Option<i32>,
+}
+
+impl InsertToNew for InsertDnsRecord {
+    type Target = NewDnsRecord;
+
+    fn try_into_new(self) -> Result<NewDnsRecord> {
+        let name = self.name.trim_end_matches('.').to_lowercase();
+        if name.is_empty() {
+            bail!("Name of dns record can't be empty");
+        }
+
+        let record_type = self.record_type.to_uppercase();
+        if record_type.is_empty() || !record_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+            bail!("Invalid dns record type: {:?}", self.record_type);
+        }
+
+        let rdata = self.rdata.trim().to_string();
+        let value = format!("{} {} {}", name, record_type, rdata);
+
+        Ok(NewDnsRecord {
+            subdomain_id: self.subdomain_id,
+            value,
+            name,
+            record_type,
+            rdata,
+            ttl: self.ttl,
+            unscoped: false,
+        })
+    }
+}
+
+#[derive(Identifiable, AsChangeset, Serialize, Deserialize, Debug)]
+#[table_name="dns_records"]
+pub struct DnsRecordUpdate {
+    pub id: i32,
+    pub ttl: Option<i32>,
+}
+
+impl Upsert for DnsRecordUpdate {
+    fn is_dirty(&self) -> bool {
+        self.ttl.is_some()
+    }
+
+    fn generic(self) -> Update {
+        Update::DnsRecord(self)
+    }
+
+    fn apply(&self, db: &Database) -> Result<i32> {
+        db.update_dns_record(self)
+    }
+}
+
+impl Updateable<DnsRecord> for DnsRecordUpdate {
+    fn changeset(&mut self, existing: &DnsRecord) {
+        Self::clear_if_equal(&mut self.ttl, &existing.ttl);
+    }
+
+    fn fmt(&self, updates: &mut Vec<String>, colors: bool) {
+        Self::push_value(updates, "ttl", &self.ttl, colors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_new() {
+        let record = InsertDnsRecord {
+            subdomain_id: 1234,
+            name: "Example.com.".to_string(),
+            record_type: "mx".to_string(),
+            rdata: "10 mx.example.com.".to_string(),
+            ttl: Some(3600),
+        };
+        assert_eq!(record.try_into_new().unwrap(), NewDnsRecord {
+            subdomain_id: 1234,
+            value: "example.com MX 10 mx.example.com.".to_string(),
+            name: "example.com".to_string(),
+            record_type: "MX".to_string(),
+            rdata: "10 mx.example.com.".to_string(),
+            ttl: Some(3600),
+            unscoped: false,
+        });
+    }
+
+    #[test]
+    fn test_to_new_txt_keeps_case() {
+        let record = InsertDnsRecord {
+            subdomain_id: 1234,
+            name: "_dmarc.example.com".to_string(),
+            record_type: "TXT".to_string(),
+            rdata: "v=DMARC1; p=reject; rua=mailto:DMARC@example.com".to_string(),
+            ttl: None,
+        };
+        let record = record.try_into_new().unwrap();
+        assert_eq!(record.value, "_dmarc.example.com TXT v=DMARC1; p=reject; rua=mailto:DMARC@example.com");
+    }
+
+    #[test]
+    fn test_to_new_invalid_type() {
+        let record = InsertDnsRecord {
+            subdomain_id: 1234,
+            name: "example.com".to_string(),
+            record_type: "M X".to_string(),
+            rdata: "10 mx.example.com.".to_string(),
+            ttl: None,
+        };
+        assert!(record.try_into_new().is_err());
+    }
+
+    #[test]
+    fn test_insert_name_matches_subdomain() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let (_, domain_id) = db.insert_generic(Insert::Domain(NewDomain {
+            value: String::from("example.com"),
+            unscoped: false,
+        })).unwrap().unwrap();
+        let (_, subdomain_id) = db.insert_generic(Insert::Subdomain(NewSubdomain {
+            domain_id,
+            value: String::from("_dmarc.example.com"),
+            resolvable: None,
+            unscoped: false,
+        })).unwrap().unwrap();
+
+        let record = |name: &str| InsertDnsRecord {
+            subdomain_id,
+            name: name.to_string(),
+            record_type: "TXT".to_string(),
+            rdata: "v=DMARC1; p=reject".to_string(),
+            ttl: None,
+        }.try_into_new().unwrap();
+
+        assert!(db.insert_generic(Insert::DnsRecord(record("_DMARC.example.com."))).unwrap().is_some());
+        assert!(db.insert_generic(Insert::DnsRecord(record("example.com"))).is_err());
+        assert!(db.insert_generic(Insert::DnsRecord(record("_dmarc.example.org"))).is_err());
+    }
+}
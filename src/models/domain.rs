@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 
 #[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="domains"]
@@ -10,6 +12,8 @@ pub struct Domain {
     pub id: i32,
     pub value: String,
     pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Domain {
@@ -30,7 +34,7 @@ impl Model for Domain {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::domains::dsl::*;
 
-        let query = domains.filter(filter.sql());
+        let query = domains.filter(filter.sql(Table::Domains)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -39,7 +43,7 @@ impl Model for Domain {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::domains::dsl::*;
 
-        diesel::delete(domains.filter(filter.sql()))
+        diesel::delete(domains.filter(filter.sql(Table::Domains)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -89,6 +93,18 @@ impl Model for Domain {
     }
 }
 
+impl Sortable for Domain {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::domains::dsl::*;
+
+        let query = domains.filter(filter.sql(Table::Domains)?).into_boxed();
+        let results = order.apply(Table::Domains, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Domain {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -105,7 +121,7 @@ impl Scopable for Domain {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::domains::dsl::*;
 
-        diesel::update(domains.filter(filter.sql()))
+        diesel::update(domains.filter(filter.sql(Table::Domains)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -114,7 +130,7 @@ impl Scopable for Domain {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::domains::dsl::*;
 
-        diesel::update(domains.filter(filter.sql()))
+        diesel::update(domains.filter(filter.sql(Table::Domains)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -129,6 +145,24 @@ impl Domain {
     }
 }
 
+pub struct PrintableDomain {
+    value: String,
+}
+
+impl fmt::Display for PrintableDomain {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?}", self.value)
+    }
+}
+
+impl Printable<PrintableDomain> for Domain {
+    fn printable(&self, _db: &Database) -> Result<PrintableDomain> {
+        Ok(PrintableDomain {
+            value: self.value.to_string(),
+        })
+    }
+}
+
 pub struct DetailedDomain {
     id: i32,
     value: String,
@@ -211,6 +245,17 @@ impl Upsertable<Domain> for NewDomain {
     }
 }
 
+impl IntoNew for Domain {
+    type Target = NewDomain;
+
+    fn into_new(self) -> NewDomain {
+        NewDomain {
+            value: self.value,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InsertDomain {
     pub value: String,
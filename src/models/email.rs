@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 
 #[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="emails"]
@@ -12,6 +14,8 @@ pub struct Email {
     pub unscoped: bool,
     pub valid: Option<bool>,
     pub displayname: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Email {
@@ -32,7 +36,7 @@ impl Model for Email {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::emails::dsl::*;
 
-        let query = emails.filter(filter.sql());
+        let query = emails.filter(filter.sql(Table::Emails)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -41,7 +45,7 @@ impl Model for Email {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::emails::dsl::*;
 
-        diesel::delete(emails.filter(filter.sql()))
+        diesel::delete(emails.filter(filter.sql(Table::Emails)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -91,6 +95,18 @@ impl Model for Email {
     }
 }
 
+impl Sortable for Email {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::emails::dsl::*;
+
+        let query = emails.filter(filter.sql(Table::Emails)?).into_boxed();
+        let results = order.apply(Table::Emails, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Email {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -107,7 +123,7 @@ impl Scopable for Email {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::emails::dsl::*;
 
-        diesel::update(emails.filter(filter.sql()))
+        diesel::update(emails.filter(filter.sql(Table::Emails)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -116,7 +132,7 @@ impl Scopable for Email {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::emails::dsl::*;
 
-        diesel::update(emails.filter(filter.sql()))
+        diesel::update(emails.filter(filter.sql(Table::Emails)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -283,6 +299,19 @@ impl Upsertable<Email> for NewEmail {
     }
 }
 
+impl IntoNew for Email {
+    type Target = NewEmail;
+
+    fn into_new(self) -> NewEmail {
+        NewEmail {
+            value: self.value,
+            displayname: self.displayname,
+            valid: self.valid,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableEmail> for NewEmail {
     fn printable(&self, _db: &Database) -> Result<PrintableEmail> {
         Ok(PrintableEmail {
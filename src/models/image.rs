@@ -3,6 +3,7 @@ use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
 use std::sync::Arc;
 use crate::engine::ctx::State;
@@ -28,6 +29,8 @@ pub struct Image {
     pub phash: Option<String>,
 
     pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Image {
@@ -48,7 +51,7 @@ impl Model for Image {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::images::dsl::*;
 
-        let query = images.filter(filter.sql());
+        let query = images.filter(filter.sql(Table::Images)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -57,7 +60,7 @@ impl Model for Image {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::images::dsl::*;
 
-        diesel::delete(images.filter(filter.sql()))
+        diesel::delete(images.filter(filter.sql(Table::Images)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -111,6 +114,18 @@ impl Model for Image {
     }
 }
 
+impl Sortable for Image {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::images::dsl::*;
+
+        let query = images.filter(filter.sql(Table::Images)?).into_boxed();
+        let results = order.apply(Table::Images, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Image {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -127,7 +142,7 @@ impl Scopable for Image {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::images::dsl::*;
 
-        diesel::update(images.filter(filter.sql()))
+        diesel::update(images.filter(filter.sql(Table::Images)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -136,7 +151,7 @@ impl Scopable for Image {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::images::dsl::*;
 
-        diesel::update(images.filter(filter.sql()))
+        diesel::update(images.filter(filter.sql(Table::Images)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -343,6 +358,28 @@ impl Upsertable<Image> for NewImage {
     }
 }
 
+impl IntoNew for Image {
+    type Target = NewImage;
+
+    fn into_new(self) -> NewImage {
+        NewImage {
+            value: self.value,
+            filename: self.filename,
+            mime: self.mime,
+            width: self.width,
+            height: self.height,
+            created: self.created,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            nudity: self.nudity,
+            ahash: self.ahash,
+            dhash: self.dhash,
+            phash: self.phash,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableImage> for NewImage {
     fn printable(&self, _db: &Database) -> Result<PrintableImage> {
         Ok(PrintableImage {
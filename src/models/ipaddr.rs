@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::Write;
 use crate::fmt::colors::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use std::net;
 use std::result;
@@ -25,6 +27,8 @@ pub struct IpAddr {
     pub as_org: Option<String>,
     pub description: Option<String>,
     pub reverse_dns: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for IpAddr {
@@ -45,7 +49,7 @@ impl Model for IpAddr {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::ipaddrs::dsl::*;
 
-        let query = ipaddrs.filter(filter.sql());
+        let query = ipaddrs.filter(filter.sql(Table::Ipaddrs)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -54,7 +58,7 @@ impl Model for IpAddr {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::ipaddrs::dsl::*;
 
-        diesel::delete(ipaddrs.filter(filter.sql()))
+        diesel::delete(ipaddrs.filter(filter.sql(Table::Ipaddrs)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -104,6 +108,18 @@ impl Model for IpAddr {
     }
 }
 
+impl Sortable for IpAddr {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::ipaddrs::dsl::*;
+
+        let query = ipaddrs.filter(filter.sql(Table::Ipaddrs)?).into_boxed();
+        let results = order.apply(Table::Ipaddrs, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for IpAddr {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -120,7 +136,7 @@ impl Scopable for IpAddr {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::ipaddrs::dsl::*;
 
-        diesel::update(ipaddrs.filter(filter.sql()))
+        diesel::update(ipaddrs.filter(filter.sql(Table::Ipaddrs)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -129,7 +145,7 @@ impl Scopable for IpAddr {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::ipaddrs::dsl::*;
 
-        diesel::update(ipaddrs.filter(filter.sql()))
+        diesel::update(ipaddrs.filter(filter.sql(Table::Ipaddrs)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -335,6 +351,29 @@ impl Upsertable<IpAddr> for NewIpAddr {
     }
 }
 
+impl IntoNew for IpAddr {
+    type Target = NewIpAddr;
+
+    fn into_new(self) -> NewIpAddr {
+        NewIpAddr {
+            family: self.family,
+            value: self.value,
+            continent: self.continent,
+            continent_code: self.continent_code,
+            country: self.country,
+            country_code: self.country_code,
+            city: self.city,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            asn: self.asn,
+            as_org: self.as_org,
+            description: self.description,
+            reverse_dns: self.reverse_dns,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableIpAddr> for NewIpAddr {
     fn printable(&self, _db: &Database) -> Result<PrintableIpAddr> {
         Ok(PrintableIpAddr {
@@ -1,4 +1,4 @@
-use crate::db::{Database, Table, Filter, Family};
+use crate::db::{Database, Table, Filter, Family, Order};
 use serde::{Serialize, Deserialize};
 use crate::engine::ctx::State;
 use crate::errors::*;
@@ -27,6 +27,21 @@ pub enum Insert {
     Port(NewPort),
     Netblock(NewNetblock),
     CryptoAddr(NewCryptoAddr),
+    Certificate(NewCertificate),
+    PortCertificate(NewPortCertificate),
+    SubdomainCertificate(NewSubdomainCertificate),
+    DnsRecord(NewDnsRecord),
+    Person(NewPerson),
+    Organization(NewOrganization),
+    PersonEmail(NewPersonEmail),
+    PersonAccount(NewPersonAccount),
+    PersonPhoneNumber(NewPersonPhoneNumber),
+    PersonImage(NewPersonImage),
+    PersonOrganization(NewPersonOrganization),
+    OrganizationDomain(NewOrganizationDomain),
+    Asn(NewAsn),
+    AsnNetblock(NewAsnNetblock),
+    AsnIpAddr(NewAsnIpAddr),
 }
 
 impl Insert {
@@ -61,6 +76,61 @@ impl Insert {
             Insert::Port(x) => Cow::Borrowed(&x.value),
             Insert::Netblock(x) => Cow::Borrowed(&x.value),
             Insert::CryptoAddr(x) => Cow::Borrowed(&x.value),
+            Insert::Certificate(x) => Cow::Borrowed(&x.value),
+            Insert::PortCertificate(x) => {
+                let port = Port::by_id(db, x.port_id)?;
+                let certificate = Certificate::by_id(db, x.certificate_id)?;
+                Cow::Owned(format!("{}+{}", port.value, certificate.value))
+            },
+            Insert::SubdomainCertificate(x) => {
+                let subdomain = Subdomain::by_id(db, x.subdomain_id)?;
+                let certificate = Certificate::by_id(db, x.certificate_id)?;
+                Cow::Owned(format!("{}+{}", subdomain.value, certificate.value))
+            },
+            Insert::DnsRecord(x) => Cow::Borrowed(&x.value),
+            Insert::Person(x) => Cow::Borrowed(&x.value),
+            Insert::Organization(x) => Cow::Borrowed(&x.value),
+            Insert::PersonEmail(x) => {
+                let person = Person::by_id(db, x.person_id)?;
+                let email = Email::by_id(db, x.email_id)?;
+                Cow::Owned(format!("{}+{}", person.value, email.value))
+            },
+            Insert::PersonAccount(x) => {
+                let person = Person::by_id(db, x.person_id)?;
+                let account = Account::by_id(db, x.account_id)?;
+                Cow::Owned(format!("{}+{}", person.value, account.value))
+            },
+            Insert::PersonPhoneNumber(x) => {
+                let person = Person::by_id(db, x.person_id)?;
+                let phonenumber = PhoneNumber::by_id(db, x.phonenumber_id)?;
+                Cow::Owned(format!("{}+{}", person.value, phonenumber.value))
+            },
+            Insert::PersonImage(x) => {
+                let person = Person::by_id(db, x.person_id)?;
+                let image = Image::by_id(db, x.image_id)?;
+                Cow::Owned(format!("{}+{}", person.value, image.value))
+            },
+            Insert::PersonOrganization(x) => {
+                let person = Person::by_id(db, x.person_id)?;
+                let organization = Organization::by_id(db, x.organization_id)?;
+                Cow::Owned(format!("{}+{}", person.value, organization.value))
+            },
+            Insert::OrganizationDomain(x) => {
+                let organization = Organization::by_id(db, x.organization_id)?;
+                let domain = Domain::by_id(db, x.domain_id)?;
+                Cow::Owned(format!("{}+{}", organization.value, domain.value))
+            },
+            Insert::Asn(x) => Cow::Borrowed(&x.value),
+            Insert::AsnNetblock(x) => {
+                let asn = Asn::by_id(db, x.asn_id)?;
+                let netblock = Netblock::by_id(db, x.netblock_id)?;
+                Cow::Owned(format!("{}+{}", asn.value, netblock.value))
+            },
+            Insert::AsnIpAddr(x) => {
+                let asn = Asn::by_id(db, x.asn_id)?;
+                let ipaddr = IpAddr::by_id(db, x.ip_addr_id)?;
+                Cow::Owned(format!("{}+{}", asn.value, ipaddr.value))
+            },
         };
         Ok(value)
     }
@@ -90,6 +160,21 @@ impl Insert {
             Insert::Port(_) => Family::Port.as_str(),
             Insert::Netblock(_) => Family::Netblock.as_str(),
             Insert::CryptoAddr(_) => Family::Cryptoaddr.as_str(),
+            Insert::Certificate(_) => Family::Certificate.as_str(),
+            Insert::PortCertificate(_) => Family::PortCertificate.as_str(),
+            Insert::SubdomainCertificate(_) => Family::SubdomainCertificate.as_str(),
+            Insert::DnsRecord(_) => Family::DnsRecord.as_str(),
+            Insert::Person(_) => Family::Person.as_str(),
+            Insert::Organization(_) => Family::Organization.as_str(),
+            Insert::PersonEmail(_) => Family::PersonEmail.as_str(),
+            Insert::PersonAccount(_) => Family::PersonAccount.as_str(),
+            Insert::PersonPhoneNumber(_) => Family::PersonPhonenumber.as_str(),
+            Insert::PersonImage(_) => Family::PersonImage.as_str(),
+            Insert::PersonOrganization(_) => Family::PersonOrganization.as_str(),
+            Insert::OrganizationDomain(_) => Family::OrganizationDomain.as_str(),
+            Insert::Asn(_) => Family::Asn.as_str(),
+            Insert::AsnNetblock(_) => Family::AsnNetblock.as_str(),
+            Insert::AsnIpAddr(_) => Family::AsnIpaddr.as_str(),
         }
     }
 }
@@ -114,6 +199,21 @@ impl From<&Insert> for Table {
             Insert::Port(_) => Table::Ports,
             Insert::Netblock(_) => Table::Netblocks,
             Insert::CryptoAddr(_) => Table::Cryptoaddrs,
+            Insert::Certificate(_) => Table::Certificates,
+            Insert::PortCertificate(_) => Table::PortCertificates,
+            Insert::SubdomainCertificate(_) => Table::SubdomainCertificates,
+            Insert::DnsRecord(_) => Table::DnsRecords,
+            Insert::Person(_) => Table::Persons,
+            Insert::Organization(_) => Table::Organizations,
+            Insert::PersonEmail(_) => Table::PersonEmails,
+            Insert::PersonAccount(_) => Table::PersonAccounts,
+            Insert::PersonPhoneNumber(_) => Table::PersonPhonenumbers,
+            Insert::PersonImage(_) => Table::PersonImages,
+            Insert::PersonOrganization(_) => Table::PersonOrganizations,
+            Insert::OrganizationDomain(_) => Table::OrganizationDomains,
+            Insert::Asn(_) => Table::Asns,
+            Insert::AsnNetblock(_) => Table::AsnNetblocks,
+            Insert::AsnIpAddr(_) => Table::AsnIpaddrs,
         }
     }
 }
@@ -134,6 +234,11 @@ pub enum Update {
     Port(PortUpdate),
     Netblock(NetblockUpdate),
     CryptoAddr(CryptoAddrUpdate),
+    Certificate(CertificateUpdate),
+    DnsRecord(DnsRecordUpdate),
+    Person(PersonUpdate),
+    Organization(OrganizationUpdate),
+    Asn(AsnUpdate),
 }
 
 impl Update {
@@ -153,6 +258,11 @@ impl Update {
             Update::Port(update)          => update.is_dirty(),
             Update::Netblock(update)      => update.is_dirty(),
             Update::CryptoAddr(update)    => update.is_dirty(),
+            Update::Certificate(update)   => update.is_dirty(),
+            Update::DnsRecord(update)     => update.is_dirty(),
+            Update::Person(update)        => update.is_dirty(),
+            Update::Organization(update)  => update.is_dirty(),
+            Update::Asn(update)           => update.is_dirty(),
         }
     }
 
@@ -172,9 +282,46 @@ impl Update {
             Update::Port(update)            => update.to_plain_str(),
             Update::Netblock(update)        => update.to_plain_str(),
             Update::CryptoAddr(update)      => update.to_plain_str(),
+            Update::Certificate(update)     => update.to_plain_str(),
+            Update::DnsRecord(update)       => update.to_plain_str(),
+            Update::Person(update)          => update.to_plain_str(),
+            Update::Organization(update)    => update.to_plain_str(),
+            Update::Asn(update)             => update.to_plain_str(),
         }
     }
 
+    /// The new value of every field that is changed by this update
+    pub fn fields(&self) -> Result<serde_json::Map<String, serde_json::Value>> {
+        // updates are serialized as {"Variant": {...}}
+        let update = match serde_json::to_value(self)? {
+            serde_json::Value::Object(map) => map.into_iter().next().map(|(_, v)| v),
+            _ => None,
+        };
+
+        let mut fields = serde_json::Map::new();
+        if let Some(serde_json::Value::Object(update)) = update {
+            for (key, new) in update {
+                // fields that are None aren't changed
+                if key != "id" && !new.is_null() {
+                    fields.insert(key, new);
+                }
+            }
+        }
+        Ok(fields)
+    }
+
+    /// The value every changed field had before the update has been applied
+    pub fn previous<T: Serialize>(&self, existing: &T) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let existing = serde_json::to_value(existing)?;
+        let previous = self.fields()?.into_iter()
+            .map(|(key, _)| {
+                let old = existing.get(&key).cloned().unwrap_or(serde_json::Value::Null);
+                (key, old)
+            })
+            .collect();
+        Ok(previous)
+    }
+
     pub fn to_term_str(&self) -> String {
         match self {
             Update::Subdomain(update)       => update.to_term_str(),
@@ -191,6 +338,37 @@ impl Update {
             Update::Port(update)            => update.to_term_str(),
             Update::Netblock(update)        => update.to_term_str(),
             Update::CryptoAddr(update)      => update.to_term_str(),
+            Update::Certificate(update)     => update.to_term_str(),
+            Update::DnsRecord(update)       => update.to_term_str(),
+            Update::Person(update)          => update.to_term_str(),
+            Update::Organization(update)    => update.to_term_str(),
+            Update::Asn(update)             => update.to_term_str(),
+        }
+    }
+}
+
+impl From<&Update> for Table {
+    fn from(update: &Update) -> Table {
+        match update {
+            Update::Subdomain(_) => Table::Subdomains,
+            Update::IpAddr(_) => Table::Ipaddrs,
+            Update::Url(_) => Table::Urls,
+            Update::Email(_) => Table::Emails,
+            Update::PhoneNumber(_) => Table::Phonenumbers,
+            Update::Device(_) => Table::Devices,
+            Update::Network(_) => Table::Networks,
+            Update::NetworkDevice(_) => Table::NetworkDevices,
+            Update::Account(_) => Table::Accounts,
+            Update::BreachEmail(_) => Table::BreachEmails,
+            Update::Image(_) => Table::Images,
+            Update::Port(_) => Table::Ports,
+            Update::Netblock(_) => Table::Netblocks,
+            Update::CryptoAddr(_) => Table::Cryptoaddrs,
+            Update::Certificate(_) => Table::Certificates,
+            Update::DnsRecord(_) => Table::DnsRecords,
+            Update::Person(_) => Table::Persons,
+            Update::Organization(_) => Table::Organizations,
+            Update::Asn(_) => Table::Asns,
         }
     }
 }
@@ -240,6 +418,11 @@ pub trait Model: Sized {
     }
 }
 
+/// Models that can be sorted and paginated by `select`
+pub trait Sortable: Model {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>>;
+}
+
 pub trait Scopable: Model {
     fn scoped(&self) -> bool;
 
@@ -415,6 +598,14 @@ pub trait UpdateToChangeset<T> {
     fn try_into_changeset(self) -> Result<T>;
 }
 
+/// Turn an entity back into the struct it was inserted with. Foreign keys
+/// still refer to the database the entity was loaded from.
+pub trait IntoNew {
+    type Target;
+
+    fn into_new(self) -> Self::Target;
+}
+
 mod domain;
 pub use self::domain::*;
 
@@ -466,5 +657,50 @@ pub use self::port::*;
 mod cryptoaddr;
 pub use self::cryptoaddr::*;
 
+mod certificate;
+pub use self::certificate::*;
+
+mod port_certificate;
+pub use self::port_certificate::*;
+
+mod subdomain_certificate;
+pub use self::subdomain_certificate::*;
+
+mod dns_record;
+pub use self::dns_record::*;
+
+mod person;
+pub use self::person::*;
+
+mod organization;
+pub use self::organization::*;
+
+mod person_email;
+pub use self::person_email::*;
+
+mod person_account;
+pub use self::person_account::*;
+
+mod person_phonenumber;
+pub use self::person_phonenumber::*;
+
+mod person_image;
+pub use self::person_image::*;
+
+mod person_organization;
+pub use self::person_organization::*;
+
+mod organization_domain;
+pub use self::organization_domain::*;
+
+mod asn;
+pub use self::asn::*;
+
+mod asn_netblock;
+pub use self::asn_netblock::*;
+
+mod asn_ipaddr;
+pub use self::asn_ipaddr::*;
+
 mod activity;
 pub use self::activity::*;
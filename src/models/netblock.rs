@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 
 #[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="netblocks"]
@@ -14,6 +16,8 @@ pub struct Netblock {
     pub asn: Option<i32>,
     pub as_org: Option<String>,
     pub description: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Netblock {
@@ -34,7 +38,7 @@ impl Model for Netblock {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::netblocks::dsl::*;
 
-        let query = netblocks.filter(filter.sql());
+        let query = netblocks.filter(filter.sql(Table::Netblocks)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -43,7 +47,7 @@ impl Model for Netblock {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::netblocks::dsl::*;
 
-        diesel::delete(netblocks.filter(filter.sql()))
+        diesel::delete(netblocks.filter(filter.sql(Table::Netblocks)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -93,6 +97,18 @@ impl Model for Netblock {
     }
 }
 
+impl Sortable for Netblock {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::netblocks::dsl::*;
+
+        let query = netblocks.filter(filter.sql(Table::Netblocks)?).into_boxed();
+        let results = order.apply(Table::Netblocks, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Netblock {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -109,7 +125,7 @@ impl Scopable for Netblock {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::netblocks::dsl::*;
 
-        diesel::update(netblocks.filter(filter.sql()))
+        diesel::update(netblocks.filter(filter.sql(Table::Netblocks)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -118,7 +134,7 @@ impl Scopable for Netblock {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::netblocks::dsl::*;
 
-        diesel::update(netblocks.filter(filter.sql()))
+        diesel::update(netblocks.filter(filter.sql(Table::Netblocks)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -242,6 +258,21 @@ impl Upsertable<Netblock> for NewNetblock {
     }
 }
 
+impl IntoNew for Netblock {
+    type Target = NewNetblock;
+
+    fn into_new(self) -> NewNetblock {
+        NewNetblock {
+            family: self.family,
+            value: self.value,
+            asn: self.asn,
+            as_org: self.as_org,
+            description: self.description,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableNetblock> for NewNetblock {
     fn printable(&self, _db: &Database) -> Result<PrintableNetblock> {
         Ok(PrintableNetblock {
@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 
 #[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
 #[table_name="networks"]
@@ -13,6 +15,8 @@ pub struct Network {
     pub latitude: Option<f32>,
     pub longitude: Option<f32>,
     pub description: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Network {
@@ -33,7 +37,7 @@ impl Model for Network {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::networks::dsl::*;
 
-        let query = networks.filter(filter.sql());
+        let query = networks.filter(filter.sql(Table::Networks)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -42,7 +46,7 @@ impl Model for Network {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::networks::dsl::*;
 
-        diesel::delete(networks.filter(filter.sql()))
+        diesel::delete(networks.filter(filter.sql(Table::Networks)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -92,6 +96,18 @@ impl Model for Network {
     }
 }
 
+impl Sortable for Network {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::networks::dsl::*;
+
+        let query = networks.filter(filter.sql(Table::Networks)?).into_boxed();
+        let results = order.apply(Table::Networks, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Network {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -108,7 +124,7 @@ impl Scopable for Network {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::networks::dsl::*;
 
-        diesel::update(networks.filter(filter.sql()))
+        diesel::update(networks.filter(filter.sql(Table::Networks)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -117,7 +133,7 @@ impl Scopable for Network {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::networks::dsl::*;
 
-        diesel::update(networks.filter(filter.sql()))
+        diesel::update(networks.filter(filter.sql(Table::Networks)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -254,6 +270,20 @@ impl Upsertable<Network> for NewNetwork {
     }
 }
 
+impl IntoNew for Network {
+    type Target = NewNetwork;
+
+    fn into_new(self) -> NewNetwork {
+        NewNetwork {
+            value: self.value,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            description: self.description,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableNetwork> for NewNetwork {
     fn printable(&self, _db: &Database) -> Result<PrintableNetwork> {
         Ok(PrintableNetwork {
@@ -1,6 +1,7 @@
 use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
 use chrono::NaiveDateTime;
 
@@ -14,6 +15,8 @@ pub struct NetworkDevice {
     pub device_id: i32,
     pub ipaddr: Option<String>,
     pub last_seen: Option<NaiveDateTime>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for NetworkDevice {
@@ -34,7 +37,7 @@ impl Model for NetworkDevice {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::network_devices::dsl::*;
 
-        let query = network_devices.filter(filter.sql());
+        let query = network_devices.filter(filter.sql(Table::NetworkDevices)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -43,7 +46,7 @@ impl Model for NetworkDevice {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::network_devices::dsl::*;
 
-        diesel::delete(network_devices.filter(filter.sql()))
+        diesel::delete(network_devices.filter(filter.sql(Table::NetworkDevices)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -142,6 +145,19 @@ impl Upsertable<NetworkDevice> for NewNetworkDevice {
     }
 }
 
+impl IntoNew for NetworkDevice {
+    type Target = NewNetworkDevice;
+
+    fn into_new(self) -> NewNetworkDevice {
+        NewNetworkDevice {
+            network_id: self.network_id,
+            device_id: self.device_id,
+            ipaddr: self.ipaddr,
+            last_seen: self.last_seen,
+        }
+    }
+}
+
 impl Printable<PrintableNetworkDevice> for NewNetworkDevice {
     fn printable(&self, db: &Database) -> Result<PrintableNetworkDevice> {
         let network = Network::by_id(db, self.network_id)?;
@@ -0,0 +1,351 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use crate::fmt::colors::*;
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="organizations"]
+pub struct Organization {
+    pub id: i32,
+    pub value: String,
+    pub description: Option<String>,
+    pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for Organization {
+    type ID = str;
+
+    fn to_string(&self) -> String {
+        self.value.to_owned()
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::organizations::dsl::*;
+
+        let results = organizations.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::organizations::dsl::*;
+
+        let query = organizations.filter(filter.sql(Table::Organizations)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::organizations::dsl::*;
+
+        diesel::delete(organizations.filter(filter.sql(Table::Organizations)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::organizations::dsl::*;
+
+        diesel::delete(organizations.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn value(&self) -> &Self::ID {
+        &self.value
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::organizations::dsl::*;
+
+        let organization = organizations.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(organization)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::organizations::dsl::*;
+
+        let organization = organizations.filter(value.eq(query))
+            .first::<Self>(db.db())?;
+
+        Ok(organization)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::organizations::dsl::*;
+
+        let organization = organizations.filter(value.eq(query))
+            .first::<Self>(db.db())
+            .optional()?;
+
+        Ok(organization)
+    }
+}
+
+impl Sortable for Organization {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::organizations::dsl::*;
+
+        let query = organizations.filter(filter.sql(Table::Organizations)?).into_boxed();
+        let results = order.apply(Table::Organizations, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
+impl Scopable for Organization {
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    fn set_scoped(&self, db: &Database, my_value: bool) -> Result<()> {
+        use crate::schema::organizations::dsl::*;
+        diesel::update(organizations.filter(id.eq(self.id)))
+            .set(unscoped.eq(!my_value))
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    fn scope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::organizations::dsl::*;
+
+        diesel::update(organizations.filter(filter.sql(Table::Organizations)?))
+            .set(unscoped.eq(false))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::organizations::dsl::*;
+
+        diesel::update(organizations.filter(filter.sql(Table::Organizations)?))
+            .set(unscoped.eq(true))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+}
+
+impl Organization {
+    fn domains(&self, db: &Database) -> Result<Vec<Domain>> {
+        let domain_ids = OrganizationDomain::belonging_to(self).select(organization_domains::domain_id);
+
+        domains::table
+            .filter(domains::id.eq_any(domain_ids))
+            .load::<Domain>(db.db())
+            .map_err(Error::from)
+    }
+
+    fn persons(&self, db: &Database) -> Result<Vec<Person>> {
+        let person_ids = PersonOrganization::belonging_to(self).select(person_organizations::person_id);
+
+        persons::table
+            .filter(persons::id.eq_any(person_ids))
+            .load::<Person>(db.db())
+            .map_err(Error::from)
+    }
+}
+
+pub struct PrintableOrganization {
+    value: String,
+}
+
+impl fmt::Display for PrintableOrganization {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?}", self.value)
+    }
+}
+
+impl Printable<PrintableOrganization> for Organization {
+    fn printable(&self, _db: &Database) -> Result<PrintableOrganization> {
+        Ok(PrintableOrganization {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+pub struct DetailedOrganization {
+    id: i32,
+    value: String,
+    unscoped: bool,
+    description: Option<String>,
+    domains: Vec<PrintableDomain>,
+    persons: Vec<PrintablePerson>,
+}
+
+impl DisplayableDetailed for DetailedOrganization {
+    #[inline]
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    #[inline]
+    fn print(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        w.id(self.id)?;
+        w.debug::<Green, _>(&self.value)?;
+
+        w.start_group();
+        w.opt_debug::<Yellow, _>(&self.description)?;
+        w.end_group()?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn children(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        for domain in &self.domains {
+            w.child(domain)?;
+        }
+        for person in &self.persons {
+            w.child(person)?;
+        }
+        Ok(())
+    }
+}
+
+display_detailed!(DetailedOrganization);
+
+impl Detailed for Organization {
+    type T = DetailedOrganization;
+
+    fn detailed(&self, db: &Database) -> Result<Self::T> {
+        let domains = self.domains(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let persons = self.persons(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        Ok(DetailedOrganization {
+            id: self.id,
+            value: self.value.to_string(),
+            unscoped: self.unscoped,
+            description: self.description.clone(),
+            domains,
+            persons,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[table_name="organizations"]
+pub struct NewOrganization {
+    pub value: String,
+    pub description: Option<String>,
+    pub unscoped: bool,
+}
+
+impl InsertableStruct<Organization> for NewOrganization {
+    fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn set_scoped(&mut self, scoped: bool) {
+        self.unscoped = !scoped;
+    }
+
+    fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(organizations::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
+impl Upsertable<Organization> for NewOrganization {
+    type Update = OrganizationUpdate;
+
+    fn upsert(self, existing: &Organization) -> Self::Update {
+        Self::Update {
+            id: existing.id,
+            description: Self::upsert_opt(self.description, &existing.description),
+        }
+    }
+}
+
+impl IntoNew for Organization {
+    type Target = NewOrganization;
+
+    fn into_new(self) -> NewOrganization {
+        NewOrganization {
+            value: self.value,
+            description: self.description,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
+impl Printable<PrintableOrganization> for NewOrganization {
+    fn printable(&self, _db: &Database) -> Result<PrintableOrganization> {
+        Ok(PrintableOrganization {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InsertOrganization {
+    pub value: String,
+    pub description: Option<String>,
+}
+
+impl InsertToNew for InsertOrganization {
+    type Target = NewOrganization;
+
+    fn try_into_new(self) -> Result<NewOrganization> {
+        let value = self.value.trim();
+        if value.is_empty() {
+            bail!("Value of organization can't be empty");
+        }
+
+        Ok(NewOrganization {
+            value: value.to_string(),
+            description: self.description,
+            unscoped: false,
+        })
+    }
+}
+
+#[derive(Identifiable, AsChangeset, Serialize, Deserialize, Debug)]
+#[table_name="organizations"]
+pub struct OrganizationUpdate {
+    pub id: i32,
+    pub description: Option<String>,
+}
+
+impl Upsert for OrganizationUpdate {
+    fn is_dirty(&self) -> bool {
+        self.description.is_some()
+    }
+
+    fn generic(self) -> Update {
+        Update::Organization(self)
+    }
+
+    fn apply(&self, db: &Database) -> Result<i32> {
+        db.update_organization(self)
+    }
+}
+
+impl Updateable<Organization> for OrganizationUpdate {
+    fn changeset(&mut self, existing: &Organization) {
+        Self::clear_if_equal(&mut self.description, &existing.description);
+    }
+
+    fn fmt(&self, updates: &mut Vec<String>, colors: bool) {
+        Self::push_value(updates, "description", &self.description, colors);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Organization)]
+#[belongs_to(Domain)]
+#[table_name="organization_domains"]
+pub struct OrganizationDomain {
+    pub id: i32,
+    pub organization_id: i32,
+    pub domain_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for OrganizationDomain {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("OrganizationDomain can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::organization_domains::dsl::*;
+
+        let results = organization_domains.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::organization_domains::dsl::*;
+
+        let query = organization_domains.filter(filter.sql(Table::OrganizationDomains)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::organization_domains::dsl::*;
+
+        diesel::delete(organization_domains.filter(filter.sql(Table::OrganizationDomains)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::organization_domains::dsl::*;
+
+        diesel::delete(organization_domains.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::organization_domains::dsl::*;
+
+        let organization_domain = organization_domains.filter(id.eq(my_id))
+                                                      .first::<Self>(db.db())?;
+
+        Ok(organization_domain)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::organization_domains::dsl::*;
+
+        let (my_organization_id, my_domain_id) = query;
+        let organization_domain = organization_domains.filter(organization_id.eq(my_organization_id))
+                                                      .filter(domain_id.eq(my_domain_id))
+                                                      .first::<Self>(db.db())?;
+
+        Ok(organization_domain)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::organization_domains::dsl::*;
+
+        let (my_organization_id, my_domain_id) = query;
+        let organization_domain = organization_domains.filter(organization_id.eq(my_organization_id))
+                                                      .filter(domain_id.eq(my_domain_id))
+                                                      .first::<Self>(db.db())
+                                                      .optional()?;
+
+        Ok(organization_domain)
+    }
+}
+
+pub struct PrintableOrganizationDomain {
+    organization: String,
+    domain: String,
+}
+
+impl fmt::Display for PrintableOrganizationDomain {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.organization, self.domain)
+    }
+}
+
+impl Printable<PrintableOrganizationDomain> for OrganizationDomain {
+    fn printable(&self, db: &Database) -> Result<PrintableOrganizationDomain> {
+        let organization = Organization::by_id(db, self.organization_id)?;
+        let domain = Domain::by_id(db, self.domain_id)?;
+        Ok(PrintableOrganizationDomain {
+            organization: organization.value,
+            domain: domain.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="organization_domains"]
+pub struct NewOrganizationDomain {
+    pub organization_id: i32,
+    pub domain_id: i32,
+}
+
+impl IntoNew for OrganizationDomain {
+    type Target = NewOrganizationDomain;
+
+    fn into_new(self) -> NewOrganizationDomain {
+        NewOrganizationDomain {
+            organization_id: self.organization_id,
+            domain_id: self.domain_id,
+        }
+    }
+}
+
+impl Printable<PrintableOrganizationDomain> for NewOrganizationDomain {
+    fn printable(&self, db: &Database) -> Result<PrintableOrganizationDomain> {
+        let organization = Organization::by_id(db, self.organization_id)?;
+        let domain = Domain::by_id(db, self.domain_id)?;
+        Ok(PrintableOrganizationDomain {
+            organization: organization.value,
+            domain: domain.value,
+        })
+    }
+}
+
+pub type InsertOrganizationDomain = NewOrganizationDomain;
+
+impl InsertToNew for InsertOrganizationDomain {
+    type Target = NewOrganizationDomain;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewOrganizationDomain> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,432 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use crate::fmt::colors::*;
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Serialize, Deserialize, PartialEq, Debug)]
+#[table_name="persons"]
+pub struct Person {
+    pub id: i32,
+    pub value: String,
+    pub description: Option<String>,
+    pub unscoped: bool,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for Person {
+    type ID = str;
+
+    fn to_string(&self) -> String {
+        self.value.to_owned()
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::persons::dsl::*;
+
+        let results = persons.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::persons::dsl::*;
+
+        let query = persons.filter(filter.sql(Table::Persons)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::persons::dsl::*;
+
+        diesel::delete(persons.filter(filter.sql(Table::Persons)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::persons::dsl::*;
+
+        diesel::delete(persons.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn value(&self) -> &Self::ID {
+        &self.value
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::persons::dsl::*;
+
+        let person = persons.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(person)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::persons::dsl::*;
+
+        let person = persons.filter(value.eq(query))
+            .first::<Self>(db.db())?;
+
+        Ok(person)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::persons::dsl::*;
+
+        let person = persons.filter(value.eq(query))
+            .first::<Self>(db.db())
+            .optional()?;
+
+        Ok(person)
+    }
+}
+
+impl Sortable for Person {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::persons::dsl::*;
+
+        let query = persons.filter(filter.sql(Table::Persons)?).into_boxed();
+        let results = order.apply(Table::Persons, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
+impl Scopable for Person {
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    fn set_scoped(&self, db: &Database, my_value: bool) -> Result<()> {
+        use crate::schema::persons::dsl::*;
+        diesel::update(persons.filter(id.eq(self.id)))
+            .set(unscoped.eq(!my_value))
+            .execute(db.db())?;
+        Ok(())
+    }
+
+    fn scope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::persons::dsl::*;
+
+        diesel::update(persons.filter(filter.sql(Table::Persons)?))
+            .set(unscoped.eq(false))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::persons::dsl::*;
+
+        diesel::update(persons.filter(filter.sql(Table::Persons)?))
+            .set(unscoped.eq(true))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+}
+
+impl Person {
+    fn emails(&self, db: &Database) -> Result<Vec<Email>> {
+        let email_ids = PersonEmail::belonging_to(self).select(person_emails::email_id);
+
+        emails::table
+            .filter(emails::id.eq_any(email_ids))
+            .load::<Email>(db.db())
+            .map_err(Error::from)
+    }
+
+    fn phonenumbers(&self, db: &Database) -> Result<Vec<PhoneNumber>> {
+        let phonenumber_ids = PersonPhoneNumber::belonging_to(self).select(person_phonenumbers::phonenumber_id);
+
+        phonenumbers::table
+            .filter(phonenumbers::id.eq_any(phonenumber_ids))
+            .load::<PhoneNumber>(db.db())
+            .map_err(Error::from)
+    }
+
+    fn accounts(&self, db: &Database) -> Result<Vec<Account>> {
+        let account_ids = PersonAccount::belonging_to(self).select(person_accounts::account_id);
+
+        accounts::table
+            .filter(accounts::id.eq_any(account_ids))
+            .load::<Account>(db.db())
+            .map_err(Error::from)
+    }
+
+    fn images(&self, db: &Database) -> Result<Vec<Image>> {
+        let image_ids = PersonImage::belonging_to(self).select(person_images::image_id);
+
+        images::table
+            .filter(images::id.eq_any(image_ids))
+            .load::<Image>(db.db())
+            .map_err(Error::from)
+    }
+
+    fn organizations(&self, db: &Database) -> Result<Vec<Organization>> {
+        let organization_ids = PersonOrganization::belonging_to(self).select(person_organizations::organization_id);
+
+        organizations::table
+            .filter(organizations::id.eq_any(organization_ids))
+            .load::<Organization>(db.db())
+            .map_err(Error::from)
+    }
+}
+
+pub struct PrintablePerson {
+    value: String,
+}
+
+impl fmt::Display for PrintablePerson {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?}", self.value)
+    }
+}
+
+impl Printable<PrintablePerson> for Person {
+    fn printable(&self, _db: &Database) -> Result<PrintablePerson> {
+        Ok(PrintablePerson {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+pub struct DetailedPerson {
+    id: i32,
+    value: String,
+    unscoped: bool,
+    description: Option<String>,
+    emails: Vec<PrintableEmail>,
+    phonenumbers: Vec<PrintablePhoneNumber>,
+    accounts: Vec<PrintableAccount>,
+    images: Vec<PrintableImage>,
+    organizations: Vec<PrintableOrganization>,
+}
+
+impl DisplayableDetailed for DetailedPerson {
+    #[inline]
+    fn scoped(&self) -> bool {
+        !self.unscoped
+    }
+
+    #[inline]
+    fn print(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        w.id(self.id)?;
+        w.debug::<Green, _>(&self.value)?;
+
+        w.start_group();
+        w.opt_debug::<Yellow, _>(&self.description)?;
+        w.end_group()?;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn children(&self, w: &mut fmt::DetailFormatter) -> fmt::Result {
+        for email in &self.emails {
+            w.child(email)?;
+        }
+        for phonenumber in &self.phonenumbers {
+            w.child(phonenumber)?;
+        }
+        for account in &self.accounts {
+            w.child(account)?;
+        }
+        for image in &self.images {
+            w.child(image)?;
+        }
+        for organization in &self.organizations {
+            w.child(organization)?;
+        }
+        Ok(())
+    }
+}
+
+display_detailed!(DetailedPerson);
+
+impl Detailed for Person {
+    type T = DetailedPerson;
+
+    fn detailed(&self, db: &Database) -> Result<Self::T> {
+        let emails = self.emails(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let phonenumbers = self.phonenumbers(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let accounts = self.accounts(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let images = self.images(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        let organizations = self.organizations(db)?.into_iter()
+            .map(|x| x.printable(db))
+            .collect::<Result<_>>()?;
+
+        Ok(DetailedPerson {
+            id: self.id,
+            value: self.value.to_string(),
+            unscoped: self.unscoped,
+            description: self.description.clone(),
+            emails,
+            phonenumbers,
+            accounts,
+            images,
+            organizations,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Insertable, Serialize, Deserialize)]
+#[table_name="persons"]
+pub struct NewPerson {
+    pub value: String,
+    pub description: Option<String>,
+    pub unscoped: bool,
+}
+
+impl InsertableStruct<Person> for NewPerson {
+    fn value(&self) -> &str {
+        &self.value
+    }
+
+    fn set_scoped(&mut self, scoped: bool) {
+        self.unscoped = !scoped;
+    }
+
+    fn insert(&self, db: &Database) -> Result<()> {
+        diesel::insert_into(persons::table)
+            .values(self)
+            .execute(db.db())?;
+        Ok(())
+    }
+}
+
+impl Upsertable<Person> for NewPerson {
+    type Update = PersonUpdate;
+
+    fn upsert(self, existing: &Person) -> Self::Update {
+        Self::Update {
+            id: existing.id,
+            description: Self::upsert_opt(self.description, &existing.description),
+        }
+    }
+}
+
+impl IntoNew for Person {
+    type Target = NewPerson;
+
+    fn into_new(self) -> NewPerson {
+        NewPerson {
+            value: self.value,
+            description: self.description,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
+impl Printable<PrintablePerson> for NewPerson {
+    fn printable(&self, _db: &Database) -> Result<PrintablePerson> {
+        Ok(PrintablePerson {
+            value: self.value.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InsertPerson {
+    pub value: String,
+    pub description: Option<String>,
+}
+
+impl InsertToNew for InsertPerson {
+    type Target = NewPerson;
+
+    fn try_into_new(self) -> Result<NewPerson> {
+        let value = self.value.trim();
+        if value.is_empty() {
+            bail!("Value of person can't be empty");
+        }
+
+        Ok(NewPerson {
+            value: value.to_string(),
+            description: self.description,
+            unscoped: false,
+        })
+    }
+}
+
+#[derive(Identifiable, AsChangeset, Serialize, Deserialize, Debug)]
+#[table_name="persons"]
+pub struct PersonUpdate {
+    pub id: i32,
+    pub description: Option<String>,
+}
+
+impl Upsert for PersonUpdate {
+    fn is_dirty(&self) -> bool {
+        self.description.is_some()
+    }
+
+    fn generic(self) -> Update {
+        Update::Person(self)
+    }
+
+    fn apply(&self, db: &Database) -> Result<i32> {
+        db.update_person(self)
+    }
+}
+
+impl Updateable<Person> for PersonUpdate {
+    fn changeset(&mut self, existing: &Person) {
+        Self::clear_if_equal(&mut self.description, &existing.description);
+    }
+
+    fn fmt(&self, updates: &mut Vec<String>, colors: bool) {
+        Self::push_value(updates, "description", &self.description, colors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_new() {
+        let person = InsertPerson {
+            value: " John Doe ".to_string(),
+            description: None,
+        };
+        assert_eq!(person.try_into_new().unwrap(), NewPerson {
+            value: "John Doe".to_string(),
+            description: None,
+            unscoped: false,
+        });
+    }
+
+    #[test]
+    fn test_to_new_empty() {
+        let person = InsertPerson {
+            value: " ".to_string(),
+            description: None,
+        };
+        assert!(person.try_into_new().is_err());
+    }
+}
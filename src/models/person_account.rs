@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Person)]
+#[belongs_to(Account)]
+#[table_name="person_accounts"]
+pub struct PersonAccount {
+    pub id: i32,
+    pub person_id: i32,
+    pub account_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for PersonAccount {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("PersonAccount can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::person_accounts::dsl::*;
+
+        let results = person_accounts.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::person_accounts::dsl::*;
+
+        let query = person_accounts.filter(filter.sql(Table::PersonAccounts)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::person_accounts::dsl::*;
+
+        diesel::delete(person_accounts.filter(filter.sql(Table::PersonAccounts)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::person_accounts::dsl::*;
+
+        diesel::delete(person_accounts.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::person_accounts::dsl::*;
+
+        let person_account = person_accounts.filter(id.eq(my_id))
+                                            .first::<Self>(db.db())?;
+
+        Ok(person_account)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::person_accounts::dsl::*;
+
+        let (my_person_id, my_account_id) = query;
+        let person_account = person_accounts.filter(person_id.eq(my_person_id))
+                                            .filter(account_id.eq(my_account_id))
+                                            .first::<Self>(db.db())?;
+
+        Ok(person_account)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::person_accounts::dsl::*;
+
+        let (my_person_id, my_account_id) = query;
+        let person_account = person_accounts.filter(person_id.eq(my_person_id))
+                                            .filter(account_id.eq(my_account_id))
+                                            .first::<Self>(db.db())
+                                            .optional()?;
+
+        Ok(person_account)
+    }
+}
+
+pub struct PrintablePersonAccount {
+    person: String,
+    account: String,
+}
+
+impl fmt::Display for PrintablePersonAccount {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.person, self.account)
+    }
+}
+
+impl Printable<PrintablePersonAccount> for PersonAccount {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonAccount> {
+        let person = Person::by_id(db, self.person_id)?;
+        let account = Account::by_id(db, self.account_id)?;
+        Ok(PrintablePersonAccount {
+            person: person.value,
+            account: account.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="person_accounts"]
+pub struct NewPersonAccount {
+    pub person_id: i32,
+    pub account_id: i32,
+}
+
+impl IntoNew for PersonAccount {
+    type Target = NewPersonAccount;
+
+    fn into_new(self) -> NewPersonAccount {
+        NewPersonAccount {
+            person_id: self.person_id,
+            account_id: self.account_id,
+        }
+    }
+}
+
+impl Printable<PrintablePersonAccount> for NewPersonAccount {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonAccount> {
+        let person = Person::by_id(db, self.person_id)?;
+        let account = Account::by_id(db, self.account_id)?;
+        Ok(PrintablePersonAccount {
+            person: person.value,
+            account: account.value,
+        })
+    }
+}
+
+pub type InsertPersonAccount = NewPersonAccount;
+
+impl InsertToNew for InsertPersonAccount {
+    type Target = NewPersonAccount;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewPersonAccount> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Person)]
+#[belongs_to(Email)]
+#[table_name="person_emails"]
+pub struct PersonEmail {
+    pub id: i32,
+    pub person_id: i32,
+    pub email_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for PersonEmail {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("PersonEmail can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::person_emails::dsl::*;
+
+        let results = person_emails.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::person_emails::dsl::*;
+
+        let query = person_emails.filter(filter.sql(Table::PersonEmails)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::person_emails::dsl::*;
+
+        diesel::delete(person_emails.filter(filter.sql(Table::PersonEmails)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::person_emails::dsl::*;
+
+        diesel::delete(person_emails.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::person_emails::dsl::*;
+
+        let person_email = person_emails.filter(id.eq(my_id))
+                                        .first::<Self>(db.db())?;
+
+        Ok(person_email)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::person_emails::dsl::*;
+
+        let (my_person_id, my_email_id) = query;
+        let person_email = person_emails.filter(person_id.eq(my_person_id))
+                                        .filter(email_id.eq(my_email_id))
+                                        .first::<Self>(db.db())?;
+
+        Ok(person_email)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::person_emails::dsl::*;
+
+        let (my_person_id, my_email_id) = query;
+        let person_email = person_emails.filter(person_id.eq(my_person_id))
+                                        .filter(email_id.eq(my_email_id))
+                                        .first::<Self>(db.db())
+                                        .optional()?;
+
+        Ok(person_email)
+    }
+}
+
+pub struct PrintablePersonEmail {
+    person: String,
+    email: String,
+}
+
+impl fmt::Display for PrintablePersonEmail {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.person, self.email)
+    }
+}
+
+impl Printable<PrintablePersonEmail> for PersonEmail {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonEmail> {
+        let person = Person::by_id(db, self.person_id)?;
+        let email = Email::by_id(db, self.email_id)?;
+        Ok(PrintablePersonEmail {
+            person: person.value,
+            email: email.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="person_emails"]
+pub struct NewPersonEmail {
+    pub person_id: i32,
+    pub email_id: i32,
+}
+
+impl IntoNew for PersonEmail {
+    type Target = NewPersonEmail;
+
+    fn into_new(self) -> NewPersonEmail {
+        NewPersonEmail {
+            person_id: self.person_id,
+            email_id: self.email_id,
+        }
+    }
+}
+
+impl Printable<PrintablePersonEmail> for NewPersonEmail {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonEmail> {
+        let person = Person::by_id(db, self.person_id)?;
+        let email = Email::by_id(db, self.email_id)?;
+        Ok(PrintablePersonEmail {
+            person: person.value,
+            email: email.value,
+        })
+    }
+}
+
+pub type InsertPersonEmail = NewPersonEmail;
+
+impl InsertToNew for InsertPersonEmail {
+    type Target = NewPersonEmail;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewPersonEmail> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Person)]
+#[belongs_to(Image)]
+#[table_name="person_images"]
+pub struct PersonImage {
+    pub id: i32,
+    pub person_id: i32,
+    pub image_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for PersonImage {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("PersonImage can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::person_images::dsl::*;
+
+        let results = person_images.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::person_images::dsl::*;
+
+        let query = person_images.filter(filter.sql(Table::PersonImages)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::person_images::dsl::*;
+
+        diesel::delete(person_images.filter(filter.sql(Table::PersonImages)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::person_images::dsl::*;
+
+        diesel::delete(person_images.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::person_images::dsl::*;
+
+        let person_image = person_images.filter(id.eq(my_id))
+                                        .first::<Self>(db.db())?;
+
+        Ok(person_image)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::person_images::dsl::*;
+
+        let (my_person_id, my_image_id) = query;
+        let person_image = person_images.filter(person_id.eq(my_person_id))
+                                        .filter(image_id.eq(my_image_id))
+                                        .first::<Self>(db.db())?;
+
+        Ok(person_image)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::person_images::dsl::*;
+
+        let (my_person_id, my_image_id) = query;
+        let person_image = person_images.filter(person_id.eq(my_person_id))
+                                        .filter(image_id.eq(my_image_id))
+                                        .first::<Self>(db.db())
+                                        .optional()?;
+
+        Ok(person_image)
+    }
+}
+
+pub struct PrintablePersonImage {
+    person: String,
+    image: String,
+}
+
+impl fmt::Display for PrintablePersonImage {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.person, self.image)
+    }
+}
+
+impl Printable<PrintablePersonImage> for PersonImage {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonImage> {
+        let person = Person::by_id(db, self.person_id)?;
+        let image = Image::by_id(db, self.image_id)?;
+        Ok(PrintablePersonImage {
+            person: person.value,
+            image: image.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="person_images"]
+pub struct NewPersonImage {
+    pub person_id: i32,
+    pub image_id: i32,
+}
+
+impl IntoNew for PersonImage {
+    type Target = NewPersonImage;
+
+    fn into_new(self) -> NewPersonImage {
+        NewPersonImage {
+            person_id: self.person_id,
+            image_id: self.image_id,
+        }
+    }
+}
+
+impl Printable<PrintablePersonImage> for NewPersonImage {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonImage> {
+        let person = Person::by_id(db, self.person_id)?;
+        let image = Image::by_id(db, self.image_id)?;
+        Ok(PrintablePersonImage {
+            person: person.value,
+            image: image.value,
+        })
+    }
+}
+
+pub type InsertPersonImage = NewPersonImage;
+
+impl InsertToNew for InsertPersonImage {
+    type Target = NewPersonImage;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewPersonImage> {
+        Ok(self)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Person)]
+#[belongs_to(Organization)]
+#[table_name="person_organizations"]
+pub struct PersonOrganization {
+    pub id: i32,
+    pub person_id: i32,
+    pub organization_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for PersonOrganization {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("PersonOrganization can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::person_organizations::dsl::*;
+
+        let results = person_organizations.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::person_organizations::dsl::*;
+
+        let query = person_organizations.filter(filter.sql(Table::PersonOrganizations)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::person_organizations::dsl::*;
+
+        diesel::delete(person_organizations.filter(filter.sql(Table::PersonOrganizations)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::person_organizations::dsl::*;
+
+        diesel::delete(person_organizations.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::person_organizations::dsl::*;
+
+        let person_organization = person_organizations.filter(id.eq(my_id))
+                                                      .first::<Self>(db.db())?;
+
+        Ok(person_organization)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::person_organizations::dsl::*;
+
+        let (my_person_id, my_organization_id) = query;
+        let person_organization = person_organizations.filter(person_id.eq(my_person_id))
+                                                      .filter(organization_id.eq(my_organization_id))
+                                                      .first::<Self>(db.db())?;
+
+        Ok(person_organization)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::person_organizations::dsl::*;
+
+        let (my_person_id, my_organization_id) = query;
+        let person_organization = person_organizations.filter(person_id.eq(my_person_id))
+                                                      .filter(organization_id.eq(my_organization_id))
+                                                      .first::<Self>(db.db())
+                                                      .optional()?;
+
+        Ok(person_organization)
+    }
+}
+
+pub struct PrintablePersonOrganization {
+    person: String,
+    organization: String,
+}
+
+impl fmt::Display for PrintablePersonOrganization {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.person, self.organization)
+    }
+}
+
+impl Printable<PrintablePersonOrganization> for PersonOrganization {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonOrganization> {
+        let person = Person::by_id(db, self.person_id)?;
+        let organization = Organization::by_id(db, self.organization_id)?;
+        Ok(PrintablePersonOrganization {
+            person: person.value,
+            organization: organization.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="person_organizations"]
+pub struct NewPersonOrganization {
+    pub person_id: i32,
+    pub organization_id: i32,
+}
+
+impl IntoNew for PersonOrganization {
+    type Target = NewPersonOrganization;
+
+    fn into_new(self) -> NewPersonOrganization {
+        NewPersonOrganization {
+            person_id: self.person_id,
+            organization_id: self.organization_id,
+        }
+    }
+}
+
+impl Printable<PrintablePersonOrganization> for NewPersonOrganization {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonOrganization> {
+        let person = Person::by_id(db, self.person_id)?;
+        let organization = Organization::by_id(db, self.organization_id)?;
+        Ok(PrintablePersonOrganization {
+            person: person.value,
+            organization: organization.value,
+        })
+    }
+}
+
+pub type InsertPersonOrganization = NewPersonOrganization;
+
+impl InsertToNew for InsertPersonOrganization {
+    type Target = NewPersonOrganization;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewPersonOrganization> {
+        Ok(self)
+    }
+}
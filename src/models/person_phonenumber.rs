@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Person)]
+#[belongs_to(PhoneNumber, foreign_key="phonenumber_id")]
+#[table_name="person_phonenumbers"]
+pub struct PersonPhoneNumber {
+    pub id: i32,
+    pub person_id: i32,
+    pub phonenumber_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for PersonPhoneNumber {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("PersonPhoneNumber can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        let results = person_phonenumbers.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        let query = person_phonenumbers.filter(filter.sql(Table::PersonPhonenumbers)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        diesel::delete(person_phonenumbers.filter(filter.sql(Table::PersonPhonenumbers)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        diesel::delete(person_phonenumbers.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        let person_phonenumber = person_phonenumbers.filter(id.eq(my_id))
+                                                    .first::<Self>(db.db())?;
+
+        Ok(person_phonenumber)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        let (my_person_id, my_phonenumber_id) = query;
+        let person_phonenumber = person_phonenumbers.filter(person_id.eq(my_person_id))
+                                                    .filter(phonenumber_id.eq(my_phonenumber_id))
+                                                    .first::<Self>(db.db())?;
+
+        Ok(person_phonenumber)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::person_phonenumbers::dsl::*;
+
+        let (my_person_id, my_phonenumber_id) = query;
+        let person_phonenumber = person_phonenumbers.filter(person_id.eq(my_person_id))
+                                                    .filter(phonenumber_id.eq(my_phonenumber_id))
+                                                    .first::<Self>(db.db())
+                                                    .optional()?;
+
+        Ok(person_phonenumber)
+    }
+}
+
+pub struct PrintablePersonPhoneNumber {
+    person: String,
+    phonenumber: String,
+}
+
+impl fmt::Display for PrintablePersonPhoneNumber {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.person, self.phonenumber)
+    }
+}
+
+impl Printable<PrintablePersonPhoneNumber> for PersonPhoneNumber {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonPhoneNumber> {
+        let person = Person::by_id(db, self.person_id)?;
+        let phonenumber = PhoneNumber::by_id(db, self.phonenumber_id)?;
+        Ok(PrintablePersonPhoneNumber {
+            person: person.value,
+            phonenumber: phonenumber.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="person_phonenumbers"]
+pub struct NewPersonPhoneNumber {
+    pub person_id: i32,
+    pub phonenumber_id: i32,
+}
+
+impl IntoNew for PersonPhoneNumber {
+    type Target = NewPersonPhoneNumber;
+
+    fn into_new(self) -> NewPersonPhoneNumber {
+        NewPersonPhoneNumber {
+            person_id: self.person_id,
+            phonenumber_id: self.phonenumber_id,
+        }
+    }
+}
+
+impl Printable<PrintablePersonPhoneNumber> for NewPersonPhoneNumber {
+    fn printable(&self, db: &Database) -> Result<PrintablePersonPhoneNumber> {
+        let person = Person::by_id(db, self.person_id)?;
+        let phonenumber = PhoneNumber::by_id(db, self.phonenumber_id)?;
+        Ok(PrintablePersonPhoneNumber {
+            person: person.value,
+            phonenumber: phonenumber.value,
+        })
+    }
+}
+
+pub type InsertPersonPhoneNumber = NewPersonPhoneNumber;
+
+impl InsertToNew for InsertPersonPhoneNumber {
+    type Target = NewPersonPhoneNumber;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewPersonPhoneNumber> {
+        Ok(self)
+    }
+}
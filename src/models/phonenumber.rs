@@ -1,6 +1,7 @@
 use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
+use crate::db::Table;
 use crate::models::*;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
@@ -21,6 +22,8 @@ pub struct PhoneNumber {
     pub last_ported: Option<NaiveDateTime>,
     pub caller_name: Option<String>,
     pub caller_type: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for PhoneNumber {
@@ -41,7 +44,7 @@ impl Model for PhoneNumber {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::phonenumbers::dsl::*;
 
-        let query = phonenumbers.filter(filter.sql());
+        let query = phonenumbers.filter(filter.sql(Table::Phonenumbers)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -50,7 +53,7 @@ impl Model for PhoneNumber {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::phonenumbers::dsl::*;
 
-        diesel::delete(phonenumbers.filter(filter.sql()))
+        diesel::delete(phonenumbers.filter(filter.sql(Table::Phonenumbers)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -100,6 +103,18 @@ impl Model for PhoneNumber {
     }
 }
 
+impl Sortable for PhoneNumber {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::phonenumbers::dsl::*;
+
+        let query = phonenumbers.filter(filter.sql(Table::Phonenumbers)?).into_boxed();
+        let results = order.apply(Table::Phonenumbers, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for PhoneNumber {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -116,7 +131,7 @@ impl Scopable for PhoneNumber {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::phonenumbers::dsl::*;
 
-        diesel::update(phonenumbers.filter(filter.sql()))
+        diesel::update(phonenumbers.filter(filter.sql(Table::Phonenumbers)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -125,7 +140,7 @@ impl Scopable for PhoneNumber {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::phonenumbers::dsl::*;
 
-        diesel::update(phonenumbers.filter(filter.sql()))
+        diesel::update(phonenumbers.filter(filter.sql(Table::Phonenumbers)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -277,6 +292,27 @@ impl Upsertable<PhoneNumber> for NewPhoneNumber {
     }
 }
 
+impl IntoNew for PhoneNumber {
+    type Target = NewPhoneNumber;
+
+    fn into_new(self) -> NewPhoneNumber {
+        NewPhoneNumber {
+            value: self.value,
+            name: self.name,
+            valid: self.valid,
+            last_online: self.last_online,
+            country: self.country,
+            carrier: self.carrier,
+            line: self.line,
+            is_ported: self.is_ported,
+            last_ported: self.last_ported,
+            caller_name: self.caller_name,
+            caller_type: self.caller_type,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintablePhoneNumber> for NewPhoneNumber {
     fn printable(&self, _db: &Database) -> Result<PrintablePhoneNumber> {
         Ok(PrintablePhoneNumber {
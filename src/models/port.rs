@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::Write;
 use crate::fmt::colors::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use std::net::{self, SocketAddr};
 
@@ -22,6 +24,8 @@ pub struct Port {
     pub banner: Option<String>,
     pub service: Option<String>,
     pub version: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Port {
@@ -42,7 +46,7 @@ impl Model for Port {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::ports::dsl::*;
 
-        let query = ports.filter(filter.sql());
+        let query = ports.filter(filter.sql(Table::Ports)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -51,7 +55,7 @@ impl Model for Port {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::ports::dsl::*;
 
-        diesel::delete(ports.filter(filter.sql()))
+        diesel::delete(ports.filter(filter.sql(Table::Ports)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -101,6 +105,18 @@ impl Model for Port {
     }
 }
 
+impl Sortable for Port {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::ports::dsl::*;
+
+        let query = ports.filter(filter.sql(Table::Ports)?).into_boxed();
+        let results = order.apply(Table::Ports, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Port {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -117,7 +133,7 @@ impl Scopable for Port {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::ports::dsl::*;
 
-        diesel::update(ports.filter(filter.sql()))
+        diesel::update(ports.filter(filter.sql(Table::Ports)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -126,7 +142,7 @@ impl Scopable for Port {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::ports::dsl::*;
 
-        diesel::update(ports.filter(filter.sql()))
+        diesel::update(ports.filter(filter.sql(Table::Ports)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -257,6 +273,25 @@ impl Upsertable<Port> for NewPort {
     }
 }
 
+impl IntoNew for Port {
+    type Target = NewPort;
+
+    fn into_new(self) -> NewPort {
+        NewPort {
+            ip_addr_id: self.ip_addr_id,
+            value: self.value,
+            ip_addr: self.ip_addr,
+            port: self.port,
+            protocol: self.protocol,
+            status: self.status,
+            banner: self.banner,
+            service: self.service,
+            version: self.version,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintablePort> for NewPort {
     fn printable(&self, _db: &Database) -> Result<PrintablePort> {
         Ok(PrintablePort {
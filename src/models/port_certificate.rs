@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Port)]
+#[belongs_to(Certificate)]
+#[table_name="port_certificates"]
+pub struct PortCertificate {
+    pub id: i32,
+    pub port_id: i32,
+    pub certificate_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for PortCertificate {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("PortCertificate can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::port_certificates::dsl::*;
+
+        let results = port_certificates.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::port_certificates::dsl::*;
+
+        let query = port_certificates.filter(filter.sql(Table::PortCertificates)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::port_certificates::dsl::*;
+
+        diesel::delete(port_certificates.filter(filter.sql(Table::PortCertificates)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::port_certificates::dsl::*;
+
+        diesel::delete(port_certificates.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::port_certificates::dsl::*;
+
+        let port_certificate = port_certificates.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(port_certificate)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::port_certificates::dsl::*;
+
+        let (my_port_id, my_certificate_id) = query;
+        let port_certificate = port_certificates.filter(port_id.eq(my_port_id))
+                                                .filter(certificate_id.eq(my_certificate_id))
+                                                .first::<Self>(db.db())?;
+
+        Ok(port_certificate)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::port_certificates::dsl::*;
+
+        let (my_port_id, my_certificate_id) = query;
+        let port_certificate = port_certificates.filter(port_id.eq(my_port_id))
+                                                .filter(certificate_id.eq(my_certificate_id))
+                                                .first::<Self>(db.db())
+                                                .optional()?;
+
+        Ok(port_certificate)
+    }
+}
+
+pub struct PrintablePortCertificate {
+    port: String,
+    certificate: String,
+}
+
+impl fmt::Display for PrintablePortCertificate {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.port, self.certificate)
+    }
+}
+
+impl Printable<PrintablePortCertificate> for PortCertificate {
+    fn printable(&self, db: &Database) -> Result<PrintablePortCertificate> {
+        let port = Port::by_id(db, self.port_id)?;
+        let certificate = Certificate::by_id(db, self.certificate_id)?;
+        Ok(PrintablePortCertificate {
+            port: port.value,
+            certificate: certificate.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="port_certificates"]
+pub struct NewPortCertificate {
+    pub port_id: i32,
+    pub certificate_id: i32,
+}
+
+impl IntoNew for PortCertificate {
+    type Target = NewPortCertificate;
+
+    fn into_new(self) -> NewPortCertificate {
+        NewPortCertificate {
+            port_id: self.port_id,
+            certificate_id: self.certificate_id,
+        }
+    }
+}
+
+impl Printable<PrintablePortCertificate> for NewPortCertificate {
+    fn printable(&self, db: &Database) -> Result<PrintablePortCertificate> {
+        let port = Port::by_id(db, self.port_id)?;
+        let certificate = Certificate::by_id(db, self.certificate_id)?;
+        Ok(PrintablePortCertificate {
+            port: port.value,
+            certificate: certificate.value,
+        })
+    }
+}
+
+pub type InsertPortCertificate = NewPortCertificate;
+
+impl InsertToNew for InsertPortCertificate {
+    type Target = NewPortCertificate;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewPortCertificate> {
+        Ok(self)
+    }
+}
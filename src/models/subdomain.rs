@@ -2,7 +2,9 @@ use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use crate::fmt::colors::*;
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 use std::result;
 
 #[derive(Identifiable, Queryable, Associations, Serialize, Deserialize, PartialEq, Debug)]
@@ -14,6 +16,8 @@ pub struct Subdomain {
     pub value: String,
     pub unscoped: bool,
     pub resolvable: Option<bool>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Subdomain {
@@ -34,7 +38,7 @@ impl Model for Subdomain {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::subdomains::dsl::*;
 
-        let query = subdomains.filter(filter.sql());
+        let query = subdomains.filter(filter.sql(Table::Subdomains)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -43,7 +47,7 @@ impl Model for Subdomain {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::subdomains::dsl::*;
 
-        diesel::delete(subdomains.filter(filter.sql()))
+        diesel::delete(subdomains.filter(filter.sql(Table::Subdomains)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -93,6 +97,18 @@ impl Model for Subdomain {
     }
 }
 
+impl Sortable for Subdomain {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::subdomains::dsl::*;
+
+        let query = subdomains.filter(filter.sql(Table::Subdomains)?).into_boxed();
+        let results = order.apply(Table::Subdomains, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+}
+
 impl Scopable for Subdomain {
     fn scoped(&self) -> bool {
         !self.unscoped
@@ -109,7 +125,7 @@ impl Scopable for Subdomain {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::subdomains::dsl::*;
 
-        diesel::update(subdomains.filter(filter.sql()))
+        diesel::update(subdomains.filter(filter.sql(Table::Subdomains)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -118,7 +134,7 @@ impl Scopable for Subdomain {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::subdomains::dsl::*;
 
-        diesel::update(subdomains.filter(filter.sql()))
+        diesel::update(subdomains.filter(filter.sql(Table::Subdomains)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -245,6 +261,19 @@ impl Upsertable<Subdomain> for NewSubdomain {
     }
 }
 
+impl IntoNew for Subdomain {
+    type Target = NewSubdomain;
+
+    fn into_new(self) -> NewSubdomain {
+        NewSubdomain {
+            domain_id: self.domain_id,
+            value: self.value,
+            resolvable: self.resolvable,
+            unscoped: self.unscoped,
+        }
+    }
+}
+
 impl Printable<PrintableSubdomain> for NewSubdomain {
     fn printable(&self, _db: &Database) -> Result<PrintableSubdomain> {
         Ok(PrintableSubdomain {
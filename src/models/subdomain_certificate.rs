@@ -0,0 +1,157 @@
+use crate::errors::*;
+use serde::{Serialize, Deserialize};
+use diesel::prelude::*;
+use crate::db::Table;
+use crate::models::*;
+use chrono::NaiveDateTime;
+
+#[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
+#[belongs_to(Subdomain)]
+#[belongs_to(Certificate)]
+#[table_name="subdomain_certificates"]
+pub struct SubdomainCertificate {
+    pub id: i32,
+    pub subdomain_id: i32,
+    pub certificate_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
+}
+
+impl Model for SubdomainCertificate {
+    type ID = (i32, i32);
+
+    fn to_string(&self) -> String {
+        unimplemented!("SubdomainCertificate can not be printed")
+    }
+
+    fn list(db: &Database) -> Result<Vec<Self>> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        let results = subdomain_certificates.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        let query = subdomain_certificates.filter(filter.sql(Table::SubdomainCertificates)?);
+        let results = query.load::<Self>(db.db())?;
+
+        Ok(results)
+    }
+
+    fn delete(db: &Database, filter: &Filter) -> Result<usize> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        diesel::delete(subdomain_certificates.filter(filter.sql(Table::SubdomainCertificates)?))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn delete_id(db: &Database, my_id: i32) -> Result<usize> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        diesel::delete(subdomain_certificates.filter(id.eq(my_id)))
+            .execute(db.db())
+            .map_err(Error::from)
+    }
+
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn by_id(db: &Database, my_id: i32) -> Result<Self> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        let subdomain_certificate = subdomain_certificates.filter(id.eq(my_id))
+            .first::<Self>(db.db())?;
+
+        Ok(subdomain_certificate)
+    }
+
+    fn get(db: &Database, query: &Self::ID) -> Result<Self> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        let (my_subdomain_id, my_certificate_id) = query;
+        let subdomain_certificate = subdomain_certificates.filter(subdomain_id.eq(my_subdomain_id))
+                                                          .filter(certificate_id.eq(my_certificate_id))
+                                                          .first::<Self>(db.db())?;
+
+        Ok(subdomain_certificate)
+    }
+
+    fn get_opt(db: &Database, query: &Self::ID) -> Result<Option<Self>> {
+        use crate::schema::subdomain_certificates::dsl::*;
+
+        let (my_subdomain_id, my_certificate_id) = query;
+        let subdomain_certificate = subdomain_certificates.filter(subdomain_id.eq(my_subdomain_id))
+                                                          .filter(certificate_id.eq(my_certificate_id))
+                                                          .first::<Self>(db.db())
+                                                          .optional()?;
+
+        Ok(subdomain_certificate)
+    }
+}
+
+pub struct PrintableSubdomainCertificate {
+    subdomain: String,
+    certificate: String,
+}
+
+impl fmt::Display for PrintableSubdomainCertificate {
+    fn fmt(&self, w: &mut fmt::Formatter) -> fmt::Result {
+        write!(w, "{:?} -> {:?}", self.subdomain, self.certificate)
+    }
+}
+
+impl Printable<PrintableSubdomainCertificate> for SubdomainCertificate {
+    fn printable(&self, db: &Database) -> Result<PrintableSubdomainCertificate> {
+        let subdomain = Subdomain::by_id(db, self.subdomain_id)?;
+        let certificate = Certificate::by_id(db, self.certificate_id)?;
+        Ok(PrintableSubdomainCertificate {
+            subdomain: subdomain.value,
+            certificate: certificate.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[table_name="subdomain_certificates"]
+pub struct NewSubdomainCertificate {
+    pub subdomain_id: i32,
+    pub certificate_id: i32,
+}
+
+impl IntoNew for SubdomainCertificate {
+    type Target = NewSubdomainCertificate;
+
+    fn into_new(self) -> NewSubdomainCertificate {
+        NewSubdomainCertificate {
+            subdomain_id: self.subdomain_id,
+            certificate_id: self.certificate_id,
+        }
+    }
+}
+
+impl Printable<PrintableSubdomainCertificate> for NewSubdomainCertificate {
+    fn printable(&self, db: &Database) -> Result<PrintableSubdomainCertificate> {
+        let subdomain = Subdomain::by_id(db, self.subdomain_id)?;
+        let certificate = Certificate::by_id(db, self.certificate_id)?;
+        Ok(PrintableSubdomainCertificate {
+            subdomain: subdomain.value,
+            certificate: certificate.value,
+        })
+    }
+}
+
+pub type InsertSubdomainCertificate = NewSubdomainCertificate;
+
+impl InsertToNew for InsertSubdomainCertificate {
+    type Target = NewSubdomainCertificate;
+
+    #[inline]
+    fn try_into_new(self) -> Result<NewSubdomainCertificate> {
+        Ok(self)
+    }
+}
@@ -1,7 +1,9 @@
 use crate::errors::*;
 use serde::{Serialize, Deserialize};
 use diesel::prelude::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 use std::net;
 
 #[derive(Identifiable, Queryable, Associations, Serialize, Deserialize)]
@@ -12,6 +14,8 @@ pub struct SubdomainIpAddr {
     pub id: i32,
     pub subdomain_id: i32,
     pub ip_addr_id: i32,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for SubdomainIpAddr {
@@ -32,7 +36,7 @@ impl Model for SubdomainIpAddr {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::subdomain_ipaddrs::dsl::*;
 
-        let query = subdomain_ipaddrs.filter(filter.sql());
+        let query = subdomain_ipaddrs.filter(filter.sql(Table::SubdomainIpaddrs)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -41,7 +45,7 @@ impl Model for SubdomainIpAddr {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::subdomain_ipaddrs::dsl::*;
 
-        diesel::delete(subdomain_ipaddrs.filter(filter.sql()))
+        diesel::delete(subdomain_ipaddrs.filter(filter.sql(Table::SubdomainIpaddrs)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -120,6 +124,17 @@ pub struct NewSubdomainIpAddr {
     pub ip_addr_id: i32,
 }
 
+impl IntoNew for SubdomainIpAddr {
+    type Target = NewSubdomainIpAddr;
+
+    fn into_new(self) -> NewSubdomainIpAddr {
+        NewSubdomainIpAddr {
+            subdomain_id: self.subdomain_id,
+            ip_addr_id: self.ip_addr_id,
+        }
+    }
+}
+
 impl Printable<PrintableSubdomainIpAddr> for NewSubdomainIpAddr {
     fn printable(&self, db: &Database) -> Result<PrintableSubdomainIpAddr> {
         let subdomain = Subdomain::by_id(db, self.subdomain_id)?;
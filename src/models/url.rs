@@ -3,7 +3,9 @@ use serde::{Serialize, Deserialize};
 use crate::errors::*;
 use crate::fmt::Write;
 use crate::fmt::colors::*;
+use crate::db::Table;
 use crate::models::*;
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
 #[derive(Identifiable, Queryable, Associations, Serialize, Deserialize, PartialEq, Debug)]
@@ -20,6 +22,9 @@ pub struct Url {
     pub online: Option<bool>,
     pub title: Option<String>,
     pub redirect: Option<String>,
+    pub blob: Option<String>,
+    pub first_observed: Option<NaiveDateTime>,
+    pub last_observed: Option<NaiveDateTime>,
 }
 
 impl Model for Url {
@@ -40,7 +45,7 @@ impl Model for Url {
     fn filter(db: &Database, filter: &Filter) -> Result<Vec<Self>> {
         use crate::schema::urls::dsl::*;
 
-        let query = urls.filter(filter.sql());
+        let query = urls.filter(filter.sql(Table::Urls)?);
         let results = query.load::<Self>(db.db())?;
 
         Ok(results)
@@ -49,7 +54,7 @@ impl Model for Url {
     fn delete(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::urls::dsl::*;
 
-        diesel::delete(urls.filter(filter.sql()))
+        diesel::delete(urls.filter(filter.sql(Table::Urls)?))
             .execute(db.db())
             .map_err(Error::from)
     }
@@ -97,6 +102,22 @@ impl Model for Url {
 
         Ok(url)
     }
+
+    fn blob(&self) -> Option<&str> {
+        self.blob.as_deref()
+    }
+}
+
+impl Sortable for Url {
+    fn filter_ordered(db: &Database, filter: &Filter, order: &Order) -> Result<Vec<Self>> {
+        use crate::schema::urls::dsl::*;
+
+        let query = urls.filter(filter.sql(Table::Urls)?).into_boxed();
+        let results = order.apply(Table::Urls, query)?
+            .load::<Self>(db.db())?;
+
+        Ok(results)
+    }
 }
 
 impl Scopable for Url {
@@ -115,7 +136,7 @@ impl Scopable for Url {
     fn scope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::urls::dsl::*;
 
-        diesel::update(urls.filter(filter.sql()))
+        diesel::update(urls.filter(filter.sql(Table::Urls)?))
             .set(unscoped.eq(false))
             .execute(db.db())
             .map_err(Error::from)
@@ -124,7 +145,7 @@ impl Scopable for Url {
     fn noscope(db: &Database, filter: &Filter) -> Result<usize> {
         use crate::schema::urls::dsl::*;
 
-        diesel::update(urls.filter(filter.sql()))
+        diesel::update(urls.filter(filter.sql(Table::Urls)?))
             .set(unscoped.eq(true))
             .execute(db.db())
             .map_err(Error::from)
@@ -239,6 +260,7 @@ pub struct NewUrl {
     pub title: Option<String>,
     pub redirect: Option<String>,
     pub unscoped: bool,
+    pub blob: Option<String>,
 }
 
 impl InsertableStruct<Url> for NewUrl {
@@ -269,6 +291,26 @@ impl Upsertable<Url> for NewUrl {
             online: Self::upsert_opt(self.online, &existing.online),
             title: Self::upsert_opt(self.title, &existing.title),
             redirect: Self::upsert_opt(self.redirect, &existing.redirect),
+            blob: Self::upsert_opt(self.blob, &existing.blob),
+        }
+    }
+}
+
+impl IntoNew for Url {
+    type Target = NewUrl;
+
+    fn into_new(self) -> NewUrl {
+        NewUrl {
+            subdomain_id: self.subdomain_id,
+            value: self.value,
+            path: self.path,
+            status: self.status,
+            body: self.body,
+            online: self.online,
+            title: self.title,
+            redirect: self.redirect,
+            unscoped: self.unscoped,
+            blob: self.blob,
         }
     }
 }
@@ -318,6 +360,7 @@ impl InsertToNew for InsertUrl {
             title: self.title,
             redirect,
             unscoped: false,
+            blob: None,
         })
     }
 }
@@ -331,6 +374,7 @@ pub struct UrlChangeset {
     pub online: Option<bool>,
     pub title: Option<String>,
     pub redirect: Option<String>,
+    pub blob: Option<String>,
 }
 
 impl Upsert for UrlChangeset {
@@ -339,7 +383,8 @@ impl Upsert for UrlChangeset {
         self.body.is_some() ||
         self.online.is_some() ||
         self.title.is_some() ||
-        self.redirect.is_some()
+        self.redirect.is_some() ||
+        self.blob.is_some()
     }
 
     fn generic(self) -> Update {
@@ -358,6 +403,7 @@ impl Updateable<Url> for UrlChangeset {
         Self::clear_if_equal(&mut self.body, &existing.body);
         Self::clear_if_equal(&mut self.title, &existing.title);
         Self::clear_if_equal(&mut self.redirect, &existing.redirect);
+        Self::clear_if_equal(&mut self.blob, &existing.blob);
     }
 
     fn fmt(&self, updates: &mut Vec<String>, colors: bool) {
@@ -366,6 +412,7 @@ impl Updateable<Url> for UrlChangeset {
         Self::push_raw(updates, "body", self.body.as_ref().map(|x| format!("[{} bytes]", x.len())), colors);
         Self::push_value(updates, "title", &self.title, colors);
         Self::push_value(updates, "redirect", &self.redirect, colors);
+        Self::push_value(updates, "blob", &self.blob, colors);
     }
 }
 
@@ -389,6 +436,7 @@ impl UpdateToChangeset<UrlChangeset> for UrlUpdate {
             online: self.online,
             title: self.title,
             redirect: self.redirect,
+            blob: None,
         })
     }
 }
@@ -418,6 +466,7 @@ mod tests {
             title: None,
             redirect: None,
             unscoped: false,
+            blob: None,
         });
     }
 
@@ -456,6 +505,7 @@ mod tests {
             title: None,
             redirect: Some("https://github.com/robots.txt".to_string()),
             unscoped: false,
+            blob: None,
         });
     }
 
@@ -480,6 +530,7 @@ mod tests {
             title: None,
             redirect: Some("https://example.com/".to_string()),
             unscoped: false,
+            blob: None,
         });
     }
 
@@ -504,6 +555,7 @@ mod tests {
             title: None,
             redirect: Some("https://github.com/robots.txt".to_string()),
             unscoped: false,
+            blob: None,
         });
     }
 }
@@ -104,7 +104,7 @@ pub fn exec(rl: &mut Shell, module: &Module, ratelimit: &mut Ratelimiter, option
     let args = vec![prepare_arg(notification)?];
 
     debug!("Executing notification module {:?}", module_name);
-    let errors = worker::spawn(rl, module, ratelimit, args, &params, rl.config().network.proxy, None, options);
+    let errors = worker::spawn(rl, module, ratelimit, args, &params, rl.config().network.proxy, None, options, None);
     debug!("Notification module {:?} exited with {:?} errors", module_name, errors);
 
     Ok(errors)
@@ -51,6 +51,14 @@ pub fn blobs_dir(workspace: &Workspace) -> Result<PathBuf> {
     Ok(path)
 }
 
+pub fn snapshots_dir(workspace: &Workspace) -> Result<PathBuf> {
+    let path = workspace_dir(workspace)?
+        .join("snapshots");
+    fs::create_dir_all(&path)
+        .context("Failed to create snapshots directory")?;
+    Ok(path)
+}
+
 pub fn cache_dir() -> Result<PathBuf> {
     let path = dirs_next::cache_dir()
         .ok_or_else(|| format_err!("Failed to find cache directory"))?;
@@ -2,7 +2,7 @@ use crate::errors::*;
 
 use crate::db::Family;
 use crate::engine::ctx::State;
-use crate::engine::structs;
+use crate::engine::structs::{self, LuaList};
 use crate::hlua::{self, AnyLuaValue};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -72,6 +72,51 @@ fn into_insert(family: Family, object: LuaJsonValue, state: &Arc<dyn State>) ->
         Family::Cryptoaddr => {
             Insert::CryptoAddr(try_into_new::<InsertCryptoAddr>(object, state)?)
         },
+        Family::Certificate => {
+            Insert::Certificate(try_into_new::<InsertCertificate>(object, state)?)
+        },
+        Family::PortCertificate => {
+            Insert::PortCertificate(try_into_new::<InsertPortCertificate>(object, state)?)
+        },
+        Family::SubdomainCertificate => {
+            Insert::SubdomainCertificate(try_into_new::<InsertSubdomainCertificate>(object, state)?)
+        },
+        Family::DnsRecord => {
+            Insert::DnsRecord(try_into_new::<InsertDnsRecord>(object, state)?)
+        },
+        Family::Person => {
+            Insert::Person(try_into_new::<InsertPerson>(object, state)?)
+        },
+        Family::Organization => {
+            Insert::Organization(try_into_new::<InsertOrganization>(object, state)?)
+        },
+        Family::PersonEmail => {
+            Insert::PersonEmail(try_into_new::<InsertPersonEmail>(object, state)?)
+        },
+        Family::PersonAccount => {
+            Insert::PersonAccount(try_into_new::<InsertPersonAccount>(object, state)?)
+        },
+        Family::PersonPhonenumber => {
+            Insert::PersonPhoneNumber(try_into_new::<InsertPersonPhoneNumber>(object, state)?)
+        },
+        Family::PersonImage => {
+            Insert::PersonImage(try_into_new::<InsertPersonImage>(object, state)?)
+        },
+        Family::PersonOrganization => {
+            Insert::PersonOrganization(try_into_new::<InsertPersonOrganization>(object, state)?)
+        },
+        Family::OrganizationDomain => {
+            Insert::OrganizationDomain(try_into_new::<InsertOrganizationDomain>(object, state)?)
+        },
+        Family::Asn => {
+            Insert::Asn(try_into_new::<InsertAsn>(object, state)?)
+        },
+        Family::AsnNetblock => {
+            Insert::AsnNetblock(try_into_new::<InsertAsnNetblock>(object, state)?)
+        },
+        Family::AsnIpaddr => {
+            Insert::AsnIpAddr(try_into_new::<InsertAsnIpAddr>(object, state)?)
+        },
     };
     Ok(obj)
 }
@@ -89,11 +134,12 @@ pub fn db_add(lua: &mut hlua::Lua, state: Arc<dyn State>) {
             .map_err(|e| state.set_error(e))?;
 
         let r = match r {
-            // Found is technically unreachable
+            // Found and Tags are technically unreachable
             DatabaseResponse::Inserted(id) => Some(id),
             DatabaseResponse::Updated(id) => Some(id),
             DatabaseResponse::NoChange(id) => Some(id),
             DatabaseResponse::Found(id) => Some(id),
+            DatabaseResponse::Tags(_) => None,
             DatabaseResponse::None => None,
         };
         Ok(r)
@@ -113,11 +159,12 @@ pub fn db_add_ttl(lua: &mut hlua::Lua, state: Arc<dyn State>) {
             .map_err(|e| state.set_error(e))?;
 
         let r = match r {
-            // Found is technically unreachable
+            // Found and Tags are technically unreachable
             DatabaseResponse::Inserted(id) => Some(id),
             DatabaseResponse::Updated(id) => Some(id),
             DatabaseResponse::NoChange(id) => Some(id),
             DatabaseResponse::Found(id) => Some(id),
+            DatabaseResponse::Tags(_) => None,
             DatabaseResponse::None => None,
         };
         Ok(r)
@@ -150,12 +197,56 @@ pub fn db_select(lua: &mut hlua::Lua, state: Arc<dyn State>) {
             DatabaseResponse::Updated(id) => Some(id),
             DatabaseResponse::NoChange(id) => Some(id),
             DatabaseResponse::Found(id) => Some(id),
+            DatabaseResponse::Tags(_) => None,
             DatabaseResponse::None => None,
         };
         Ok(r)
     }))
 }
 
+pub fn db_tag(lua: &mut hlua::Lua, state: Arc<dyn State>) {
+    lua.set("db_tag", hlua::function3(move |family: String, value: String, tag: String| -> Result<Option<i32>> {
+        let family = Family::from_str(&family)
+            .map_err(|e| state.set_error(e.into()))?;
+
+        let r = state.db_tag(family, value, tag)
+            .map_err(|e| state.set_error(e))?;
+
+        let r = match r {
+            // Updated, Found and Tags are technically unreachable
+            DatabaseResponse::Inserted(id) => Some(id),
+            DatabaseResponse::Updated(id) => Some(id),
+            DatabaseResponse::NoChange(id) => Some(id),
+            DatabaseResponse::Found(id) => Some(id),
+            DatabaseResponse::Tags(_) => None,
+            DatabaseResponse::None => None,
+        };
+        Ok(r)
+    }))
+}
+
+pub fn db_tags(lua: &mut hlua::Lua, state: Arc<dyn State>) {
+    lua.set("db_tags", hlua::function2(move |family: String, value: String| -> Result<AnyLuaValue> {
+        let family = Family::from_str(&family)
+            .map_err(|e| state.set_error(e.into()))?;
+
+        let r = state.db_tags(family, value)
+            .map_err(|e| state.set_error(e))?;
+
+        match r {
+            DatabaseResponse::Tags(tags) => {
+                let mut list = LuaList::new();
+                for tag in tags {
+                    list.push_str(tag);
+                }
+                Ok(list.into())
+            },
+            DatabaseResponse::None => Ok(AnyLuaValue::LuaNil),
+            r => Err(state.set_error(format_err!("Unexpected database response for db_tags: {:?}", r))),
+        }
+    }))
+}
+
 fn gen_changeset<T: Model, U: Updateable<T>>(object: LuaJsonValue, mut update: LuaJsonValue) -> Result<(i32, String, U)>
     where
         for<'de> T: serde::Deserialize<'de>,
@@ -235,15 +326,36 @@ fn run_update(state: Arc<dyn State>, family: &str, object: AnyLuaValue, update:
             .map(|(id, v, u)| (id, v, Update::Netblock(u))),
         Family::Cryptoaddr => gen_changeset::<CryptoAddr, CryptoAddrUpdate>(object, update)
             .map(|(id, v, u)| (id, v, Update::CryptoAddr(u))),
+        Family::Certificate => gen_changeset::<Certificate, CertificateUpdate>(object, update)
+            .map(|(id, v, u)| (id, v, Update::Certificate(u))),
+        Family::PortCertificate => bail!("Port-Certificate doesn't have mutable fields"),
+        Family::SubdomainCertificate => bail!("Subdomain-Certificate doesn't have mutable fields"),
+        Family::DnsRecord => gen_changeset::<DnsRecord, DnsRecordUpdate>(object, update)
+            .map(|(id, v, u)| (id, v, Update::DnsRecord(u))),
+        Family::Person => gen_changeset::<Person, PersonUpdate>(object, update)
+            .map(|(id, v, u)| (id, v, Update::Person(u))),
+        Family::Organization => gen_changeset::<Organization, OrganizationUpdate>(object, update)
+            .map(|(id, v, u)| (id, v, Update::Organization(u))),
+        Family::PersonEmail => bail!("Person-Email doesn't have mutable fields"),
+        Family::PersonAccount => bail!("Person-Account doesn't have mutable fields"),
+        Family::PersonPhonenumber => bail!("Person-PhoneNumber doesn't have mutable fields"),
+        Family::PersonImage => bail!("Person-Image doesn't have mutable fields"),
+        Family::PersonOrganization => bail!("Person-Organization doesn't have mutable fields"),
+        Family::OrganizationDomain => bail!("Organization-Domain doesn't have mutable fields"),
+        Family::Asn => gen_changeset::<Asn, AsnUpdate>(object, update)
+            .map(|(id, v, u)| (id, v, Update::Asn(u))),
+        Family::AsnNetblock => bail!("Asn-Netblock doesn't have mutable fields"),
+        Family::AsnIpaddr => bail!("Asn-IpAddr doesn't have mutable fields"),
     }?;
 
     if update.is_dirty() {
         let r = match state.db_update(family, value, update)? {
-            // Inserted and Found are technically unreachable
+            // Inserted, Found and Tags are technically unreachable
             DatabaseResponse::Inserted(id) => Some(id),
             DatabaseResponse::Updated(id) => Some(id),
             DatabaseResponse::NoChange(id) => Some(id),
             DatabaseResponse::Found(id) => Some(id),
+            DatabaseResponse::Tags(_) => None,
             DatabaseResponse::None => None,
         };
         Ok(r)
@@ -1,6 +1,6 @@
 use crate::errors::*;
 
-use crate::crt::Certificate;
+use crate::crt::CertificateInfo;
 use crate::engine::ctx::State;
 use crate::json::LuaJsonValue;
 use crate::hlua::{self, AnyLuaValue};
@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 pub fn x509_parse_pem(lua: &mut hlua::Lua, state: Arc<dyn State>) {
     lua.set("x509_parse_pem", hlua::function1(move |cert: String| -> Result<AnyLuaValue> {
-        let cert = Certificate::parse_pem(&cert)
+        let cert = CertificateInfo::parse_pem(&cert)
             .map_err(|e| state.set_error(e))?;
 
         let cert = serde_json::to_value(cert)
@@ -88,6 +88,10 @@ WUjbST4VXmdaol7uzFMojA4zkxQDZAvF5XgJlAFadfySna/teik=
             if i ~= 3 then
                 return 'Unexpected number of names'
             end
+
+            if x['fingerprint'] ~= '3111500c4a66012cdae333ec3fca1c9dde45c954440e7ee413716bff3663c074' then
+                return 'Unexpected fingerprint'
+            end
         end
         "#).expect("Failed to load script");
         script.test().expect("Script failed");
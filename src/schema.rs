@@ -12,6 +12,8 @@ table! {
         phonenumber -> Nullable<Text>,
         profile_pic -> Nullable<Text>,
         birthday -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -28,6 +30,37 @@ table! {
     }
 }
 
+table! {
+    asn_ipaddrs (id) {
+        id -> Integer,
+        asn_id -> Integer,
+        ip_addr_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    asn_netblocks (id) {
+        id -> Integer,
+        asn_id -> Integer,
+        netblock_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    asns (id) {
+        id -> Integer,
+        value -> Text,
+        as_org -> Nullable<Text>,
+        unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
 table! {
     autonoscope (id) {
         id -> Integer,
@@ -43,6 +76,8 @@ table! {
         breach_id -> Integer,
         email_id -> Integer,
         password -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -51,6 +86,25 @@ table! {
         id -> Integer,
         value -> Text,
         unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    certificates (id) {
+        id -> Integer,
+        value -> Text,
+        subject -> Nullable<Text>,
+        issuer -> Nullable<Text>,
+        sans -> Nullable<Text>,
+        serial -> Nullable<Text>,
+        key_type -> Nullable<Text>,
+        not_before -> Nullable<Timestamp>,
+        not_after -> Nullable<Timestamp>,
+        unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -66,6 +120,8 @@ table! {
         last_withdrawal -> Nullable<Timestamp>,
         unscoped -> Bool,
         description -> Nullable<Text>,
+        last_observed -> Nullable<Timestamp>,
+        first_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -78,6 +134,23 @@ table! {
         vendor -> Nullable<Text>,
         unscoped -> Bool,
         last_seen -> Nullable<Timestamp>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    dns_records (id) {
+        id -> Integer,
+        subdomain_id -> Integer,
+        value -> Text,
+        name -> Text,
+        record_type -> Text,
+        rdata -> Text,
+        ttl -> Nullable<Integer>,
+        unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -86,6 +159,8 @@ table! {
         id -> Integer,
         value -> Text,
         unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -96,6 +171,21 @@ table! {
         unscoped -> Bool,
         valid -> Nullable<Bool>,
         displayname -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    history (id) {
+        id -> Integer,
+        family -> Text,
+        key -> Integer,
+        field -> Text,
+        old -> Text,
+        new -> Text,
+        time -> Timestamp,
+        source -> Nullable<Text>,
     }
 }
 
@@ -115,6 +205,8 @@ table! {
         dhash -> Nullable<Text>,
         phash -> Nullable<Text>,
         unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -135,6 +227,8 @@ table! {
         as_org -> Nullable<Text>,
         description -> Nullable<Text>,
         reverse_dns -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -147,6 +241,8 @@ table! {
         asn -> Nullable<Integer>,
         as_org -> Nullable<Text>,
         description -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -157,6 +253,8 @@ table! {
         device_id -> Integer,
         ipaddr -> Nullable<Text>,
         last_seen -> Nullable<Timestamp>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -168,6 +266,100 @@ table! {
         latitude -> Nullable<Float>,
         longitude -> Nullable<Float>,
         description -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    notes (id) {
+        id -> Integer,
+        family -> Text,
+        key -> Integer,
+        body -> Text,
+        time -> Timestamp,
+    }
+}
+
+table! {
+    organization_domains (id) {
+        id -> Integer,
+        organization_id -> Integer,
+        domain_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    organizations (id) {
+        id -> Integer,
+        value -> Text,
+        description -> Nullable<Text>,
+        unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    person_accounts (id) {
+        id -> Integer,
+        person_id -> Integer,
+        account_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    person_emails (id) {
+        id -> Integer,
+        person_id -> Integer,
+        email_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    person_images (id) {
+        id -> Integer,
+        person_id -> Integer,
+        image_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    person_organizations (id) {
+        id -> Integer,
+        person_id -> Integer,
+        organization_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    person_phonenumbers (id) {
+        id -> Integer,
+        person_id -> Integer,
+        phonenumber_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    persons (id) {
+        id -> Integer,
+        value -> Text,
+        description -> Nullable<Text>,
+        unscoped -> Bool,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -186,6 +378,18 @@ table! {
         last_ported -> Nullable<Timestamp>,
         caller_name -> Nullable<Text>,
         caller_type -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    port_certificates (id) {
+        id -> Integer,
+        port_id -> Integer,
+        certificate_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -202,6 +406,39 @@ table! {
         banner -> Nullable<Text>,
         service -> Nullable<Text>,
         version -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    provenance (id) {
+        id -> Integer,
+        family -> Text,
+        key -> Integer,
+        run_id -> Integer,
+        action -> Text,
+        time -> Timestamp,
+        previous -> Nullable<Text>,
+    }
+}
+
+table! {
+    runs (id) {
+        id -> Integer,
+        module -> Text,
+        version -> Text,
+        started -> Timestamp,
+    }
+}
+
+table! {
+    subdomain_certificates (id) {
+        id -> Integer,
+        subdomain_id -> Integer,
+        certificate_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -210,6 +447,8 @@ table! {
         id -> Integer,
         subdomain_id -> Integer,
         ip_addr_id -> Integer,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
@@ -220,6 +459,17 @@ table! {
         value -> Text,
         unscoped -> Bool,
         resolvable -> Nullable<Bool>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    tags (id) {
+        id -> Integer,
+        family -> Text,
+        key -> Integer,
+        name -> Text,
     }
 }
 
@@ -245,14 +495,39 @@ table! {
         online -> Nullable<Bool>,
         title -> Nullable<Text>,
         redirect -> Nullable<Text>,
+        blob -> Nullable<Text>,
+        first_observed -> Nullable<Timestamp>,
+        last_observed -> Nullable<Timestamp>,
     }
 }
 
+joinable!(asn_ipaddrs -> asns (asn_id));
+joinable!(asn_ipaddrs -> ipaddrs (ip_addr_id));
+joinable!(asn_netblocks -> asns (asn_id));
+joinable!(asn_netblocks -> netblocks (netblock_id));
 joinable!(breach_emails -> breaches (breach_id));
 joinable!(breach_emails -> emails (email_id));
+joinable!(dns_records -> subdomains (subdomain_id));
 joinable!(network_devices -> devices (device_id));
 joinable!(network_devices -> networks (network_id));
+joinable!(organization_domains -> domains (domain_id));
+joinable!(organization_domains -> organizations (organization_id));
+joinable!(person_accounts -> accounts (account_id));
+joinable!(person_accounts -> persons (person_id));
+joinable!(person_emails -> emails (email_id));
+joinable!(person_emails -> persons (person_id));
+joinable!(person_images -> images (image_id));
+joinable!(person_images -> persons (person_id));
+joinable!(person_organizations -> organizations (organization_id));
+joinable!(person_organizations -> persons (person_id));
+joinable!(person_phonenumbers -> persons (person_id));
+joinable!(person_phonenumbers -> phonenumbers (phonenumber_id));
+joinable!(port_certificates -> certificates (certificate_id));
+joinable!(port_certificates -> ports (port_id));
 joinable!(ports -> ipaddrs (ip_addr_id));
+joinable!(provenance -> runs (run_id));
+joinable!(subdomain_certificates -> certificates (certificate_id));
+joinable!(subdomain_certificates -> subdomains (subdomain_id));
 joinable!(subdomain_ipaddrs -> ipaddrs (ip_addr_id));
 joinable!(subdomain_ipaddrs -> subdomains (subdomain_id));
 joinable!(subdomains -> domains (domain_id));
@@ -261,22 +536,42 @@ joinable!(urls -> subdomains (subdomain_id));
 allow_tables_to_appear_in_same_query!(
     accounts,
     activity,
+    asn_ipaddrs,
+    asn_netblocks,
+    asns,
     autonoscope,
     breach_emails,
     breaches,
+    certificates,
     cryptoaddrs,
     devices,
+    dns_records,
     domains,
     emails,
+    history,
     images,
     ipaddrs,
     netblocks,
     network_devices,
     networks,
+    notes,
+    organization_domains,
+    organizations,
+    person_accounts,
+    person_emails,
+    person_images,
+    person_organizations,
+    person_phonenumbers,
+    persons,
     phonenumbers,
+    port_certificates,
     ports,
+    provenance,
+    runs,
+    subdomain_certificates,
     subdomain_ipaddrs,
     subdomains,
+    tags,
     ttls,
     urls,
 );
@@ -35,6 +35,11 @@ impl CmdCompleter {
                 "ports",
                 "netblocks",
                 "cryptoaddrs",
+                "certificates",
+                "dns-records",
+                "persons",
+                "organizations",
+                "asns",
             ], &args[1]))
         }
     }
@@ -106,7 +111,9 @@ impl Completer for CmdCompleter {
                                         "image",
                                         "netblock",
                                         "port",
-                                        "cryptoaddr"];
+                                        "cryptoaddr",
+                                        "person",
+                                        "organization"];
 
                         let results: Vec<String> = options.iter()
                             .filter(|x| x.starts_with(arg))
@@ -143,6 +150,34 @@ impl Completer for CmdCompleter {
                     }
                 },
                 Command::Delete => self.filter("delete", &cmd),
+                Command::History => {
+                    // we can only complete the 2nd argument
+                    if args != 2 {
+                        Ok((0, vec![]))
+                    } else {
+                        Ok(filter_options("history", &[
+                            "domain",
+                            "subdomain",
+                            "ipaddr",
+                            "url",
+                            "email",
+                            "phonenumber",
+                            "device",
+                            "network",
+                            "account",
+                            "breach",
+                            "image",
+                            "port",
+                            "netblock",
+                            "cryptoaddr",
+                            "certificate",
+                            "dns-record",
+                            "person",
+                            "organization",
+                            "asn",
+                        ], &cmd[1]))
+                    }
+                },
                 Command::Keyring => {
                     match (args, cmd.get(1).map(|x| x.as_str())) {
                         (2, _) => {
@@ -211,6 +246,11 @@ impl Completer for CmdCompleter {
                                     "ports",
                                     "netblocks",
                                     "cryptoaddrs",
+                                    "certificates",
+                                    "dns-records",
+                                    "persons",
+                                    "organizations",
+                                    "asns",
                                 ], current)),
                                 "--stealth" => Ok(filter_options(line, &[
                                     "loud",
@@ -243,6 +283,28 @@ impl Completer for CmdCompleter {
                     }
                 },
                 Command::Noscope => self.filter("noscope", &cmd),
+                Command::Note => {
+                    if args != 2 {
+                        Ok((0, vec![]))
+                    } else {
+                        Ok(filter_options("note", &[
+                            "add",
+                            "list",
+                            "delete",
+                        ], &cmd[1]))
+                    }
+                },
+                Command::Tag => {
+                    if args != 2 {
+                        Ok((0, vec![]))
+                    } else {
+                        Ok(filter_options("tag", &[
+                            "add",
+                            "remove",
+                            "list",
+                        ], &cmd[1]))
+                    }
+                },
                 Command::Use => {
                     // we can only complete the 2nd argument
                     if args != 2 {
@@ -258,6 +320,16 @@ impl Completer for CmdCompleter {
                     }
                 },
                 Command::Rescope => self.filter("rescope", &cmd),
+                Command::Runs => {
+                    if args != 2 {
+                        Ok((0, vec![]))
+                    } else {
+                        Ok(filter_options("runs", &[
+                            "list",
+                            "revert",
+                        ], &cmd[1]))
+                    }
+                },
                 Command::Scope => self.filter("scope", &cmd),
                 Command::Select => self.filter("select", &cmd),
                 Command::Workspace => {
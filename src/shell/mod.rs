@@ -19,6 +19,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::term::{self, Prompt};
 use crate::paths;
 use crate::psl::{Psl, PslReader};
+use crate::geoip::{AsnDB, Maxmind, MaxmindReader};
 use crate::lazy::Lazy;
 use crate::workspaces::Workspace;
 
@@ -37,16 +38,21 @@ pub enum Command {
     Back,
     Delete,
     Help,
+    History,
     Keyring,
     Mod,
     Noscope,
+    Note,
     Pkg,
     Rescope,
     Run,
+    Runs,
     Scope,
+    Search,
     Set,
     Select,
     Stats,
+    Tag,
     Target,
     Use,
     Quickstart,
@@ -71,16 +77,21 @@ impl Command {
             Command::Delete => "delete",
             Command::Exit => "exit",
             Command::Help => "help",
+            Command::History => "history",
             Command::Keyring => "keyring",
             Command::Mod => "mod",
             Command::Noscope => "noscope",
+            Command::Note => "note",
             Command::Pkg => "pkg",
             Command::Rescope => "rescope",
             Command::Run => "run",
+            Command::Runs => "runs",
             Command::Scope => "scope",
+            Command::Search => "search",
             Command::Set => "set",
             Command::Select => "select",
             Command::Stats => "stats",
+            Command::Tag => "tag",
             Command::Target => "target",
             Command::Use => "use",
             Command::Quickstart => "quickstart",
@@ -103,15 +114,20 @@ impl Command {
                 Command::Delete.as_str(),
                 Command::Exit.as_str(),
                 Command::Help.as_str(),
+                Command::History.as_str(),
                 Command::Keyring.as_str(),
                 Command::Noscope.as_str(),
+                Command::Note.as_str(),
                 Command::Pkg.as_str(),
                 Command::Rescope.as_str(),
                 Command::Run.as_str(),
+                Command::Runs.as_str(),
                 Command::Scope.as_str(),
+                Command::Search.as_str(),
                 Command::Set.as_str(),
                 Command::Select.as_str(),
                 Command::Stats.as_str(),
+                Command::Tag.as_str(),
                 Command::Target.as_str(),
                 Command::Use.as_str(),
                 Command::Quit.as_str(),
@@ -137,16 +153,21 @@ impl FromStr for Command {
             "delete" => Ok(Command::Delete),
             "exit" => Ok(Command::Exit),
             "help" => Ok(Command::Help),
+            "history" => Ok(Command::History),
             "keyring" => Ok(Command::Keyring),
             "mod" => Ok(Command::Mod),
             "noscope" => Ok(Command::Noscope),
+            "note" => Ok(Command::Note),
             "pkg" => Ok(Command::Pkg),
             "rescope" => Ok(Command::Rescope),
             "run" => Ok(Command::Run),
+            "runs" => Ok(Command::Runs),
             "scope" => Ok(Command::Scope),
+            "search" => Ok(Command::Search),
             "set" => Ok(Command::Set),
             "select" => Ok(Command::Select),
             "stats" => Ok(Command::Stats),
+            "tag" => Ok(Command::Tag),
             "target" => Ok(Command::Target),
             "use" => Ok(Command::Use),
             "quickstart" => Ok(Command::Quickstart),
@@ -164,6 +185,7 @@ pub struct Shell<'a> {
     db: Database,
     blobs: BlobStorage,
     psl: Lazy<PslReader, Arc<Psl>>,
+    asn: Option<Lazy<MaxmindReader, Arc<AsnDB>>>,
     config: &'a Config,
     library: Library<'a>,
     keyring: KeyRing,
@@ -174,7 +196,7 @@ pub struct Shell<'a> {
 }
 
 impl<'a> Shell<'a> {
-    pub fn new(config: &'a Config, db: Database, blobs: BlobStorage, psl: PslReader, library: Library<'a>, keyring: KeyRing) -> Result<Shell<'a>> {
+    pub fn new(config: &'a Config, db: Database, blobs: BlobStorage, psl: PslReader, asn: Option<MaxmindReader>, library: Library<'a>, keyring: KeyRing) -> Result<Shell<'a>> {
         let h = CmdCompleter::default();
         let rl = Readline::with(h)?;
 
@@ -186,6 +208,7 @@ impl<'a> Shell<'a> {
             db,
             blobs,
             psl: Lazy::from(psl),
+            asn: asn.map(Lazy::from),
             config,
             library,
             keyring,
@@ -242,7 +265,7 @@ impl<'a> Shell<'a> {
     pub fn scoped_targets(&self) -> db::Filter {
         match &self.prompt.target {
             Some(filter) => filter.and_scoped(),
-            _ => db::Filter::new("unscoped=0"),
+            _ => db::Filter::any().and_scoped(),
         }
     }
 
@@ -277,6 +300,14 @@ impl<'a> Shell<'a> {
         Ok(self.psl.get()?)
     }
 
+    /// Returns None if the asn database isn't installed
+    pub fn asn(&mut self) -> Result<Option<&Arc<AsnDB>>> {
+        match &mut self.asn {
+            Some(asn) => Ok(Some(&*asn.get()?)),
+            None => Ok(None),
+        }
+    }
+
     #[inline(always)]
     pub fn config(&self) -> &Config {
         self.config
@@ -517,19 +548,24 @@ pub fn run_once(rl: &mut Shell) -> Result<bool> {
         },
         Some((Command::Delete, args)) => delete_cmd::run(rl, &args)?,
         Some((Command::Help, args)) => help_cmd::run(rl, &args)?,
+        Some((Command::History, args)) => cmd::<history_cmd::Args>(rl, &args)?,
         Some((Command::Keyring, args)) => keyring_cmd::run(rl, &args)?,
         Some((Command::Mod, args)) => {
             term::warn("The \x1b[1mmod\x1b[0m command is deprecated, use \x1b[1mpkg\x1b[0m");
             cmd::<pkg_cmd::ArgsInteractive>(rl, &args)?
         },
         Some((Command::Noscope, args)) => noscope_cmd::run(rl, &args)?,
+        Some((Command::Note, args)) => cmd::<note_cmd::Args>(rl, &args)?,
         Some((Command::Pkg, args)) => cmd::<pkg_cmd::ArgsInteractive>(rl, &args)?,
         Some((Command::Rescope, args)) => cmd::<rescope_cmd::Args>(rl, &args)?,
         Some((Command::Run, args)) => cmd::<run_cmd::Args>(rl, &args)?,
+        Some((Command::Runs, args)) => cmd::<runs_cmd::Args>(rl, &args)?,
         Some((Command::Scope, args)) => scope_cmd::run(rl, &args)?,
+        Some((Command::Search, args)) => cmd::<search_cmd::Args>(rl, &args)?,
         Some((Command::Set, args)) => set_cmd::run(rl, &args)?,
         Some((Command::Select, args)) => cmd::<select_cmd::Args>(rl, &args)?,
         Some((Command::Stats, args)) => cmd::<stats_cmd::Args>(rl, &args)?,
+        Some((Command::Tag, args)) => cmd::<tag_cmd::Args>(rl, &args)?,
         Some((Command::Target, args)) => target_cmd::run(rl, &args)?,
         Some((Command::Use, args)) => use_cmd::run(rl, &args)?,
         Some((Command::Quickstart, args)) => quickstart_cmd::run(rl, &args)?,
@@ -567,6 +603,7 @@ pub fn init<'a>(args: &Args, config: &'a Config, verbose_init: bool) -> Result<S
     let psl = PslReader::open_or_download(&cache_dir,
             |cb| worker::spawn_fn("Downloading public suffix list", cb, false))
         .context("Failed to download public suffix list")?;
+    let asn = AsnDB::try_open_reader(&cache_dir)?;
     let library = Library::new(verbose_init, config)?;
     let keyring = KeyRing::init()?;
 
@@ -581,7 +618,7 @@ pub fn init<'a>(args: &Args, config: &'a Config, verbose_init: bool) -> Result<S
     }
     autoupdate.check_background(config, library.list());
 
-    let mut rl = Shell::new(config, db, blobs, psl, library, keyring)?;
+    let mut rl = Shell::new(config, db, blobs, psl, asn, library, keyring)?;
 
     ttl::reap_expired(&mut rl)?;
 
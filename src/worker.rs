@@ -4,7 +4,10 @@ use serde::{Serialize, Deserialize};
 use crate::blobs::Blob;
 use crate::channel;
 use crate::cmd::run_cmd::Params;
-use crate::db::{DbChange, Family};
+use crate::db::{Database, DbChange, Family, Previous, Table};
+use crate::db::history::History;
+use crate::db::provenance::{Action, Provenance, Run};
+use crate::db::tags::Tag;
 use crate::db::ttl::Ttl;
 use crate::engine::Module;
 use crate::ipc;
@@ -21,7 +24,7 @@ use std::sync::{mpsc, Arc, Mutex};
 use std::time::Duration;
 use std::thread;
 use std::io::{Stdin, Read, BufRead, BufReader};
-use std::net::SocketAddr;
+use std::net::{self, SocketAddr};
 use crate::term::{Spinner, StackedSpinners, SpinLogger};
 use threadpool::ThreadPool;
 
@@ -35,6 +38,7 @@ pub enum DatabaseResponse {
     Updated(i32),
     Found(i32),
     NoChange(i32),
+    Tags(Vec<String>),
     None,
 }
 
@@ -159,6 +163,8 @@ pub enum DatabaseEvent {
     Activity(NewActivity),
     Select((Family, String)),
     Update((Family, String, Update)),
+    Tag((Family, String, String)),
+    Tags((Family, String)),
 }
 
 impl EventWithCallback for DatabaseEvent {
@@ -211,6 +217,27 @@ impl DatabaseEvent {
         Self::notify(rl, spinner, ratelimit, &topic, subject);
     }
 
+    fn provenance<T: SpinLogger>(rl: &Shell, spinner: &mut T, run: Option<&Run>, family: Table, key: i32, action: Action, previous: Option<&Previous>) {
+        if let Some(run) = run {
+            if let Err(err) = Provenance::record(rl.db(), family, key, run.id, action, previous) {
+                spinner.error(&format!("Failed to record provenance: {:?}", err));
+            }
+        }
+    }
+
+    fn history<T: SpinLogger>(rl: &Shell, spinner: &mut T, run: Option<&Run>, family: Table, key: i32, update: &Update, previous: &Previous) {
+        let source = run.map(|run| run.module.as_str());
+        if let Err(err) = History::record(rl.db(), family, key, update, previous, source) {
+            spinner.error(&format!("Failed to record history: {:?}", err));
+        }
+    }
+
+    fn seen<T: SpinLogger>(rl: &Shell, spinner: &mut T, family: Table, key: i32) {
+        if let Err(err) = rl.db().seen(family, key) {
+            spinner.error(&format!("Failed to update seen timestamps: {:?}", err));
+        }
+    }
+
     fn spinner_log_new_activity<T: SpinLogger>(spinner: &mut T, object: &NewActivity, verbose: u8) {
         let mut log = format!("{:?} ", object.topic);
         if let Some(uniq) = &object.uniq {
@@ -233,7 +260,9 @@ impl DatabaseEvent {
         spinner.log(&log);
     }
 
-    fn insert<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, object: Insert, ttl: Option<i32>, tx: DbSender, verbose: u8) {
+    fn insert<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, mut object: Insert, ttl: Option<i32>, tx: DbSender, verbose: u8, run: Option<&Run>) {
+        Self::asn_lookup(rl, &mut object);
+
         let db = rl.db();
         if verbose >= 1 {
             spinner.debug(&format!("Inserting: {:?}", object));
@@ -244,6 +273,9 @@ impl DatabaseEvent {
 
         let result = match result {
             Ok(Some((DbChange::Insert, id))) => {
+                Self::provenance(rl, spinner, run, Table::from(&object), id, Action::Insert, None);
+                Self::seen(rl, spinner, Table::from(&object), id);
+
                 match object.value(rl.db()) {
                     Ok(value) => {
                         if let Some(ttl) = ttl {
@@ -259,9 +291,16 @@ impl DatabaseEvent {
                     }
                 }
 
+                Self::certificate_names(rl, spinner, ratelimit, &object, id, verbose, run);
+                Self::asn_links(rl, spinner, ratelimit, &object, id, verbose, run);
+
                 Ok(DatabaseResponse::Inserted(id))
             },
-            Ok(Some((DbChange::Update(update), id))) => {
+            Ok(Some((DbChange::Update(update, previous), id))) => {
+                Self::provenance(rl, spinner, run, Table::from(&object), id, Action::Update, Some(&previous));
+                Self::history(rl, spinner, run, Table::from(&object), id, &update, &previous);
+                Self::seen(rl, spinner, Table::from(&object), id);
+
                 if let Some(ttl) = ttl {
                     if let Err(err) = Ttl::bump(&object, id, ttl, db) {
                         spinner.error(&format!("Failed to set ttl: {:?}", err));
@@ -276,9 +315,14 @@ impl DatabaseEvent {
                     },
                 }
 
+                Self::certificate_names(rl, spinner, ratelimit, &object, id, verbose, run);
+                Self::asn_links(rl, spinner, ratelimit, &object, id, verbose, run);
+
                 Ok(DatabaseResponse::Updated(id))
             },
             Ok(Some((DbChange::None, id))) => {
+                Self::seen(rl, spinner, Table::from(&object), id);
+
                 if let Some(ttl) = ttl {
                     if let Err(err) = Ttl::bump(&object, id, ttl, db) {
                         spinner.error(&format!("Failed to set ttl: {:?}", err));
@@ -298,6 +342,145 @@ impl DatabaseEvent {
         tx.send(result).expect("Failed to send db result to channel");
     }
 
+    /// Insert an entity that is derived from another entity, returns None if
+    /// it's out of scope or failed
+    fn insert_derived<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, object: Insert, verbose: u8, run: Option<&Run>) -> Option<i32> {
+        let (tx, rx) = mpsc::channel();
+        Self::insert(rl, spinner, ratelimit, object, None, tx, verbose, run);
+        match rx.recv() {
+            Ok(Ok(DatabaseResponse::Inserted(id))) => Some(id),
+            Ok(Ok(DatabaseResponse::Updated(id))) => Some(id),
+            Ok(Ok(DatabaseResponse::Found(id))) => Some(id),
+            Ok(Ok(DatabaseResponse::NoChange(id))) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Add the dns names of a certificate as subdomains and link them to the
+    /// certificate, only for domains that already exist and are in scope
+    fn certificate_names<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, object: &Insert, certificate_id: i32, verbose: u8, run: Option<&Run>) {
+        let certificate = match object {
+            Insert::Certificate(certificate) => certificate,
+            _ => return,
+        };
+
+        for name in certificate.names() {
+            // a wildcard certificate is also valid for the name itself
+            let name = name.strip_prefix("*.").unwrap_or(name);
+
+            let dns_name = match rl.psl().and_then(|psl| psl.parse_dns_name(name)) {
+                Ok(dns_name) => dns_name,
+                Err(err) => {
+                    debug!("Skipping certificate name {:?}: {}", name, err);
+                    continue;
+                },
+            };
+
+            // names under domains we don't know about never create a new domain
+            let domain_id = match Domain::get_opt(rl.db(), &dns_name.root) {
+                Ok(Some(domain)) if domain.scoped() => domain.id,
+                Ok(_) => continue,
+                Err(err) => {
+                    spinner.error(&format!("Failed to look up domain {:?}: {:?}", dns_name.root, err));
+                    continue;
+                },
+            };
+
+            let subdomain_id = match Self::insert_derived(rl, spinner, ratelimit, Insert::Subdomain(NewSubdomain {
+                domain_id,
+                value: name.to_string(),
+                resolvable: None,
+                unscoped: false,
+            }), verbose, run) {
+                Some(subdomain_id) => subdomain_id,
+                None => continue,
+            };
+
+            Self::insert_derived(rl, spinner, ratelimit, Insert::SubdomainCertificate(NewSubdomainCertificate {
+                subdomain_id,
+                certificate_id,
+            }), verbose, run);
+        }
+    }
+
+
+    /// Fill in the autonomous system of ipaddrs and netblocks that don't have one yet
+    fn asn_lookup(rl: &mut Shell, object: &mut Insert) {
+        let ip = match asn_lookup_ip(rl.db(), object) {
+            Ok(Some(ip)) => ip,
+            Ok(None) => return,
+            Err(err) => {
+                debug!("Failed to check for existing asn: {}", err);
+                return;
+            },
+        };
+
+        let (asn, as_org) = match object {
+            Insert::IpAddr(ipaddr) => (&mut ipaddr.asn, &mut ipaddr.as_org),
+            Insert::Netblock(netblock) => (&mut netblock.asn, &mut netblock.as_org),
+            _ => return,
+        };
+
+        let lookup = match rl.asn() {
+            Ok(Some(db)) => db.lookup(ip),
+            // the asn database isn't installed
+            Ok(None) => return,
+            Err(err) => Err(err),
+        };
+
+        match lookup {
+            Ok(lookup) => {
+                *asn = i32::try_from(lookup.asn).ok();
+                if as_org.is_none() {
+                    *as_org = Some(lookup.as_org);
+                }
+            },
+            Err(err) => debug!("Failed to lookup asn for {:?}: {}", ip, err),
+        }
+    }
+
+    /// Add the autonomous system of an ipaddr or netblock and link them to it
+    fn asn_links<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, object: &Insert, id: i32, verbose: u8, run: Option<&Run>) {
+        let (asn, as_org) = match object {
+            Insert::IpAddr(ipaddr) => (ipaddr.asn, &ipaddr.as_org),
+            Insert::Netblock(netblock) => (netblock.asn, &netblock.as_org),
+            _ => return,
+        };
+
+        let asn = match asn.and_then(|x| u32::try_from(x).ok()) {
+            Some(asn) => asn,
+            None => return,
+        };
+
+        let asn = InsertAsn {
+            asn,
+            as_org: as_org.clone(),
+        };
+        let asn = match asn.try_into_new() {
+            Ok(asn) => asn,
+            Err(err) => {
+                spinner.error(&format!("Failed to create asn: {}", err));
+                return;
+            },
+        };
+
+        let asn_id = match Self::insert_derived(rl, spinner, ratelimit, Insert::Asn(asn), verbose, run) {
+            Some(asn_id) => asn_id,
+            None => return,
+        };
+
+        let link = match object {
+            Insert::IpAddr(_) => Insert::AsnIpAddr(NewAsnIpAddr {
+                asn_id,
+                ip_addr_id: id,
+            }),
+            _ => Insert::AsnNetblock(NewAsnNetblock {
+                asn_id,
+                netblock_id: id,
+            }),
+        };
+        Self::insert_derived(rl, spinner, ratelimit, link, verbose, run);
+    }
 
     pub fn activity<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, object: NewActivity, tx: DbSender, verbose: u8) {
         let db = rl.db();
@@ -320,17 +503,24 @@ impl DatabaseEvent {
         tx.send(result).expect("Failed to send db result to channel");
     }
 
-    pub fn update<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, family: &str, value: &str, update: &Update, tx: DbSender, verbose: u8) {
+    pub fn update<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, family: &str, value: &str, update: &Update, tx: DbSender, verbose: u8, run: Option<&Run>) {
         let db = rl.db();
         if verbose >= 1 {
             spinner.debug(&format!("Updating: {:?}", update));
         }
 
-        let result = db.update_generic(update);
+        // keep the old values so the update can be reverted
+        let result = db.previous_generic(update)
+            .and_then(|previous| {
+                let id = db.update_generic(update)?;
+                Ok((id, previous))
+            });
         debug!("{:?}: {:?} => {:?}", value, update, result);
 
         let result = match result {
-            Ok(id) => {
+            Ok((id, previous)) => {
+                Self::provenance(rl, spinner, run, Table::from(update), id, Action::Update, Some(&previous));
+                Self::history(rl, spinner, run, Table::from(update), id, update, &previous);
                 Self::on_update(rl, spinner, ratelimit, family, value, update);
                 Ok(DatabaseResponse::Updated(id))
             },
@@ -344,10 +534,34 @@ impl DatabaseEvent {
         tx.send(result).expect("Failed to send db result to channel");
     }
 
-    pub fn apply<T: SpinLogger>(self, rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, tx: DbSender, verbose: u8) {
+    pub fn tag<T: SpinLogger>(rl: &mut Shell, spinner: &mut T, family: &Family, value: &str, tag: &str, tx: DbSender) {
+        let db = rl.db();
+        let result = db.get_opt(family, value)
+            .and_then(|id| match id {
+                Some(id) => if Tag::add(db, Table::from(family), id, tag)? {
+                    Ok(DatabaseResponse::Inserted(id))
+                } else {
+                    Ok(DatabaseResponse::NoChange(id))
+                },
+                None => Ok(DatabaseResponse::None),
+            });
+        debug!("Tagging {:?} with {:?} => {:?}", value, tag, result);
+
+        let result = result.map_err(|err| {
+            let err = err.to_string();
+            spinner.error(&err);
+            err
+        });
+
+        tx.send(result).expect("Failed to send db result to channel");
+    }
+
+    /// Apply a database event, if it has been sent by a module run the
+    /// changes are recorded as provenance of that run
+    pub fn apply<T: SpinLogger>(self, rl: &mut Shell, spinner: &mut T, ratelimit: &mut Ratelimiter, tx: DbSender, verbose: u8, run: Option<&Run>) {
         match self {
-            DatabaseEvent::Insert(object) => Self::insert(rl, spinner, ratelimit, object, None, tx, verbose),
-            DatabaseEvent::InsertTtl((object, ttl)) => Self::insert(rl, spinner, ratelimit, object, Some(ttl), tx, verbose),
+            DatabaseEvent::Insert(object) => Self::insert(rl, spinner, ratelimit, object, None, tx, verbose, run),
+            DatabaseEvent::InsertTtl((object, ttl)) => Self::insert(rl, spinner, ratelimit, object, Some(ttl), tx, verbose, run),
             DatabaseEvent::Activity(object) => Self::activity(rl, spinner, ratelimit, object, tx, verbose),
             DatabaseEvent::Select((family, value)) => {
                 let db = rl.db();
@@ -359,7 +573,19 @@ impl DatabaseEvent {
 
                 tx.send(result).expect("Failed to send db result to channel");
             },
-            DatabaseEvent::Update((family, value, update)) => Self::update(rl, spinner, ratelimit, family.as_str(), &value, &update, tx, verbose),
+            DatabaseEvent::Update((family, value, update)) => Self::update(rl, spinner, ratelimit, family.as_str(), &value, &update, tx, verbose, run),
+            DatabaseEvent::Tag((family, value, tag)) => Self::tag(rl, spinner, &family, &value, &tag, tx),
+            DatabaseEvent::Tags((family, value)) => {
+                let db = rl.db();
+                let result = db.get_opt(&family, &value)
+                    .and_then(|id| match id {
+                        Some(id) => Ok(DatabaseResponse::Tags(Tag::names(db, Table::from(&family), id)?)),
+                        None => Ok(DatabaseResponse::None),
+                    })
+                    .map_err(|e| e.to_string());
+
+                tx.send(result).expect("Failed to send db result to channel");
+            },
         }
     }
 }
@@ -438,6 +664,31 @@ impl RatelimitEvent {
     }
 }
 
+/// The address an asn should be looked up for. Entities that already have an
+/// asn in the database are skipped, a module might have reported it and a
+/// local lookup must not overwrite it.
+fn asn_lookup_ip(db: &Database, object: &Insert) -> Result<Option<net::IpAddr>> {
+    let (ip, asn, stored) = match object {
+        Insert::IpAddr(ipaddr) => {
+            let ip = ipaddr.value.parse::<net::IpAddr>().ok();
+            let stored = IpAddr::get_opt(db, &ipaddr.value)?.and_then(|x| x.asn);
+            (ip, ipaddr.asn, stored)
+        },
+        Insert::Netblock(netblock) => {
+            let ip = netblock.value.parse::<ipnetwork::IpNetwork>().ok().map(|x| x.ip());
+            let stored = Netblock::get_opt(db, &netblock.value)?.and_then(|x| x.asn);
+            (ip, netblock.asn, stored)
+        },
+        _ => return Ok(None),
+    };
+
+    if asn.is_some() || stored.is_some() {
+        Ok(None)
+    } else {
+        Ok(ip)
+    }
+}
+
 pub fn spawn(rl: &mut Shell,
              module: &Module,
              ratelimit: &mut Ratelimiter,
@@ -446,6 +697,7 @@ pub fn spawn(rl: &mut Shell,
              proxy: Option<SocketAddr>,
              user_agent: Option<String>,
              options: HashMap<String, String>,
+             run: Option<&Run>,
 ) -> usize {
     // This function hangs if args is empty, so return early if that's the case
     if args.is_empty() {
@@ -513,7 +765,7 @@ pub fn spawn(rl: &mut Shell,
                         Event2::Log(log) => log.apply(&mut stack.prefixed(name)),
                         Event2::Database(tuple) => {
                             let (db, tx) = *tuple;
-                            db.apply(rl, &mut stack.prefixed(name), ratelimit, tx, verbose)
+                            db.apply(rl, &mut stack.prefixed(name), ratelimit, tx, verbose, run)
                         },
                         Event2::Ratelimit((req, tx)) => ratelimit.pass(tx, &req.key, req.passes, req.time),
                         Event2::Blob((blob, tx)) => rl.store_blob(tx, &blob),
@@ -688,3 +940,52 @@ pub fn spawn_multi<T: Task, F>(tasks: Vec<T>, mut done_fn: F, threads: usize) ->
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_ipaddr(value: &str, asn: Option<i32>) -> Insert {
+        Insert::IpAddr(NewIpAddr {
+            family: String::from("4"),
+            value: value.to_string(),
+            continent: None,
+            continent_code: None,
+            country: None,
+            country_code: None,
+            city: None,
+            latitude: None,
+            longitude: None,
+            asn,
+            as_org: None,
+            description: None,
+            reverse_dns: None,
+            unscoped: false,
+        })
+    }
+
+    #[test]
+    fn test_asn_lookup_new() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+
+        let ip = asn_lookup_ip(&db, &new_ipaddr("192.0.2.1", None)).unwrap();
+        assert_eq!(ip, Some("192.0.2.1".parse().unwrap()));
+        let ip = asn_lookup_ip(&db, &new_ipaddr("192.0.2.1", Some(64496))).unwrap();
+        assert_eq!(ip, None);
+    }
+
+    #[test]
+    fn test_asn_lookup_existing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = Database::establish_tmp(dir.path()).unwrap();
+        db.insert_generic(new_ipaddr("192.0.2.1", Some(64496))).unwrap();
+        db.insert_generic(new_ipaddr("192.0.2.2", None)).unwrap();
+
+        // the asn a module reported is kept
+        let ip = asn_lookup_ip(&db, &new_ipaddr("192.0.2.1", None)).unwrap();
+        assert_eq!(ip, None);
+        let ip = asn_lookup_ip(&db, &new_ipaddr("192.0.2.2", None)).unwrap();
+        assert_eq!(ip, Some("192.0.2.2".parse().unwrap()));
+    }
+}
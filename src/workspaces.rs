@@ -2,6 +2,7 @@ use crate::errors::*;
 
 use crate::blobs::BlobStorage;
 use crate::paths;
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use lazy_static::lazy_static;
 use std::collections::HashSet;
@@ -10,6 +11,13 @@ use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
 
+fn is_valid_name(s: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9\._\-]*[a-zA-Z0-9])?$").unwrap();
+    }
+    RE.is_match(s)
+}
+
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Workspace {
@@ -77,6 +85,117 @@ impl Workspace {
 
         Ok(())
     }
+
+    /// Prepare a new, empty snapshot directory
+    pub fn new_snapshot(&self, name: &str) -> Result<Snapshot> {
+        let snapshot = Snapshot::from_name(self, name)?;
+        if snapshot.path.exists() {
+            bail!("Snapshot already exists: {:?}", name);
+        }
+        fs::create_dir(&snapshot.path)
+            .context("Failed to create snapshot directory")?;
+        Ok(snapshot)
+    }
+
+    pub fn snapshot(&self, name: &str) -> Result<Snapshot> {
+        let snapshot = Snapshot::from_name(self, name)?;
+        if !snapshot.db_path().exists() {
+            bail!("Snapshot doesn't exist: {:?}", name);
+        }
+        Ok(snapshot)
+    }
+
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        let mut snapshots = Vec::new();
+
+        for entry in fs::read_dir(paths::snapshots_dir(self)?)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_str()
+                .ok_or_else(|| format_err!("Snapshot has invalid name: {:?}", name))?;
+
+            if let Ok(snapshot) = self.snapshot(name) {
+                snapshots.push(snapshot);
+            }
+        }
+
+        snapshots.sort();
+        Ok(snapshots)
+    }
+}
+
+/// A point-in-time copy of the database and blobs of a workspace
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Snapshot {
+    workspace: Workspace,
+    name: String,
+    path: PathBuf,
+}
+
+impl Snapshot {
+    fn from_name(workspace: &Workspace, name: &str) -> Result<Snapshot> {
+        if !is_valid_name(name) {
+            bail!("Snapshot name contains invalid characters")
+        }
+
+        let path = paths::snapshots_dir(workspace)?
+            .join(name);
+
+        Ok(Snapshot {
+            workspace: workspace.clone(),
+            name: name.to_string(),
+            path,
+        })
+    }
+
+    #[inline(always)]
+    pub fn workspace(&self) -> &Workspace {
+        &self.workspace
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn db_path(&self) -> PathBuf {
+        self.path.join("db.sqlite")
+    }
+
+    #[inline]
+    pub fn blobs(&self) -> BlobStorage {
+        BlobStorage::new(self.path.join("blobs"))
+    }
+
+    pub fn created(&self) -> Result<DateTime<Utc>> {
+        let mtime = fs::metadata(self.db_path())?
+            .modified()?;
+        Ok(DateTime::from(mtime))
+    }
+
+    #[inline]
+    pub fn usage_human(&self) -> Result<String> {
+        let usage = self.usage()?;
+        Ok(humansize::format_size(usage, humansize::BINARY))
+    }
+
+    pub fn usage(&self) -> Result<u64> {
+        let blobs = self.blobs();
+
+        let mut sum = fs::metadata(self.db_path())?.len();
+        for id in blobs.list()? {
+            sum += blobs.stat(&id)?;
+        }
+
+        Ok(sum)
+    }
+
+    /// Remove a snapshot that couldn't be completed
+    pub(crate) fn discard(self) -> Result<()> {
+        fs::remove_dir_all(&self.path)?;
+        Ok(())
+    }
 }
 
 impl FromStr for Workspace {
@@ -87,10 +206,7 @@ impl FromStr for Workspace {
             bail!("Workspace can't be empty")
         }
 
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9\._\-]*[a-zA-Z0-9])?$").unwrap();
-        }
-        if !RE.is_match(s) {
+        if !is_valid_name(s) {
             bail!("Workspace contains invalid characters")
         }
 